@@ -1,19 +1,78 @@
-use crate::{player::Players, world::World};
+use crate::{
+    player::{Player, Players},
+    world::World,
+};
 
+mod alias;
+mod areas;
 mod communication;
 mod debug;
+mod equipment;
+mod group;
 mod look;
+mod map;
 mod movement;
+mod objects;
+mod olc;
+mod position;
+mod score;
+mod shop;
+mod skills;
+mod track;
 
+pub use alias::*;
+pub use areas::*;
 pub use communication::*;
 pub use debug::*;
+pub use equipment::*;
+pub use group::*;
 pub use look::*;
+pub use map::*;
 pub use movement::*;
+pub use objects::*;
+pub use olc::*;
+pub use position::*;
+pub use score::*;
+pub use shop::*;
+pub use skills::*;
+pub use track::*;
 
 // TODO: Should Players just be a part of World...
 pub trait PlayerAction: std::fmt::Debug {
     // TODO: Is this supposed to work or is there something I'm missing? For now doing this at the
     // level of each action...
     //#[tracing::instrument(skip(players, world))]
-    fn perform(&self, players: &Players, world: &World);
+    fn perform(&self, players: &Players, world: &mut World);
+}
+
+// Utility function to send a message to some subset of players
+// TODO: It might be useful to be able to trace the predicates being called
+// https://boydjohnson.dev/blog/impl-debug-for-fn-type/
+#[tracing::instrument(skip(players, predicate))]
+pub fn send_targeted_message<F>(players: &Players, message: String, predicate: F)
+where
+    F: FnMut(&(&u32, &Player)) -> bool,
+{
+    for (_id, player) in players.read().iter().filter(predicate) {
+        tracing::debug!("Sending to {}", player.username);
+        let message = message.clone();
+        player.send_message(message);
+    }
+}
+
+// Send a message to everyone in a room except the player who caused it
+fn send_room_message(players: &Players, room: u32, sender: u32, message: String) {
+    send_targeted_message(players, message, |&(id, player)| {
+        player.current_room == room && *id != sender
+    });
+}
+
+// Uppercase the first letter of something, eg a mobile's short description at the start of a
+// sentence
+pub fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
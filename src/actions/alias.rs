@@ -0,0 +1,101 @@
+use crate::{actions::PlayerAction, input::SEPARATOR, player::Players, world::World};
+
+// So the player file doesn't grow without end
+const MAX_ALIASES: usize = 50;
+
+const SYNTAX: &str = "Syntax: alias [word [commands]]\n\
+    alias               list your aliases\n\
+    alias word          show what an alias does\n\
+    alias word commands make a word stand for some commands\n\
+    $1 to $9 in the commands are replaced by the words after the alias and $* by all of them.\n\
+    Commands can be separated with ;, eg alias loot get all $1;put all bag";
+
+#[derive(Debug)]
+pub struct AliasAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for AliasAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let mut guard = players.write();
+        let Some(player) = guard.get_mut(&self.sender) else {
+            return;
+        };
+
+        let argument = self.argument.as_deref().unwrap_or_default().trim();
+        let (word, commands) = argument.split_once(' ').unwrap_or((argument, ""));
+        let word = word.to_lowercase();
+        let commands = commands.trim();
+
+        let response = if word.is_empty() {
+            if player.aliases.is_empty() {
+                String::from("You have no aliases.")
+            } else {
+                let lines: Vec<String> = player
+                    .aliases
+                    .iter()
+                    .map(|(word, commands)| format!("{:<12} {}", word, commands))
+                    .collect();
+                format!("Your aliases:\n{}", lines.join("\n"))
+            }
+        } else if word == "?" {
+            String::from(SYNTAX)
+        } else if commands.is_empty() {
+            match player.aliases.get(&word) {
+                Some(commands) => format!("{} is an alias for: {}", word, commands),
+                None => format!("{} isn't an alias.", word),
+            }
+        } else if word == "alias" || word == "unalias" || word.contains(SEPARATOR) {
+            String::from("That word can't be an alias.")
+        } else if !player.aliases.contains_key(&word) && player.aliases.len() >= MAX_ALIASES {
+            format!("You can't have more than {} aliases.", MAX_ALIASES)
+        } else {
+            player.aliases.insert(word.clone(), commands.to_string());
+            match player.save(&world.player_directory) {
+                Ok(()) => format!("{} is now an alias for: {}", word, commands),
+                Err(error) => {
+                    tracing::error!(
+                        username = player.username,
+                        "Couldn't save player: {}",
+                        error
+                    );
+                    format!("{} is an alias for now, but it couldn't be saved.", word)
+                }
+            }
+        };
+        player.send_message(response);
+    }
+}
+
+#[derive(Debug)]
+pub struct UnaliasAction {
+    pub sender: u32,
+    pub word: String,
+}
+
+impl PlayerAction for UnaliasAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let mut guard = players.write();
+        let Some(player) = guard.get_mut(&self.sender) else {
+            return;
+        };
+
+        let word = self.word.trim().to_lowercase();
+        let response = match player.aliases.remove(&word) {
+            None => format!("{} isn't an alias.", word),
+            Some(_) => match player.save(&world.player_directory) {
+                Ok(()) => format!("{} is no longer an alias.", word),
+                Err(error) => {
+                    tracing::error!(
+                        username = player.username,
+                        "Couldn't save player: {}",
+                        error
+                    );
+                    format!("{} is gone for now, but that couldn't be saved.", word)
+                }
+            },
+        };
+        player.send_message(response);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{actions::PlayerAction, player::Players, world::World};
+
+#[derive(Debug)]
+pub struct AreasAction {
+    pub sender: u32,
+    // An area to show the details of, rather than listing them all
+    pub target: Option<String>,
+}
+
+impl PlayerAction for AreasAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let players_in_area = |area_id| {
+            players
+                .read()
+                .values()
+                .filter(|p| world.room_area(p.current_room) == Some(area_id))
+                .count()
+        };
+
+        let response = match &self.target {
+            None => {
+                let mut lines = vec![format!(
+                    "{:<8}{:<15}{:<30}{}",
+                    "Levels", "Author", "Area", "Vnums"
+                )];
+                for area in world.areas() {
+                    let metadata = &area.metadata;
+                    lines.push(format!(
+                        "{:<8}{:<15}{:<30}{}-{}",
+                        metadata.level_str(),
+                        metadata.author,
+                        metadata.name,
+                        metadata.vnums.0,
+                        metadata.vnums.1
+                    ));
+                }
+                lines.join("\n")
+            }
+            Some(name) => {
+                let Some(area_id) = world.find_area(name) else {
+                    if let Some(sending_player) = players.read().get(&self.sender) {
+                        sending_player.send_message(String::from("There's no area by that name."));
+                    }
+                    return;
+                };
+                let Some(area) = world.get_area(area_id) else {
+                    return;
+                };
+                let metadata = &area.metadata;
+                let mobiles = world
+                    .mobiles
+                    .values()
+                    .filter(|m| area.rooms.contains_key(&m.current_room))
+                    .count();
+
+                format!(
+                    "Name:     {}\n\
+                     Author:   {}\n\
+                     Levels:   {}\n\
+                     Vnums:    {}-{}\n\
+                     File:     {}\n\
+                     Rooms: {}  Mobiles: {}  Objects: {}  Resets: {}  Programs: {}\n\
+                     There are {} mobiles and {} players in the area, which last reset {} updates ago.",
+                    metadata.name,
+                    metadata.author,
+                    metadata.level_str(),
+                    metadata.vnums.0,
+                    metadata.vnums.1,
+                    metadata
+                        .file
+                        .as_ref()
+                        .map_or_else(|| String::from("none"), |f| f.display().to_string()),
+                    area.rooms.len(),
+                    area.mobiles.len(),
+                    area.objects.len(),
+                    area.resets.len(),
+                    area.programs.len(),
+                    mobiles,
+                    players_in_area(area_id),
+                    area.age
+                )
+            }
+        };
+
+        if let Some(sending_player) = players.read().get(&self.sender) {
+            tracing::debug!("Received areas from player: {}", sending_player.username);
+            sending_player.send_message(response);
+        }
+    }
+}
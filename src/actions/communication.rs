@@ -1,7 +1,8 @@
 use crate::{
-    actions::PlayerAction,
+    actions::{send_targeted_message, PlayerAction},
     message::GameMessage,
-    player::{Player, Players},
+    player::Players,
+    programs,
     world::World,
 };
 
@@ -15,7 +16,7 @@ pub struct GossipAction {
 
 impl PlayerAction for GossipAction {
     #[tracing::instrument(skip(players, _world), fields(username=tracing::field::Empty))]
-    fn perform(&self, players: &Players, _world: &World) {
+    fn perform(&self, players: &Players, _world: &mut World) {
         // Do all the reading from the players map at once
         let sending_player_username = {
             if let Some(sending_player) = players.read().get(&self.sender) {
@@ -50,8 +51,8 @@ pub struct SayAction {
 }
 
 impl PlayerAction for SayAction {
-    #[tracing::instrument(skip(players, _world), fields(username=tracing::field::Empty))]
-    fn perform(&self, players: &Players, _world: &World) {
+    #[tracing::instrument(skip(players, world), fields(username=tracing::field::Empty))]
+    fn perform(&self, players: &Players, world: &mut World) {
         // Do all the reading from the players map at once
         let (sending_player_username, room) = {
             if let Some(sending_player) = players.read().get(&self.sender) {
@@ -74,21 +75,7 @@ impl PlayerAction for SayAction {
             // Send to everyone so don't bother with a real predicate
             |&(_, player)| player.current_room == room,
         );
-    }
-}
 
-// Utility function to send a message to some subset of players
-// TODO: Should this be even more generic - use outside of player communications?
-// TODO: It might be useful to be able to trace the predicates being called
-// https://boydjohnson.dev/blog/impl-debug-for-fn-type/
-#[tracing::instrument(skip(players, predicate))]
-fn send_targeted_message<F>(players: &Players, message: String, predicate: F)
-where
-    F: FnMut(&(&u32, &Player)) -> bool,
-{
-    for (_id, player) in players.read().iter().filter(predicate) {
-        tracing::debug!("Sending to {}", player.username);
-        let message = message.clone();
-        player.send_message(message);
+        programs::speech_trigger(players, world, self.sender, &self.content);
     }
 }
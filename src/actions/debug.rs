@@ -8,7 +8,7 @@ pub struct MobileAction {
 }
 
 impl PlayerAction for MobileAction {
-    fn perform(&self, players: &Players, world: &World) {
+    fn perform(&self, players: &Players, world: &mut World) {
         if let Some(sending_player) = players.read().get(&self.sender) {
             tracing::debug!("Received mobile from player: {}", sending_player.username);
             // Get a list of every mobile in the world and what room they're in
@@ -0,0 +1,229 @@
+use crate::{
+    actions::{send_room_message, PlayerAction},
+    equipment::{flag_locations, wear_locations, WearLocation},
+    objects::{ExtraFlag, ItemType, ObjectInstance, WearFlag},
+    player::{Player, Players},
+    programs::{self, TriggerType},
+    world::World,
+};
+
+// Something put on or taken off, with what the rest of the room is told about it
+struct Change {
+    message: String,
+    object: ObjectInstance,
+    trigger: TriggerType,
+}
+
+#[derive(Debug)]
+pub struct WearAction {
+    pub sender: u32,
+    pub target: String,
+    // Set for `wield` and `hold`, which only accept objects with that wear flag
+    pub required_flag: Option<WearFlag>,
+}
+
+impl PlayerAction for WearAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        // Work out what everyone else in the room should be told while we have the lock, then send
+        // it afterwards
+        let (room_id, changes) = {
+            let mut guard = players.write();
+            let Some(sending_player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            tracing::debug!("Received wear from player: {}", sending_player.username);
+
+            let changes = if self.target == "all" && self.required_flag.is_none() {
+                wear_all(sending_player)
+            } else {
+                self.wear_one(sending_player)
+            };
+            sending_player.clamp_points();
+
+            (sending_player.current_room, changes)
+        };
+
+        for change in changes {
+            send_room_message(players, room_id, self.sender, change.message);
+            programs::object_trigger(players, world, self.sender, &change.object, change.trigger);
+        }
+    }
+}
+
+impl WearAction {
+    fn wear_one(&self, player: &mut Player) -> Vec<Change> {
+        let Some(index) = player
+            .inventory
+            .iter()
+            .position(|o| o.matches(&self.target))
+        else {
+            player.send_message(String::from("You do not have that item."));
+            return Vec::new();
+        };
+
+        let template = &player.inventory[index].template;
+        let locations = match self.required_flag {
+            None => wear_locations(template),
+            // Lights are held, but go in their own slot
+            Some(WearFlag::Hold) if template.item_type == ItemType::Light => {
+                wear_locations(template)
+            }
+            Some(flag) if template.can_wear(flag) => flag_locations(flag),
+            Some(_) => &[],
+        };
+
+        if locations.is_empty() {
+            let response = match self.required_flag {
+                Some(WearFlag::Wield) => "You can't wield that.",
+                Some(WearFlag::Hold) => "You can't hold that.",
+                _ => "You can't wear, wield, or hold that.",
+            };
+            player.send_message(String::from(response));
+            return Vec::new();
+        }
+
+        // Prefer an empty slot, otherwise swap out whatever is in the first one
+        let location = locations
+            .iter()
+            .copied()
+            .find(|l| player.equipment.get(*l).is_none())
+            .unwrap_or(locations[0]);
+        if let Some(message) = cant_remove(player, location) {
+            player.send_message(message);
+            return Vec::new();
+        }
+
+        let mut changes = Vec::new();
+        let object = player.inventory.remove(index);
+        if let Some(change) = remove(player, location) {
+            changes.push(change);
+        }
+        changes.push(wear(player, location, object));
+        changes
+    }
+}
+
+// Wear everything that fits in an empty slot, without swapping anything out
+fn wear_all(player: &mut Player) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let mut index = 0;
+    while index < player.inventory.len() {
+        let free_location = wear_locations(&player.inventory[index].template)
+            .iter()
+            .copied()
+            .find(|l| player.equipment.get(*l).is_none());
+
+        if let Some(location) = free_location {
+            let object = player.inventory.remove(index);
+            changes.push(wear(player, location, object));
+        } else {
+            index += 1;
+        }
+    }
+
+    if changes.is_empty() {
+        player.send_message(String::from("You have nothing else you can wear."));
+    }
+    changes
+}
+
+// Put an object in a slot
+fn wear(player: &mut Player, location: WearLocation, object: ObjectInstance) -> Change {
+    let object_name = object.template.short_description.clone();
+    player.send_message(location.wear_message(&object_name));
+    player.equipment.equip(location, object.clone());
+    Change {
+        message: format!("{} {} {}.", player.username, location.verb(), object_name),
+        object,
+        trigger: TriggerType::Wear,
+    }
+}
+
+// Why whatever is in a slot can't be taken off, if it can't
+fn cant_remove(player: &Player, location: WearLocation) -> Option<String> {
+    player
+        .equipment
+        .get(location)
+        .filter(|o| o.template.has_flag(ExtraFlag::NoRemove))
+        .map(|o| format!("You can't remove {}.", o.template.short_description))
+}
+
+// Move whatever is in a slot back to the inventory
+fn remove(player: &mut Player, location: WearLocation) -> Option<Change> {
+    let object = player.equipment.unequip(location)?;
+    let object_name = object.template.short_description.clone();
+    player.send_message(format!("You stop using {}.", object_name));
+    player.inventory.push(object.clone());
+    Some(Change {
+        message: format!("{} stops using {}.", player.username, object_name),
+        object,
+        trigger: TriggerType::Remove,
+    })
+}
+
+#[derive(Debug)]
+pub struct RemoveAction {
+    pub sender: u32,
+    pub target: String,
+}
+
+impl PlayerAction for RemoveAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let (room_id, change) = {
+            let mut guard = players.write();
+            let Some(sending_player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            tracing::debug!("Received remove from player: {}", sending_player.username);
+
+            let Some(location) = sending_player.equipment.find(&self.target) else {
+                sending_player.send_message(String::from("You do not have that item."));
+                return;
+            };
+            if let Some(message) = cant_remove(sending_player, location) {
+                sending_player.send_message(message);
+                return;
+            }
+            let change = remove(sending_player, location);
+            sending_player.clamp_points();
+
+            (sending_player.current_room, change)
+        };
+
+        if let Some(change) = change {
+            send_room_message(players, room_id, self.sender, change.message);
+            programs::object_trigger(players, world, self.sender, &change.object, change.trigger);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EquipmentAction {
+    pub sender: u32,
+}
+
+impl PlayerAction for EquipmentAction {
+    fn perform(&self, players: &Players, _world: &mut World) {
+        if let Some(sending_player) = players.read().get(&self.sender) {
+            tracing::debug!(
+                "Received equipment from player: {}",
+                sending_player.username
+            );
+            let worn = {
+                if sending_player.equipment.is_empty() {
+                    String::from("Nothing.")
+                } else {
+                    sending_player
+                        .equipment
+                        .iter()
+                        .map(|(location, o)| {
+                            format!("{:<20}{}", location.label(), o.template.short_description)
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            };
+            sending_player.send_message(format!("You are using:\n{}", worn));
+        }
+    }
+}
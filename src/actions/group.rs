@@ -0,0 +1,314 @@
+// Following other players around and grouping up with them, along the lines of do_follow, do_group
+// and do_gtell in ROM's act_comm.c
+use crate::{
+    actions::{send_targeted_message, PlayerAction},
+    character::{find_in_room, CharacterId},
+    player::{Player, Players},
+    world::World,
+};
+use std::collections::HashMap;
+use stylish::ansi::format as ansi_format;
+
+// Find another player in the sender's room for following or grouping. Mobiles can't be either yet.
+fn find_player(players: &Players, world: &World, sender: u32, name: &str) -> Result<u32, String> {
+    let Some(room_id) = players.read().get(&sender).map(|p| p.current_room) else {
+        return Err(String::new());
+    };
+    match find_in_room(players, world, CharacterId::Player(sender), room_id, name) {
+        Some(CharacterId::Player(id)) => Ok(id),
+        Some(CharacterId::Mobile(_)) => Err(String::from("You can only do that with players.")),
+        None => Err(String::from("They aren't here.")),
+    }
+}
+
+fn username(players: &HashMap<u32, Player>, id: u32) -> String {
+    players
+        .get(&id)
+        .map_or_else(|| String::from("someone"), |p| p.username.clone())
+}
+
+// Stop following whoever the player is following, which also takes them out of any group, like
+// ROM's stop_follower
+fn stop_following(players: &mut HashMap<u32, Player>, id: u32) {
+    let Some(master_id) = players.get(&id).and_then(|p| p.master) else {
+        return;
+    };
+    let name = username(players, id);
+    let master_name = username(players, master_id);
+    if let Some(master) = players.get(&master_id) {
+        master.send_message(format!("{} stops following you.", name));
+    }
+    if let Some(player) = players.get_mut(&id) {
+        player.send_message(format!("You stop following {}.", master_name));
+        player.master = None;
+        player.leader = None;
+    }
+}
+
+// Nobody can follow or be led by a player who's gone, like ROM's die_follower
+pub fn die_follower(players: &Players, gone: &Player) {
+    let mut guard = players.write();
+    if let Some(master) = gone.master.and_then(|id| guard.get(&id)) {
+        master.send_message(format!("{} stops following you.", gone.username));
+    }
+    for player in guard.values_mut() {
+        if player.master == Some(gone.id) {
+            player.send_message(format!("You stop following {}.", gone.username));
+            player.master = None;
+        }
+        if player.leader == Some(gone.id) {
+            player.leader = None;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FollowAction {
+    pub sender: u32,
+    pub target: Option<String>,
+}
+
+impl PlayerAction for FollowAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let found = match self.target.as_deref() {
+            Some(name) => find_player(players, world, self.sender, name),
+            None => Err(String::from("Follow whom?")),
+        };
+        let mut guard = players.write();
+        let target = match found {
+            Ok(target) => target,
+            Err(message) => {
+                if let Some(player) = guard.get(&self.sender) {
+                    player.send_message(message);
+                }
+                return;
+            }
+        };
+        let Some(master) = guard.get(&self.sender).map(|p| p.master) else {
+            return;
+        };
+
+        // Following yourself is how you stop following anyone else
+        if target == self.sender {
+            match master {
+                Some(_) => stop_following(&mut guard, self.sender),
+                None => {
+                    if let Some(player) = guard.get(&self.sender) {
+                        player.send_message(String::from("You already follow yourself."));
+                    }
+                }
+            }
+            return;
+        }
+        let target_name = username(&guard, target);
+        if master == Some(target) {
+            if let Some(player) = guard.get(&self.sender) {
+                player.send_message(format!("You already follow {}.", target_name));
+            }
+            return;
+        }
+
+        stop_following(&mut guard, self.sender);
+        let name = username(&guard, self.sender);
+        if let Some(player) = guard.get_mut(&self.sender) {
+            player.master = Some(target);
+            player.leader = None;
+            player.send_message(format!("You now follow {}.", target_name));
+        }
+        if let Some(leader) = guard.get(&target) {
+            leader.send_message(format!("{} now follows you.", name));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GroupAction {
+    pub sender: u32,
+    pub target: Option<String>,
+}
+
+impl GroupAction {
+    // Everyone in the sender's group with how they're doing, leader first
+    fn list(&self, players: &Players) {
+        let guard = players.read();
+        let Some(player) = guard.get(&self.sender) else {
+            return;
+        };
+        let leader = player.group_leader();
+        let mut members: Vec<&Player> = guard
+            .values()
+            .filter(|p| p.group_leader() == leader)
+            .collect();
+        members.sort_by_key(|p| (p.id != leader, p.id));
+
+        let mut lines = vec![format!("{}'s group:", username(&guard, leader))];
+        lines.extend(members.iter().map(|p| {
+            let stats = p.stats();
+            format!(
+                "[{:>2} {:<7}] {:<16} {:>4}/{:<4} hp {:>4}/{:<4} mana {:>4}/{:<4} mv {:>6} xp",
+                p.level,
+                p.class.name,
+                p.username,
+                p.hit,
+                stats.max_hit,
+                p.mana,
+                stats.max_mana,
+                p.movement,
+                stats.max_move,
+                p.exp
+            )
+        }));
+        player.send_message(lines.join("\n"));
+    }
+}
+
+impl PlayerAction for GroupAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let Some(name) = self.target.as_deref() else {
+            self.list(players);
+            return;
+        };
+        let found = find_player(players, world, self.sender, name);
+        let guard = players.read();
+        let Some(player) = guard.get(&self.sender) else {
+            return;
+        };
+        let target = match found {
+            Ok(target) => target,
+            Err(message) => {
+                player.send_message(message);
+                return;
+            }
+        };
+        let Some(victim) = guard.get(&target) else {
+            return;
+        };
+
+        if player.master.is_some() || player.leader.is_some() {
+            player.send_message(String::from("But you are following someone else!"));
+            return;
+        }
+        if target == self.sender {
+            player.send_message(String::from("You lead your own group."));
+            return;
+        }
+        if victim.master != Some(self.sender) {
+            player.send_message(format!("{} isn't following you.", victim.username));
+            return;
+        }
+
+        let room_id = player.current_room;
+        let (_, _, their) = player.sex.pronouns();
+        let (leader, member) = (player.username.clone(), victim.username.clone());
+        let in_group = victim.leader == Some(self.sender);
+        drop(guard);
+
+        let room_message = if in_group {
+            format!("{} removes {} from {} group.", leader, member, their)
+        } else {
+            format!("{} joins {}'s group.", member, leader)
+        };
+        send_targeted_message(players, room_message, |&(id, p)| {
+            p.current_room == room_id && *id != self.sender && *id != target
+        });
+
+        let mut guard = players.write();
+        if let Some(victim) = guard.get_mut(&target) {
+            if in_group {
+                victim.leader = None;
+                victim.send_message(format!("{} removes you from {} group.", leader, their));
+            } else {
+                victim.leader = Some(self.sender);
+                victim.send_message(format!("You join {}'s group.", leader));
+            }
+        }
+        if let Some(player) = guard.get(&self.sender) {
+            let message = if in_group {
+                format!("You remove {} from your group.", member)
+            } else {
+                format!("{} joins your group.", member)
+            };
+            player.send_message(message);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UngroupAction {
+    pub sender: u32,
+}
+
+impl PlayerAction for UngroupAction {
+    fn perform(&self, players: &Players, _world: &mut World) {
+        let mut guard = players.write();
+        let Some(player) = guard.get(&self.sender) else {
+            return;
+        };
+        let name = player.username.clone();
+
+        // Members leave the group they're in, while leaders break theirs up
+        if let Some(leader_id) = player.leader {
+            let leader_name = username(&guard, leader_id);
+            if let Some(leader) = guard.get(&leader_id) {
+                leader.send_message(format!("{} leaves your group.", name));
+            }
+            if let Some(player) = guard.get_mut(&self.sender) {
+                player.leader = None;
+                player.send_message(format!("You leave {}'s group.", leader_name));
+            }
+            return;
+        }
+
+        let mut disbanded = false;
+        for member in guard.values_mut() {
+            if member.leader == Some(self.sender) {
+                member.leader = None;
+                member.send_message(format!("{} disbands the group.", name));
+                disbanded = true;
+            }
+        }
+        if let Some(player) = guard.get(&self.sender) {
+            player.send_message(String::from(if disbanded {
+                "You disband your group."
+            } else {
+                "You aren't in a group."
+            }));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GtellAction {
+    pub sender: u32,
+    pub content: Option<String>,
+}
+
+impl PlayerAction for GtellAction {
+    fn perform(&self, players: &Players, _world: &mut World) {
+        let (name, leader) = {
+            let guard = players.read();
+            let Some(player) = guard.get(&self.sender) else {
+                return;
+            };
+            let Some(content) = self.content.as_deref() else {
+                player.send_message(String::from("Tell your group what?"));
+                return;
+            };
+            player.send_message(ansi_format!(
+                "You tell the group '{:(fg=magenta)}'",
+                content
+            ));
+            (player.username.clone(), player.group_leader())
+        };
+
+        send_targeted_message(
+            players,
+            ansi_format!(
+                "{} tells the group '{:(fg=magenta)}'",
+                name,
+                self.content.as_deref().unwrap_or_default()
+            ),
+            |&(id, p)| *id != self.sender && p.group_leader() == leader,
+        );
+    }
+}
@@ -1,6 +1,27 @@
-use crate::{actions::PlayerAction, message::GameMessage, player::Players, world::World};
+use crate::{
+    actions::{capitalize, PlayerAction},
+    affects, map,
+    message::GameMessage,
+    mobiles::AffectFlag,
+    player::Players,
+    world::World,
+};
+use std::collections::HashSet;
 use stylish::ansi::format as ansi_format;
 
+// What shows someone is affected by something, eg "(White Aura) " for sanctuary, like the start of
+// ROM's show_char_to_char_0
+fn aura(bits: u64) -> String {
+    let mut aura = String::new();
+    if bits & AffectFlag::Invisible.bit() != 0 {
+        aura.push_str("(Invis) ");
+    }
+    if bits & AffectFlag::Sanctuary.bit() != 0 {
+        aura.push_str("(White Aura) ");
+    }
+    aura
+}
+
 #[derive(Debug)]
 pub struct LookAction {
     pub sender: u32,
@@ -8,11 +29,16 @@ pub struct LookAction {
 }
 
 impl PlayerAction for LookAction {
-    fn perform(&self, players: &Players, world: &World) {
+    fn perform(&self, players: &Players, world: &mut World) {
         if let Some(sending_player) = players.read().get(&self.sender) {
             tracing::debug!("Received look from player: {}", sending_player.username);
+            if sending_player.is_affected(AffectFlag::Blind) {
+                sending_player.send_message(String::from("You can't see a thing!"));
+                return;
+            }
             // TODO: Again, what if they're in a non-existent room or something
             if let Some(room) = world.get_player_room(&sending_player) {
+                let sight = sending_player.affected_by();
                 let exits = {
                     if room.exits.is_empty() {
                         String::from("You don't see any exits.")
@@ -27,6 +53,13 @@ impl PlayerAction for LookAction {
                     }
                 };
 
+                // Objects on the floor each get their own line
+                let objects = room
+                    .objects
+                    .iter()
+                    .map(|o| format!("{}\n", o.template.long_description))
+                    .collect::<String>();
+
                 let mut other_characters: Vec<String> = Vec::new();
 
                 let players_in_room = room.get_players(&players);
@@ -37,7 +70,15 @@ impl PlayerAction for LookAction {
                         // Don't tell us that we're in the room, we know that.
                         .filter(|key| *key != &self.sender)
                         .filter_map(|key| guard.get(key))
-                        .map(|p| p.username.clone());
+                        .filter(|p| affects::can_see(sight, p.affected_by()))
+                        .map(|p| {
+                            format!(
+                                "{}{} {}",
+                                aura(p.affected_by()),
+                                p.username,
+                                p.position.room_text()
+                            )
+                        });
                     other_characters.extend(players_list);
                 }
 
@@ -46,7 +87,22 @@ impl PlayerAction for LookAction {
                 let mobiles_list = mobiles_in_room
                     .iter()
                     .filter_map(|key| world.mobiles.get(key))
-                    .map(|m| m.template.room_description.clone());
+                    .filter(|m| affects::can_see(sight, m.affected_by()))
+                    .map(|m| {
+                        // Mobiles where the area put them get their own description, like ROM
+                        let description = if m.position == m.default_position()
+                            && !m.template.long_description.trim().is_empty()
+                        {
+                            m.template.long_description.trim_end().to_string()
+                        } else {
+                            format!(
+                                "{} {}",
+                                capitalize(&m.template.room_description),
+                                m.position.room_text()
+                            )
+                        };
+                        format!("{}{}", aura(m.affected_by()), description)
+                    });
 
                 other_characters.extend(mobiles_list);
 
@@ -54,15 +110,30 @@ impl PlayerAction for LookAction {
                     if other_characters.len() == 0 {
                         String::from("You're the only one here.")
                     } else {
-                        format!("You see {} here.", other_characters.join(", "))
+                        other_characters.join("\n")
                     }
                 };
 
+                // A small map of the rooms next to this one, for players who want it
+                let description = if sending_player.automap {
+                    let others: HashSet<u32> = players
+                        .read()
+                        .values()
+                        .filter(|p| p.id != self.sender)
+                        .map(|p| p.current_room)
+                        .collect();
+                    let lines = map::draw(world, room.id, 1, &others);
+                    format!("{}\n", map::beside(&lines, &room.description))
+                } else {
+                    room.description.clone()
+                };
+
                 sending_player.send_message(ansi_format!(
-                    "{:(fg=green,bold)}\n{}\n{}\n{}",
+                    "{:(fg=green,bold)}\n{}\n{}\n{}{}",
                     room.name,
-                    room.description,
+                    description,
                     exits,
+                    objects,
                     other_characters_string
                 ));
             }
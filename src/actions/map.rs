@@ -0,0 +1,59 @@
+use crate::{actions::PlayerAction, map, player::Players, world::World};
+use std::collections::HashSet;
+
+const DEFAULT_RADIUS: u32 = 2;
+const MAX_RADIUS: u32 = 6;
+
+#[derive(Debug)]
+pub struct MapAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for MapAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let argument = self.argument.as_deref().unwrap_or_default().trim();
+
+        // `map auto` turns the small map beside room descriptions on and off
+        if argument.eq_ignore_ascii_case("auto") {
+            if let Some(player) = players.write().get_mut(&self.sender) {
+                player.automap = !player.automap;
+                player.send_message(String::from(if player.automap {
+                    "You'll see a map when you look around."
+                } else {
+                    "You won't see a map when you look around."
+                }));
+            }
+            return;
+        }
+
+        let radius = match argument {
+            "" => DEFAULT_RADIUS,
+            number => match number.parse::<u32>() {
+                Ok(radius) => radius.clamp(1, MAX_RADIUS),
+                Err(_) => {
+                    if let Some(player) = players.read().get(&self.sender) {
+                        player.send_message(format!(
+                            "Syntax: map [radius]|auto\nThe radius is how many rooms away to \
+                             show, up to {}.",
+                            MAX_RADIUS
+                        ));
+                    }
+                    return;
+                }
+            },
+        };
+
+        let guard = players.read();
+        let Some(player) = guard.get(&self.sender) else {
+            return;
+        };
+        let others: HashSet<u32> = guard
+            .values()
+            .filter(|p| p.id != self.sender)
+            .map(|p| p.current_room)
+            .collect();
+        let lines = map::draw(world, player.current_room, radius, &others);
+        player.send_message(format!("{}\n\n{}", lines.join("\n"), map::LEGEND));
+    }
+}
@@ -1,4 +1,32 @@
-use crate::{actions::PlayerAction, message::Direction, player::Players, world::World};
+use crate::{
+    actions::{LookAction, PlayerAction},
+    affects,
+    message::Direction,
+    player::Players,
+    position::Position,
+    programs,
+    world::World,
+};
+
+// Tell everyone else in a room about the player coming or going. Those who can't see them are
+// told it was someone.
+fn announce(players: &Players, room_id: u32, mover: u32, message: impl Fn(&str) -> String) {
+    let guard = players.read();
+    let Some(player) = guard.get(&mover) else {
+        return;
+    };
+    let bits = player.affected_by();
+    for other in guard.values() {
+        if other.id == mover || other.current_room != room_id {
+            continue;
+        }
+        if affects::can_see(other.affected_by(), bits) {
+            other.send_message(message(&player.username));
+        } else {
+            other.send_message(message("Someone"));
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct MoveAction {
@@ -7,8 +35,18 @@ pub struct MoveAction {
 }
 
 impl PlayerAction for MoveAction {
-    fn perform(&self, players: &Players, world: &World) {
-        if let Some(sending_player) = players.write().get_mut(&self.sender) {
+    fn perform(&self, players: &Players, world: &mut World) {
+        // Mobiles guarding the exit get a chance to stop the player
+        if programs::exit_trigger(players, world, self.sender, self.direction.number()) {
+            return;
+        }
+
+        let moved = {
+            let mut guard = players.write();
+            let Some(sending_player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            let from = sending_player.current_room;
             // TODO: Deal with locking the world here at some point
             if let Some(exit) = world
                 .get_player_room(&sending_player)
@@ -16,12 +54,53 @@ impl PlayerAction for MoveAction {
             {
                 tracing::debug!("Moving player {} to {}", &self.direction, exit);
                 sending_player.move_to_room(*exit);
+                Some((from, *exit))
             } else {
                 // TODO: This will read sort of awkward (eg "You don't see an
                 // exit north from here" when we'd probably say "north of
                 // here"). Should figure out a way to get consistent.
                 let response = format!("You don't see an exit {} from here", self.direction);
                 sending_player.send_message(response);
+                None
+            }
+        };
+
+        if let Some((from, to)) = moved {
+            announce(players, from, self.sender, |name| {
+                format!("{} leaves {}.", name, self.direction)
+            });
+            announce(players, to, self.sender, |name| {
+                format!("{} has arrived.", name)
+            });
+            // TODO: Make this optional
+            LookAction {
+                sender: self.sender,
+            }
+            .perform(players, world);
+            programs::greet_trigger(players, world, self.sender);
+
+            // Anyone following who was left behind comes along too, like in ROM's move_char
+            let leader = players.read().get(&self.sender).map(|p| p.username.clone());
+            let mut followers: Vec<u32> = players
+                .read()
+                .values()
+                .filter(|p| p.master == Some(self.sender) && p.current_room == from)
+                .filter(|p| p.position == Position::Standing)
+                .map(|p| p.id)
+                .collect();
+            followers.sort();
+            for follower in followers {
+                if let Some(player) = players.read().get(&follower) {
+                    player.send_message(format!(
+                        "You follow {}.",
+                        leader.as_deref().unwrap_or("someone")
+                    ));
+                }
+                MoveAction {
+                    sender: follower,
+                    direction: self.direction,
+                }
+                .perform(players, world);
             }
         }
     }
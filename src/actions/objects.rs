@@ -0,0 +1,312 @@
+use crate::{
+    actions::{send_room_message, send_targeted_message, PlayerAction},
+    objects::{ExtraFlag, ObjectInstance, WearFlag},
+    player::Players,
+    programs::{self, TriggerType},
+    world::World,
+};
+
+#[derive(Debug)]
+pub struct GetAction {
+    pub sender: u32,
+    pub target: String,
+}
+
+impl PlayerAction for GetAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        // Do all the work with the players map locked, then tell the room about it afterwards
+        let (username, room_id, object) = {
+            let mut guard = players.write();
+            let Some(sending_player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            tracing::debug!("Received get from player: {}", sending_player.username);
+
+            let Some(room) = world.get_room_mut(sending_player.current_room) else {
+                return;
+            };
+            let Some(index) = room.objects.iter().position(|o| o.matches(&self.target)) else {
+                sending_player.send_message(format!("I see no {} here.", self.target));
+                return;
+            };
+            if !room.objects[index].template.can_wear(WearFlag::Take) {
+                sending_player.send_message(String::from("You can't take that."));
+                return;
+            }
+
+            let object = room.objects.remove(index);
+            sending_player.send_message(format!("You get {}.", object.template.short_description));
+            sending_player.inventory.push(object.clone());
+
+            (
+                sending_player.username.clone(),
+                sending_player.current_room,
+                object,
+            )
+        };
+
+        send_room_message(
+            players,
+            room_id,
+            self.sender,
+            format!("{} gets {}.", username, object.template.short_description),
+        );
+        programs::object_trigger(players, world, self.sender, &object, TriggerType::Get);
+    }
+}
+
+#[derive(Debug)]
+pub struct DropAction {
+    pub sender: u32,
+    pub target: String,
+}
+
+impl PlayerAction for DropAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let (username, room_id, object) = {
+            let mut guard = players.write();
+            let Some(sending_player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            tracing::debug!("Received drop from player: {}", sending_player.username);
+
+            let Some(object) = sending_player.take_from_inventory(&self.target) else {
+                sending_player.send_message(String::from("You do not have that item."));
+                return;
+            };
+            if object.template.has_flag(ExtraFlag::NoDrop) {
+                sending_player.inventory.push(object);
+                sending_player.send_message(String::from("You can't let go of it."));
+                return;
+            }
+
+            let Some(room) = world.get_room_mut(sending_player.current_room) else {
+                // Nowhere to put it, so hang onto it
+                sending_player.inventory.push(object);
+                return;
+            };
+            room.objects.push(object.clone());
+            sending_player.send_message(format!("You drop {}.", object.template.short_description));
+
+            (
+                sending_player.username.clone(),
+                sending_player.current_room,
+                object,
+            )
+        };
+
+        send_room_message(
+            players,
+            room_id,
+            self.sender,
+            format!("{} drops {}.", username, object.template.short_description),
+        );
+        programs::drop_trigger(players, world, self.sender, &object);
+    }
+}
+
+#[derive(Debug)]
+pub struct GiveAction {
+    pub sender: u32,
+    // Either `<object> <target>` or `<amount> gold|silver|coins <target>`
+    pub arguments: String,
+}
+
+enum Recipient {
+    Player(u32),
+    Mobile(u32),
+}
+
+enum Gift {
+    Object(Box<ObjectInstance>),
+    Gold(u32),
+    Silver(u32),
+}
+
+impl Gift {
+    fn name(&self) -> String {
+        match self {
+            Gift::Object(object) => object.template.short_description.clone(),
+            Gift::Gold(amount) => format!("{} gold", amount),
+            Gift::Silver(amount) => format!("{} silver", amount),
+        }
+    }
+}
+
+impl PlayerAction for GiveAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let words: Vec<&str> = self.arguments.split_whitespace().collect();
+        let (what, amount, target) = match words.as_slice() {
+            [amount, currency @ ("gold" | "silver" | "coin" | "coins"), target @ ..]
+                if amount.parse::<u32>().is_ok() =>
+            {
+                (*currency, amount.parse::<u32>().ok(), target.join(" "))
+            }
+            [object, target @ ..] => (*object, None, target.join(" ")),
+            [] => return,
+        };
+
+        let (username, room_id, recipient, recipient_name, gift) = {
+            let mut guard = players.write();
+            let Some(room_id) = guard.get(&self.sender).map(|p| p.current_room) else {
+                return;
+            };
+
+            let target_lower = target.to_lowercase();
+            let recipient = guard
+                .values()
+                .find(|p| {
+                    p.id != self.sender
+                        && p.current_room == room_id
+                        && !target_lower.is_empty()
+                        && p.username.to_lowercase().starts_with(&target_lower)
+                })
+                .map(|p| (Recipient::Player(p.id), p.username.clone()))
+                .or_else(|| {
+                    world
+                        .mobiles
+                        .values()
+                        .find(|m| m.current_room == room_id && m.matches(&target))
+                        .map(|m| (Recipient::Mobile(m.id), m.template.room_description.clone()))
+                });
+
+            let Some(sending_player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            tracing::debug!("Received give from player: {}", sending_player.username);
+
+            if target.is_empty() {
+                sending_player.send_message(String::from("Give what to whom?"));
+                return;
+            }
+            let Some((recipient, recipient_name)) = recipient else {
+                sending_player.send_message(String::from("They aren't here."));
+                return;
+            };
+
+            // Take whatever's being given from the player
+            let gift = match (amount, what) {
+                (Some(0), _) => {
+                    sending_player.send_message(String::from("Sorry, you can't do that."));
+                    return;
+                }
+                (Some(amount), "gold") if sending_player.gold >= amount => {
+                    sending_player.gold -= amount;
+                    Gift::Gold(amount)
+                }
+                (Some(amount), "silver" | "coin" | "coins") if sending_player.silver >= amount => {
+                    sending_player.silver -= amount;
+                    Gift::Silver(amount)
+                }
+                (Some(_), _) => {
+                    sending_player.send_message(String::from("You haven't got that much."));
+                    return;
+                }
+                (None, name) => {
+                    let Some(object) = sending_player.take_from_inventory(name) else {
+                        sending_player.send_message(String::from("You do not have that item."));
+                        return;
+                    };
+                    if object.template.has_flag(ExtraFlag::NoDrop) {
+                        sending_player.inventory.push(object);
+                        sending_player.send_message(String::from("You can't let go of it."));
+                        return;
+                    }
+                    Gift::Object(Box::new(object))
+                }
+            };
+            sending_player.send_message(format!("You give {} to {}.", gift.name(), recipient_name));
+            let username = sending_player.username.clone();
+
+            // And hand it over
+            match recipient {
+                Recipient::Player(id) => {
+                    if let Some(receiving_player) = guard.get_mut(&id) {
+                        receiving_player.send_message(format!(
+                            "{} gives you {}.",
+                            username,
+                            gift.name()
+                        ));
+                        match &gift {
+                            Gift::Object(object) => {
+                                receiving_player.inventory.push(*object.clone())
+                            }
+                            Gift::Gold(amount) => receiving_player.gold += amount,
+                            Gift::Silver(amount) => receiving_player.silver += amount,
+                        }
+                    }
+                }
+                Recipient::Mobile(id) => {
+                    if let Some(mobile) = world.mobiles.get_mut(&id) {
+                        match &gift {
+                            Gift::Object(object) => mobile.inventory.push(*object.clone()),
+                            Gift::Gold(amount) => mobile.gold += amount,
+                            Gift::Silver(amount) => mobile.silver += amount,
+                        }
+                    }
+                }
+            }
+
+            (username, room_id, recipient, recipient_name, gift)
+        };
+
+        let recipient_id = match recipient {
+            Recipient::Player(id) => Some(id),
+            Recipient::Mobile(_) => None,
+        };
+        send_targeted_message(
+            players,
+            format!("{} gives {} to {}.", username, gift.name(), recipient_name),
+            |&(id, player)| {
+                player.current_room == room_id && *id != self.sender && Some(*id) != recipient_id
+            },
+        );
+
+        // The object and mobiles might have something to say about it
+        if let Gift::Object(object) = &gift {
+            programs::object_trigger(players, world, self.sender, object, TriggerType::Give);
+        }
+        if let Recipient::Mobile(mobile_id) = recipient {
+            match gift {
+                Gift::Object(object) => {
+                    programs::give_trigger(players, world, self.sender, mobile_id, &object)
+                }
+                Gift::Gold(amount) => {
+                    programs::bribe_trigger(players, world, self.sender, mobile_id, amount * 100)
+                }
+                Gift::Silver(amount) => {
+                    programs::bribe_trigger(players, world, self.sender, mobile_id, amount)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InventoryAction {
+    pub sender: u32,
+}
+
+impl PlayerAction for InventoryAction {
+    fn perform(&self, players: &Players, _world: &mut World) {
+        if let Some(sending_player) = players.read().get(&self.sender) {
+            tracing::debug!(
+                "Received inventory from player: {}",
+                sending_player.username
+            );
+            let contents = {
+                if sending_player.inventory.is_empty() {
+                    String::from("     Nothing.")
+                } else {
+                    sending_player
+                        .inventory
+                        .iter()
+                        .map(|o| format!("     {}", o.template.short_description))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            };
+            sending_player.send_message(format!("You are carrying:\n{}", contents));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{actions::PlayerAction, olc, player::Players, world::World};
+
+#[derive(Debug)]
+pub struct ReditAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for ReditAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        olc::start_redit(self.sender, self.argument.as_deref(), players, world);
+    }
+}
+
+#[derive(Debug)]
+pub struct MeditAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for MeditAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        olc::start_medit(self.sender, self.argument.as_deref(), players, world);
+    }
+}
+
+#[derive(Debug)]
+pub struct OeditAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for OeditAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        olc::start_oedit(self.sender, self.argument.as_deref(), players, world);
+    }
+}
+
+#[derive(Debug)]
+pub struct ResetsAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for ResetsAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        olc::edit_resets(self.sender, self.argument.as_deref(), players, world);
+    }
+}
+
+#[derive(Debug)]
+pub struct AsaveAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for AsaveAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        olc::asave(self.sender, self.argument.as_deref(), players, world);
+    }
+}
+
+#[derive(Debug)]
+pub struct AreloadAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for AreloadAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        olc::areload(self.sender, self.argument.as_deref(), players, world);
+    }
+}
+
+#[derive(Debug)]
+pub struct GraphAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for GraphAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        olc::graph(self.sender, self.argument.as_deref(), players, world);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    actions::{capitalize, send_room_message, PlayerAction},
+    player::Players,
+    position::Position,
+    world::World,
+};
+
+// What the player sees and what the room sees when they go from one position to another, using
+// ROM's messages. The room message follows the player's name. Err has the reason they can't.
+fn change_message(
+    from: Position,
+    to: Position,
+) -> Result<(&'static str, Option<&'static str>), &'static str> {
+    use Position::*;
+    match (to, from) {
+        (Sitting, Fighting) => Err("Maybe you should finish this fight first?"),
+        (_, Fighting) => Err("You are already fighting!"),
+
+        (Sleeping, Sleeping) => Err("You are already sleeping."),
+        (Sleeping, _) => Ok(("You go to sleep.", Some("goes to sleep."))),
+
+        (Resting, Sleeping) => Ok((
+            "You wake up and start resting.",
+            Some("wakes up and starts resting."),
+        )),
+        (Resting, Resting) => Err("You are already resting."),
+        (Resting, Sitting) => Ok(("You rest.", Some("rests."))),
+        (Resting, _) => Ok(("You sit down and rest.", Some("sits down and rests."))),
+
+        (Sitting, Sleeping) => Ok(("You wake and sit up.", Some("wakes and sits up."))),
+        (Sitting, Resting) => Ok(("You stop resting.", Some("stops resting."))),
+        (Sitting, Sitting) => Err("You are already sitting down."),
+        (Sitting, _) => Ok(("You sit down.", Some("sits down on the ground."))),
+
+        (Standing, Sleeping) => Ok(("You wake and stand up.", Some("wakes and stands up."))),
+        (Standing, Standing) => Err("You are already standing."),
+        (Standing, _) => Ok(("You stand up.", Some("stands up."))),
+
+        _ => Err("You can't do that."),
+    }
+}
+
+// Sleep, rest, sit and stand, which only differ in the position they leave the player in
+#[derive(Debug)]
+pub struct PositionAction {
+    pub sender: u32,
+    pub position: Position,
+}
+
+impl PlayerAction for PositionAction {
+    fn perform(&self, players: &Players, _world: &mut World) {
+        let (username, room_id, message) = {
+            let mut guard = players.write();
+            let Some(player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            match change_message(player.position, self.position) {
+                Ok((message, room_message)) => {
+                    player.position = self.position;
+                    player.send_message(String::from(message));
+                    (player.username.clone(), player.current_room, room_message)
+                }
+                Err(message) => {
+                    player.send_message(String::from(message));
+                    return;
+                }
+            }
+        };
+
+        if let Some(message) = message {
+            let message = format!("{} {}", username, message);
+            send_room_message(players, room_id, self.sender, message);
+        }
+    }
+}
+
+// Wake up, or wake someone else up
+#[derive(Debug)]
+pub struct WakeAction {
+    pub sender: u32,
+    pub target: Option<String>,
+}
+
+impl PlayerAction for WakeAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let Some(target) = self.target.as_deref() else {
+            PositionAction {
+                sender: self.sender,
+                position: Position::Standing,
+            }
+            .perform(players, world);
+            return;
+        };
+
+        let Some((username, room_id, awake)) = players
+            .read()
+            .get(&self.sender)
+            .map(|p| (p.username.clone(), p.current_room, p.position.is_awake()))
+        else {
+            return;
+        };
+        let name = target.to_lowercase();
+
+        let response = if !awake {
+            String::from("You are asleep yourself!")
+        } else if let Some(other) = players.write().values_mut().find(|p| {
+            p.id != self.sender
+                && p.current_room == room_id
+                && p.username.to_lowercase().starts_with(&name)
+        }) {
+            if other.position.is_awake() {
+                format!("{} is already awake.", other.username)
+            } else {
+                other.position = Position::Standing;
+                other.send_message(format!("{} wakes you. You stand up.", username));
+                format!("You wake {}.", other.username)
+            }
+        } else if let Some(mobile) = world
+            .mobiles
+            .values_mut()
+            .find(|m| m.current_room == room_id && m.matches(&name))
+        {
+            let mobile_name = capitalize(&mobile.template.room_description);
+            if mobile.position.is_awake() {
+                format!("{} is already awake.", mobile_name)
+            } else {
+                mobile.position = Position::Standing;
+                format!("You wake {}.", mobile.template.room_description)
+            }
+        } else {
+            String::from("They aren't here.")
+        };
+
+        if let Some(player) = players.read().get(&self.sender) {
+            player.send_message(response);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{actions::PlayerAction, affects, player::Players, world::World};
+
+#[derive(Debug)]
+pub struct ScoreAction {
+    pub sender: u32,
+}
+
+impl PlayerAction for ScoreAction {
+    fn perform(&self, players: &Players, _world: &mut World) {
+        if let Some(sending_player) = players.read().get(&self.sender) {
+            tracing::debug!("Received score from player: {}", sending_player.username);
+            let stats = sending_player.stats();
+            // TODO: Show the base stats alongside the modified ones like ROM does
+            let score = [
+                format!(
+                    "You are {}, level {}.",
+                    sending_player.username, sending_player.level
+                ),
+                format!(
+                    "Race: {}  Sex: {}  Class: {}  Alignment: {}",
+                    sending_player.race.name,
+                    sending_player.sex,
+                    sending_player.class.name,
+                    sending_player.alignment
+                ),
+                format!("You are {}.", sending_player.position),
+                format!(
+                    "You have {}/{} hit, {}/{} mana, {}/{} movement.",
+                    sending_player.hit,
+                    stats.max_hit,
+                    sending_player.mana,
+                    stats.max_mana,
+                    sending_player.movement,
+                    stats.max_move
+                ),
+                format!(
+                    "Str: {}  Int: {}  Wis: {}  Dex: {}  Con: {}",
+                    stats.strength,
+                    stats.intelligence,
+                    stats.wisdom,
+                    stats.dexterity,
+                    stats.constitution
+                ),
+                format!(
+                    "Hitroll: {}  Damroll: {}  Armor: {}  Saves: {}",
+                    stats.hitroll, stats.damroll, stats.armor, stats.saves
+                ),
+                format!(
+                    "You have scored {} exp, and have {} gold and {} silver coins.",
+                    sending_player.exp, sending_player.gold, sending_player.silver
+                ),
+            ];
+            sending_player.send_message(score.join("\n"));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AffectsAction {
+    pub sender: u32,
+}
+
+impl PlayerAction for AffectsAction {
+    fn perform(&self, players: &Players, _world: &mut World) {
+        let guard = players.read();
+        let Some(player) = guard.get(&self.sender) else {
+            return;
+        };
+        if player.affects.is_empty() {
+            player.send_message(String::from("You are not affected by any spells."));
+            return;
+        }
+
+        // Like ROM, a spell with more than one affect is only named on its first line
+        let mut lines = vec![String::from("You are affected by the following spells:")];
+        let mut previous: Option<&str> = None;
+        for affect in &player.affects {
+            let name = if previous == Some(affect.skill.as_str()) {
+                String::new()
+            } else {
+                format!("Spell: {}", affect.skill)
+            };
+            lines.push(format!("{:<22}: {}", name, affects::describe(affect)));
+            previous = Some(&affect.skill);
+        }
+        player.send_message(lines.join("\n"));
+    }
+}
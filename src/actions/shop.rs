@@ -0,0 +1,346 @@
+use crate::{
+    actions::{capitalize, send_room_message, PlayerAction},
+    objects::{ExtraFlag, ItemType, ObjectInstance},
+    player::{Player, Players},
+    world::World,
+};
+
+// Find a shopkeeper in the player's room who is open for business, telling the player why not if
+// there isn't one. Returns the ID of the keeper's MobileInstance.
+fn find_keeper(world: &World, player: &Player) -> Option<u32> {
+    let room = world.get_player_room(player)?;
+    let keeper = room
+        .get_mobiles(world)
+        .into_iter()
+        .filter_map(|id| world.mobiles.get(&id))
+        .find(|m| m.template.shop.is_some());
+
+    let Some(keeper) = keeper else {
+        player.send_message(String::from("You can't do that here."));
+        return None;
+    };
+
+    let shop = keeper.template.shop.as_ref()?;
+    if let Some(closed) = shop.closed_message(world.time.hour) {
+        player.send_message(format!(
+            "{} says '{}'",
+            capitalize(&keeper.template.room_description),
+            closed
+        ));
+        return None;
+    }
+
+    Some(keeper.id)
+}
+
+// A message from the keeper to the player
+fn keeper_tells(player: &Player, keeper_name: &str, message: String) {
+    player.send_message(format!(
+        "{} tells you '{}'",
+        capitalize(keeper_name),
+        message
+    ));
+}
+
+#[derive(Debug)]
+pub struct ListAction {
+    pub sender: u32,
+}
+
+impl PlayerAction for ListAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        if let Some(sending_player) = players.read().get(&self.sender) {
+            tracing::debug!("Received list from player: {}", sending_player.username);
+            let Some(keeper) =
+                find_keeper(world, sending_player).and_then(|id| world.mobiles.get(&id))
+            else {
+                return;
+            };
+            let Some(shop) = &keeper.template.shop else {
+                return;
+            };
+
+            // Group identical objects together, keeping the order they were stocked in
+            let mut stock: Vec<(&ObjectInstance, u32, Option<u32>)> = Vec::new();
+            for object in &keeper.inventory {
+                let price = shop.selling_price(&object.template);
+                if price == 0 {
+                    continue;
+                }
+                match stock
+                    .iter_mut()
+                    .find(|(o, _, _)| o.template.id == object.template.id)
+                {
+                    Some((_, _, Some(count))) => *count += 1,
+                    Some(_) => {}
+                    // Stock that never runs out doesn't have a quantity
+                    None => stock.push((
+                        object,
+                        price,
+                        (!object.template.has_flag(ExtraFlag::Inventory)).then_some(1),
+                    )),
+                }
+            }
+
+            if stock.is_empty() {
+                sending_player.send_message(String::from("You can't buy anything here."));
+                return;
+            }
+
+            let mut lines = vec![String::from("[Lv Price Qty] Item")];
+            for (object, price, count) in stock {
+                let quantity = count.map_or_else(|| String::from("--"), |c| format!("{:2}", c));
+                lines.push(format!(
+                    "[{:2} {:5} {} ] {}",
+                    object.template.level, price, quantity, object.template.short_description
+                ));
+            }
+            sending_player.send_message(lines.join("\n"));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BuyAction {
+    pub sender: u32,
+    pub target: String,
+}
+
+impl PlayerAction for BuyAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let (username, room_id, object_name) = {
+            let mut guard = players.write();
+            let Some(sending_player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            tracing::debug!("Received buy from player: {}", sending_player.username);
+
+            let Some(keeper_id) = find_keeper(world, sending_player) else {
+                return;
+            };
+            let Some(keeper) = world.mobiles.get(&keeper_id) else {
+                return;
+            };
+            let Some(shop) = &keeper.template.shop else {
+                return;
+            };
+            let keeper_name = keeper.template.room_description.clone();
+
+            let index = keeper
+                .inventory
+                .iter()
+                .position(|o| o.matches(&self.target));
+            let price = index.map_or(0, |i| shop.selling_price(&keeper.inventory[i].template));
+            let Some(index) = index.filter(|_| price > 0) else {
+                keeper_tells(
+                    sending_player,
+                    &keeper_name,
+                    String::from("I don't sell that -- try 'list'."),
+                );
+                return;
+            };
+            let object = &keeper.inventory[index];
+
+            if sending_player.wealth() < price {
+                keeper_tells(
+                    sending_player,
+                    &keeper_name,
+                    format!(
+                        "You can't afford to buy {}.",
+                        object.template.short_description
+                    ),
+                );
+                return;
+            }
+
+            // Stock that never runs out sells copies, anything else is handed over
+            let bought = if object.template.has_flag(ExtraFlag::Inventory) {
+                let object_id = object.template.id;
+                world.create_object(object_id)
+            } else {
+                world
+                    .mobiles
+                    .get_mut(&keeper_id)
+                    .map(|keeper| keeper.inventory.remove(index))
+            };
+            let Some(bought) = bought else {
+                return;
+            };
+
+            let object_name = bought.template.short_description.clone();
+            sending_player.deduct_cost(price);
+            if let Some(keeper) = world.mobiles.get_mut(&keeper_id) {
+                keeper.add_money(price);
+            }
+            sending_player.send_message(format!("You buy {} for {} silver.", object_name, price));
+            sending_player.inventory.push(bought);
+
+            (
+                sending_player.username.clone(),
+                sending_player.current_room,
+                object_name,
+            )
+        };
+
+        send_room_message(
+            players,
+            room_id,
+            self.sender,
+            format!("{} buys {}.", username, object_name),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SellAction {
+    pub sender: u32,
+    pub target: String,
+}
+
+impl PlayerAction for SellAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let (username, room_id, object_name) = {
+            let mut guard = players.write();
+            let Some(sending_player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            tracing::debug!("Received sell from player: {}", sending_player.username);
+
+            let Some(keeper_id) = find_keeper(world, sending_player) else {
+                return;
+            };
+            let Some(keeper) = world.mobiles.get_mut(&keeper_id) else {
+                return;
+            };
+            let Some(shop) = &keeper.template.shop else {
+                return;
+            };
+
+            let Some(index) = sending_player
+                .inventory
+                .iter()
+                .position(|o| o.matches(&self.target))
+            else {
+                keeper_tells(
+                    sending_player,
+                    &keeper.template.room_description,
+                    String::from("You don't have that item."),
+                );
+                return;
+            };
+
+            let object = &sending_player.inventory[index];
+            if object.template.has_flag(ExtraFlag::NoDrop) {
+                sending_player.send_message(String::from("You can't let go of it."));
+                return;
+            }
+
+            let price = shop.buying_price(&object.template, &keeper.inventory);
+            if price == 0 {
+                sending_player.send_message(format!(
+                    "{} looks uninterested in {}.",
+                    capitalize(&keeper.template.room_description),
+                    object.template.short_description
+                ));
+                return;
+            }
+            if price > keeper.wealth() {
+                keeper_tells(
+                    sending_player,
+                    &keeper.template.room_description,
+                    String::from("I'm afraid I don't have enough wealth to buy that."),
+                );
+                return;
+            }
+
+            let object = sending_player.inventory.remove(index);
+            let object_name = object.template.short_description.clone();
+            keeper.deduct_cost(price);
+            sending_player.add_money(price);
+            sending_player.send_message(format!(
+                "You sell {} for {} silver and {} gold piece{}.",
+                object_name,
+                price % 100,
+                price / 100,
+                if price / 100 == 1 { "" } else { "s" }
+            ));
+
+            // Keepers don't resell junk
+            if object.template.item_type != ItemType::Trash
+                && !object.template.has_flag(ExtraFlag::SellExtract)
+            {
+                keeper.inventory.push(object);
+            }
+
+            (
+                sending_player.username.clone(),
+                sending_player.current_room,
+                object_name,
+            )
+        };
+
+        send_room_message(
+            players,
+            room_id,
+            self.sender,
+            format!("{} sells {}.", username, object_name),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ValueAction {
+    pub sender: u32,
+    pub target: String,
+}
+
+impl PlayerAction for ValueAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        if let Some(sending_player) = players.read().get(&self.sender) {
+            tracing::debug!("Received value from player: {}", sending_player.username);
+            let Some(keeper) =
+                find_keeper(world, sending_player).and_then(|id| world.mobiles.get(&id))
+            else {
+                return;
+            };
+            let Some(shop) = &keeper.template.shop else {
+                return;
+            };
+            let keeper_name = &keeper.template.room_description;
+
+            let Some(object) = sending_player
+                .inventory
+                .iter()
+                .find(|o| o.matches(&self.target))
+            else {
+                keeper_tells(
+                    sending_player,
+                    keeper_name,
+                    String::from("You don't have that item."),
+                );
+                return;
+            };
+
+            let price = shop.buying_price(&object.template, &keeper.inventory);
+            if price == 0 {
+                sending_player.send_message(format!(
+                    "{} looks uninterested in {}.",
+                    capitalize(keeper_name),
+                    object.template.short_description
+                ));
+                return;
+            }
+
+            keeper_tells(
+                sending_player,
+                keeper_name,
+                format!(
+                    "I'll give you {} silver and {} gold coins for {}.",
+                    price % 100,
+                    price / 100,
+                    object.template.short_description
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    actions::{send_room_message, PlayerAction},
+    character::{find_in_room, CharacterId},
+    input,
+    mobiles::ActFlag,
+    player::Players,
+    random::number_percent,
+    room::RoomFlag,
+    skills::{self, Cast, Skill, TargetType, ADEPT, SKILLS},
+    world::World,
+};
+
+#[derive(Debug)]
+pub struct CastAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl CastAction {
+    // Work out which spell and who it's being cast on, or why it can't be
+    fn prepare(
+        &self,
+        players: &Players,
+        world: &World,
+    ) -> Result<(&'static Skill, Option<CharacterId>), String> {
+        let caster = CharacterId::Player(self.sender);
+        let argument = self.argument.as_deref().unwrap_or_default();
+        let (spell_name, target_name) = input::one_argument(argument);
+        if spell_name.is_empty() {
+            return Err(String::from("Cast which what where?"));
+        }
+
+        let guard = players.read();
+        let Some(player) = guard.get(&self.sender) else {
+            return Err(String::new());
+        };
+        let skill = skills::find(spell_name, |s| {
+            s.spell.is_some() && player.proficiency(s) > 0
+        })
+        .ok_or_else(|| String::from("You don't know any spells of that name."))?;
+        if player.position < skill.min_position {
+            return Err(String::from("You can't concentrate enough."));
+        }
+        let room_id = player.current_room;
+        let cost = skill.mana_cost(player.skill_level(skill), player.level);
+        let mana = player.mana;
+        drop(guard);
+
+        let found = (!target_name.is_empty())
+            .then(|| find_in_room(players, world, caster, room_id, target_name))
+            .flatten();
+        let target = match skill.target {
+            TargetType::Ignore => None,
+            TargetType::CharOffensive => match found {
+                _ if target_name.is_empty() => {
+                    return Err(String::from("Cast the spell on whom?"));
+                }
+                None => return Err(String::from("They aren't here.")),
+                Some(CharacterId::Player(_)) => {
+                    return Err(String::from("You can't attack other players."));
+                }
+                Some(CharacterId::Mobile(mobile_id)) => {
+                    let room = world.get_room(room_id);
+                    if room.is_some_and(|r| r.has_flag(RoomFlag::Safe)) {
+                        return Err(String::from("Not in this room."));
+                    }
+                    if world
+                        .mobiles
+                        .get(&mobile_id)
+                        .is_some_and(|m| m.template.shop.is_some())
+                    {
+                        return Err(String::from("The shopkeeper wouldn't like that."));
+                    }
+                    found
+                }
+            },
+            TargetType::CharDefensive if target_name.is_empty() => Some(caster),
+            TargetType::CharDefensive => {
+                Some(found.ok_or_else(|| String::from("They aren't here."))?)
+            }
+            TargetType::CharSelf => {
+                if !target_name.is_empty() && found != Some(caster) {
+                    return Err(String::from("You cannot cast this spell on another."));
+                }
+                Some(caster)
+            }
+        };
+
+        if mana < cost {
+            return Err(String::from("You don't have enough mana."));
+        }
+        Ok((skill, target))
+    }
+}
+
+impl PlayerAction for CastAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let (skill, target) = match self.prepare(players, world) {
+            Ok(prepared) => prepared,
+            Err(message) => {
+                if let Some(player) = players.read().get(&self.sender) {
+                    player.send_message(message);
+                }
+                return;
+            }
+        };
+
+        // Like ROM, failing costs half the mana and still takes the time
+        let (username, room_id, level, success) = {
+            let mut guard = players.write();
+            let Some(player) = guard.get_mut(&self.sender) else {
+                return;
+            };
+            let cost = skill.mana_cost(player.skill_level(skill), player.level);
+            player.wait_state(skill.beats);
+            let success = number_percent() <= player.proficiency(skill) as i32;
+            if success {
+                player.mana -= cost;
+            } else {
+                player.mana -= cost / 2;
+                player.send_message(String::from("You lost your concentration."));
+            }
+            if let Some(message) = player.improve_skill(skill, success) {
+                player.send_message(message);
+            }
+            (
+                player.username.clone(),
+                player.current_room,
+                player.level,
+                success,
+            )
+        };
+        if !success {
+            return;
+        }
+
+        // TODO: ROM garbles the words so other classes can't tell what's being cast
+        send_room_message(
+            players,
+            room_id,
+            self.sender,
+            format!("{} utters the words, '{}'.", username, skill.name),
+        );
+        if let Some(spell) = skill.spell {
+            let cast = Cast {
+                skill,
+                level,
+                caster: CharacterId::Player(self.sender),
+                target,
+            };
+            spell(&cast, players, world);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PracticeAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for PracticeAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let mut guard = players.write();
+        let Some(player) = guard.get_mut(&self.sender) else {
+            return;
+        };
+
+        // Everything the player knows, three to a line
+        let Some(name) = self.argument.as_deref() else {
+            let known: Vec<String> = SKILLS
+                .iter()
+                .filter(|s| player.proficiency(s) > 0)
+                .map(|s| format!("{:<18} {:>3}%", s.name, player.proficiency(s)))
+                .collect();
+            let lines: Vec<String> = known.chunks(3).map(|row| row.join("    ")).collect();
+            player.send_message(format!(
+                "{}\nYou have {} practice sessions left.",
+                lines.join("\n"),
+                player.practices
+            ));
+            return;
+        };
+
+        let room_id = player.current_room;
+        let has_master = world.mobiles.values().any(|m| {
+            m.current_room == room_id && m.template.act_flags & ActFlag::Practice.bit() != 0
+        });
+        let skill = skills::find(name, |s| player.proficiency(s) > 0);
+        let response = if !player.position.is_awake() {
+            String::from("In your dreams, or what?")
+        } else if !has_master {
+            String::from("You can't do that here.")
+        } else if player.practices == 0 {
+            String::from("You have no practice sessions left.")
+        } else if let Some(skill) = skill {
+            let learned = player.proficiency(skill);
+            if learned >= ADEPT {
+                format!("You are already learned at {}.", skill.name)
+            } else {
+                let gain = skills::learn_rate(player.stats().intelligence) as u32;
+                let learned = (learned + gain).min(ADEPT);
+                player.practices -= 1;
+                player.skills.insert(String::from(skill.name), learned);
+                if learned >= ADEPT {
+                    format!("You are now learned at {}.", skill.name)
+                } else {
+                    format!("You practice {}.", skill.name)
+                }
+            }
+        } else {
+            String::from("You can't practice that.")
+        };
+        player.send_message(response);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    actions::PlayerAction,
+    pathfind::{self, PathOptions},
+    player::Players,
+    random::number_percent,
+    skills,
+    world::World,
+};
+use std::collections::HashSet;
+
+// Players and mobiles in an area whose names start with what was typed, as (name, room) pairs.
+// Players come first.
+fn find_in_area(
+    players: &Players,
+    world: &World,
+    sender: u32,
+    area: Option<usize>,
+    name: &str,
+) -> Vec<(String, u32)> {
+    let name = name.to_lowercase();
+    let in_area = |room_id: u32| area.is_some() && world.room_area(room_id) == area;
+    let mut found: Vec<(String, u32)> = players
+        .read()
+        .values()
+        .filter(|p| p.id != sender && in_area(p.current_room))
+        .filter(|p| p.username.to_lowercase().starts_with(&name))
+        .map(|p| (p.username.clone(), p.current_room))
+        .collect();
+    let mut mobiles: Vec<_> = world
+        .mobiles
+        .values()
+        .filter(|m| in_area(m.current_room) && m.matches(&name))
+        .collect();
+    mobiles.sort_by_key(|m| m.id);
+    found.extend(
+        mobiles
+            .into_iter()
+            .map(|m| (m.template.room_description.clone(), m.current_room)),
+    );
+    found
+}
+
+fn room_name(world: &World, room_id: u32) -> String {
+    world
+        .get_room(room_id)
+        .map_or_else(|| String::from("somewhere"), |r| r.name.clone())
+}
+
+// The track skill. Players who don't know it well enough lose the trail.
+#[derive(Debug)]
+pub struct TrackAction {
+    pub sender: u32,
+    pub target: Option<String>,
+}
+
+impl PlayerAction for TrackAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let Some(skill) = skills::lookup("track") else {
+            return;
+        };
+        let Some((room_id, learned)) = players
+            .read()
+            .get(&self.sender)
+            .map(|p| (p.current_room, p.proficiency(skill)))
+        else {
+            return;
+        };
+        let success = number_percent() <= learned as i32;
+        let response = match self.target.as_deref() {
+            _ if learned == 0 => String::from("You don't know how to track."),
+            None => String::from("Whom are you trying to track?"),
+            Some(_) if !success => String::from("You can't find a trail."),
+            Some(target) => {
+                let area = world.room_area(room_id);
+                let rooms: HashSet<u32> = find_in_area(players, world, self.sender, area, target)
+                    .into_iter()
+                    .map(|(_, room)| room)
+                    .collect();
+                let options = PathOptions {
+                    through_doors: true,
+                    area,
+                    ..PathOptions::default()
+                };
+                match pathfind::find_path_to(world, room_id, &options, |r| rooms.contains(&r)) {
+                    Some((_, path)) if path.is_empty() => {
+                        String::from("You're already in the same room!")
+                    }
+                    Some((_, path)) => format!("You sense a trail {} from here.", path[0]),
+                    None => String::from("You can't find a trail."),
+                }
+            }
+        };
+        if let Some(player) = players.write().get_mut(&self.sender) {
+            player.send_message(response);
+            if learned > 0 && self.target.is_some() {
+                player.wait_state(skill.beats);
+                if let Some(message) = player.improve_skill(skill, success) {
+                    player.send_message(message);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct WhereAction {
+    pub sender: u32,
+    pub target: Option<String>,
+}
+
+impl PlayerAction for WhereAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let Some(room_id) = players.read().get(&self.sender).map(|p| p.current_room) else {
+            return;
+        };
+        let area = world.room_area(room_id);
+
+        let response = match self.target.as_deref() {
+            // Everyone else in the area
+            None => {
+                let mut nearby: Vec<(String, u32)> = players
+                    .read()
+                    .values()
+                    .filter(|p| p.id != self.sender)
+                    .filter(|p| area.is_some() && world.room_area(p.current_room) == area)
+                    .map(|p| (p.username.clone(), p.current_room))
+                    .collect();
+                nearby.sort();
+                let lines: Vec<String> = nearby
+                    .into_iter()
+                    .map(|(name, room)| format!("{:<28} {}", name, room_name(world, room)))
+                    .collect();
+                if lines.is_empty() {
+                    String::from("Players near you:\nNone")
+                } else {
+                    format!("Players near you:\n{}", lines.join("\n"))
+                }
+            }
+            Some(target) => match find_in_area(players, world, self.sender, area, target).first() {
+                Some((name, room)) => format!("{:<28} {}", name, room_name(world, *room)),
+                None => format!("You didn't find any {}.", target),
+            },
+        };
+        if let Some(player) = players.read().get(&self.sender) {
+            player.send_message(response);
+        }
+    }
+}
+
+// The way to a room as a speedwalk, for builders
+#[derive(Debug)]
+pub struct PathAction {
+    pub sender: u32,
+    pub argument: Option<String>,
+}
+
+impl PlayerAction for PathAction {
+    fn perform(&self, players: &Players, world: &mut World) {
+        let Some((username, room_id)) = players
+            .read()
+            .get(&self.sender)
+            .map(|p| (p.username.clone(), p.current_room))
+        else {
+            return;
+        };
+
+        let response = if !world.is_builder(&username) {
+            String::from("Only builders can do that.")
+        } else {
+            match self.argument.as_deref().map(str::parse::<u32>) {
+                Some(Ok(vnum)) if world.get_room(vnum).is_none() => {
+                    format!("There's no room {}.", vnum)
+                }
+                Some(Ok(vnum)) => {
+                    let options = PathOptions {
+                        through_doors: true,
+                        ..PathOptions::default()
+                    };
+                    match pathfind::find_path(world, room_id, vnum, &options) {
+                        Some(path) if path.is_empty() => String::from("You're already there."),
+                        Some(path) => format!(
+                            "{} ({} rooms): {}",
+                            room_name(world, vnum),
+                            path.len(),
+                            pathfind::speedwalk(&path)
+                        ),
+                        None => format!("There's no way to room {} from here.", vnum),
+                    }
+                }
+                _ => String::from("Syntax: path <vnum>"),
+            }
+        };
+        if let Some(player) = players.read().get(&self.sender) {
+            player.send_message(response);
+        }
+    }
+}
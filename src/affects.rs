@@ -0,0 +1,102 @@
+// Things temporarily changing a character, like ROM's AFFECT_DATA, eg the armor spell making them
+// harder to hit for a while. Nothing is changed on the character when an affect is added or
+// removed. Their stats and flags are worked out from the affects they have whenever they're
+// needed, so taking an affect away always undoes exactly what it did.
+use crate::{
+    mobiles::AffectFlag,
+    skills,
+    stats::{Apply, Stats},
+};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Affect {
+    // The skill or spell it came from
+    pub skill: String,
+    // Ticks left before it wears off. Affects with a negative duration never do.
+    pub duration: i32,
+    pub location: Apply,
+    pub modifier: i32,
+    // The AffectFlag bits it gives the character
+    pub bits: u64,
+}
+
+pub fn is_affected_by(affects: &[Affect], skill: &str) -> bool {
+    affects.iter().any(|a| a.skill == skill)
+}
+
+// Like ROM's affect_to_char
+pub fn add(affects: &mut Vec<Affect>, affect: Affect) {
+    affects.push(affect);
+}
+
+// All the AffectFlag bits the affects give
+pub fn bits(affects: &[Affect]) -> u64 {
+    affects.iter().fold(0, |bits, a| bits | a.bits)
+}
+
+pub fn apply(stats: &mut Stats, affects: &[Affect]) {
+    for affect in affects {
+        stats.apply(affect.location, affect.modifier);
+    }
+}
+
+// The flags in some bits, eg "detect invis, sanctuary", like ROM's affect_bit_name
+fn bit_names(bits: u64) -> String {
+    AffectFlag::iter()
+        .filter(|flag| bits & flag.bit() != 0)
+        .map(|flag| flag.to_string().replace('_', " "))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// What an affect does, for the affects command, eg "modifies armor class by -20 for 24 hours"
+pub fn describe(affect: &Affect) -> String {
+    let what = if affect.location == Apply::None {
+        format!("adds {}", bit_names(affect.bits))
+    } else {
+        format!("modifies {} by {}", affect.location, affect.modifier)
+    };
+    if affect.duration < 0 {
+        format!("{} permanently", what)
+    } else {
+        format!("{} for {} hours", what, affect.duration)
+    }
+}
+
+// Whether a character with the first set of bits can see one with the second, like the affect
+// parts of ROM's can_see
+pub fn can_see(looker: u64, target: u64) -> bool {
+    if looker & AffectFlag::Blind.bit() != 0 {
+        return false;
+    }
+    target & AffectFlag::Invisible.bit() == 0 || looker & AffectFlag::DetectInvis.bit() != 0
+}
+
+// Count down the affects on a character for a tick. Like ROM, an affect goes the tick after it
+// gets to zero. Returns what to tell the character about the skills that have worn off.
+pub fn tick(affects: &mut Vec<Affect>) -> Vec<&'static str> {
+    let mut worn_off = Vec::new();
+    affects.retain_mut(|affect| {
+        if affect.duration > 0 {
+            affect.duration -= 1;
+            true
+        } else if affect.duration < 0 {
+            true
+        } else {
+            worn_off.push(affect.skill.clone());
+            false
+        }
+    });
+
+    // Skills with more than one affect only say they've gone once they all have
+    worn_off.dedup();
+    worn_off
+        .iter()
+        .filter(|skill| !is_affected_by(affects, skill))
+        .filter_map(|skill| skills::lookup(skill))
+        .map(|skill| skill.msg_off)
+        .filter(|message| !message.is_empty())
+        .collect()
+}
@@ -1,7 +1,113 @@
-use crate::{mobiles::Mobile, reset::ResetCommand, room::Room};
+use crate::{
+    area_file::AreaFormat,
+    mobiles::Mobile,
+    objects::Object,
+    programs::{MobProgram, ProgramKind},
+    reset::ResetCommand,
+    room::Room,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+// What an area says about itself, from the header of its file
+#[derive(Clone, Debug)]
+pub struct AreaMetadata {
+    pub name: String,
+    pub author: String,
+    // The vnums the area's rooms, mobiles and objects should fall in, inclusive
+    pub vnums: (u32, u32),
+    // The levels the area is meant for, or None if it's for everyone
+    pub levels: Option<(u32, u32)>,
+    // Where the area was loaded from, so it can be reloaded
+    pub file: Option<PathBuf>,
+    // What format that file is in, so it's saved and reloaded the same way
+    pub format: Option<AreaFormat>,
+    // A ROM area's credits, eg "{ 5 20} Hatchet  Mud School", which are saved as they were rather
+    // than pieced back together from the levels, author and name
+    pub credits: Option<String>,
+    // The fields of an OLC #AREADATA header, so areas that had one are saved with one
+    pub areadata: Option<Vec<(String, String)>>,
+}
+
+impl AreaMetadata {
+    // The level range the way ROM writes it in area headers and the area list, eg "{ 5 20}"
+    pub fn level_str(&self) -> String {
+        match self.levels {
+            Some((low, high)) => format!("{{{:2} {:2}}}", low, high),
+            None => String::from("{ All }"),
+        }
+    }
+}
 
 pub struct Area {
-    pub rooms: Vec<Room>,
-    pub mobiles: Vec<Mobile>,
+    pub metadata: AreaMetadata,
+    pub rooms: HashMap<u32, Room>,
+    pub mobiles: HashMap<u32, Mobile>,
+    pub objects: HashMap<u32, Object>,
     pub resets: Vec<ResetCommand>,
+    pub programs: HashMap<(ProgramKind, u32), MobProgram>,
+    // Area updates since the area last reset
+    pub age: u32,
+    // Whether the area has been edited since it was loaded
+    pub changed: bool,
+    // When its file was last changed, as of loading or saving it, so that the file watcher can
+    // tell someone editing the file from our own saves
+    pub modified: Option<SystemTime>,
+}
+
+impl Area {
+    pub fn new(metadata: AreaMetadata) -> Self {
+        Area {
+            metadata,
+            rooms: HashMap::new(),
+            mobiles: HashMap::new(),
+            objects: HashMap::new(),
+            resets: Vec::new(),
+            programs: HashMap::new(),
+            age: 0,
+            changed: false,
+            modified: None,
+        }
+    }
+
+    pub fn add_room(&mut self, room: Room) {
+        self.rooms.insert(room.id, room);
+    }
+
+    pub fn add_mobile(&mut self, mobile: Mobile) {
+        self.mobiles.insert(mobile.id, mobile);
+    }
+
+    pub fn add_object(&mut self, object: Object) {
+        self.objects.insert(object.id, object);
+    }
+
+    pub fn add_program(&mut self, kind: ProgramKind, program: MobProgram) {
+        self.programs.insert((kind, program.vnum), program);
+    }
+
+    // The programs of one kind, in vnum order
+    pub fn programs_of(&self, kind: ProgramKind) -> Vec<&MobProgram> {
+        let mut programs: Vec<&MobProgram> = self
+            .programs
+            .iter()
+            .filter(|((k, _), _)| *k == kind)
+            .map(|(_, program)| program)
+            .collect();
+        programs.sort_by_key(|p| p.vnum);
+        programs
+    }
+
+    // The lowest and highest vnum of anything actually in the area, for areas whose header
+    // doesn't say
+    pub fn used_vnums(&self) -> Option<(u32, u32)> {
+        let vnums = self
+            .rooms
+            .keys()
+            .chain(self.mobiles.keys())
+            .chain(self.objects.keys());
+        let (low, high) = vnums.fold((u32::MAX, 0), |(low, high), &v| (low.min(v), high.max(v)));
+        (low <= high).then_some((low, high))
+    }
 }
@@ -0,0 +1,173 @@
+// Loading and saving area files in whichever format they're in. ROM .are files are handled by the
+// merc module, CircleMUD's split world files by the circle module, and our own native format is
+// the area serialized with serde as TOML, RON or JSON.
+use crate::{
+    area::Area,
+    circle::{self, CircleLoader},
+    merc::MercLoader,
+    world::World,
+};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+mod error;
+mod native;
+mod watch;
+
+pub use error::LoadError;
+pub use watch::watch;
+
+use native::NativeLoader;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AreaFormat {
+    Rom,
+    Toml,
+    Ron,
+    Json,
+    // CircleMUD and tbaMUD, where a zone is split over .wld, .mob, .obj, .zon and .shp files
+    Circle,
+}
+
+// Something that can turn the file(s) at a path into an area. Problems that only lose part of
+// the area are added to the warnings rather than failing the whole thing.
+pub trait AreaLoader {
+    fn load(&self, path: &Path, warnings: &mut Vec<LoadError>) -> Result<Area, LoadError>;
+}
+
+// An entry in the areas list of settings.toml. Usually just the path, but the format can be given
+// when the extension doesn't say, eg `{ path = "lib/world/30", format = "circle" }`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum AreaEntry {
+    Path(PathBuf),
+    Table {
+        path: PathBuf,
+        #[serde(default)]
+        format: Option<AreaFormat>,
+    },
+}
+
+impl AreaEntry {
+    pub fn path(&self) -> &Path {
+        match self {
+            AreaEntry::Path(path) | AreaEntry::Table { path, .. } => path,
+        }
+    }
+
+    pub fn format(&self) -> Option<AreaFormat> {
+        match self {
+            AreaEntry::Path(_) => None,
+            AreaEntry::Table { format, .. } => *format,
+        }
+    }
+}
+
+impl AreaFormat {
+    // Which format a file is in, going by its extension
+    pub fn from_path(path: &Path) -> Option<AreaFormat> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "are" => Some(AreaFormat::Rom),
+            "toml" => Some(AreaFormat::Toml),
+            "ron" => Some(AreaFormat::Ron),
+            "json" => Some(AreaFormat::Json),
+            "wld" | "mob" | "obj" | "zon" | "shp" => Some(AreaFormat::Circle),
+            _ => None,
+        }
+    }
+
+    pub fn loader(self) -> Box<dyn AreaLoader> {
+        match self {
+            AreaFormat::Rom => Box::new(MercLoader),
+            AreaFormat::Circle => Box::new(CircleLoader),
+            AreaFormat::Toml | AreaFormat::Ron | AreaFormat::Json => Box::new(NativeLoader(self)),
+        }
+    }
+}
+
+fn unknown_format(path: &Path) -> String {
+    format!(
+        "{} isn't an area file, expected .are, .toml, .ron, .json or CircleMUD's .wld/.zon, or \
+         give its format in the settings",
+        path.display()
+    )
+}
+
+// Load an area, in the given format or else the one its extension suggests. Returns the area
+// along with any warnings about parts of it that were skipped.
+pub fn load(path: &Path, format: Option<AreaFormat>) -> Result<(Area, Vec<LoadError>), LoadError> {
+    let format = format
+        .or_else(|| AreaFormat::from_path(path))
+        .ok_or_else(|| LoadError::new(unknown_format(path)))?;
+
+    let mut warnings = Vec::new();
+    let mut area = format.loader().load(path, &mut warnings)?;
+    area.metadata.file = Some(path.to_path_buf());
+    area.metadata.format = Some(format);
+    area.modified = modified(path, Some(format));
+    Ok((area, warnings))
+}
+
+// The files an area is made of. That's just the one, apart from Circle zones.
+pub fn files(path: &Path, format: Option<AreaFormat>) -> Vec<PathBuf> {
+    match format.or_else(|| AreaFormat::from_path(path)) {
+        Some(AreaFormat::Circle) => circle::zone_files(path),
+        _ => vec![path.to_path_buf()],
+    }
+}
+
+// When any of an area's files was last changed
+pub fn modified(path: &Path, format: Option<AreaFormat>) -> Option<SystemTime> {
+    files(path, format)
+        .iter()
+        .filter_map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+// Save an area, in the given format or else the one its path suggests. It's written to a temporary
+// file first and then moved over the old one, so a failed save doesn't leave a half-written area
+// behind.
+pub fn save(area: &Area, world: &World, path: &Path, format: Option<AreaFormat>) -> io::Result<()> {
+    let format = format
+        .or_else(|| AreaFormat::from_path(path))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, unknown_format(path)))?;
+    // TODO: A writer for Circle's world files. Until then they can be converted to another format.
+    if format == AreaFormat::Circle {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "CircleMUD areas can't be saved yet, convert them to .are or .toml first",
+        ));
+    }
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file"))?;
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+
+    let result = (|| {
+        let mut out = BufWriter::new(File::create(&temp_path)?);
+        match format {
+            AreaFormat::Rom => crate::merc::write_area(area, world, &mut out)?,
+            _ => native::write(area, format, &mut out)?,
+        }
+        out.flush()?;
+        out.get_ref().sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+// Write out a map in key order, so that saving an area twice gives the same file
+pub fn sorted_map<S: Serializer, V: Serialize>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
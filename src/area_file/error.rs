@@ -0,0 +1,64 @@
+// Problems found loading an area, saying as closely as we can where they are. The same type is
+// used for errors, which stop the area loading, and warnings about things that were skipped.
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Default)]
+pub struct LoadError {
+    pub file: Option<PathBuf>,
+    // eg "#ROOMS"
+    pub section: Option<String>,
+    pub vnum: Option<u32>,
+    // Line and column, counting from 1
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl LoadError {
+    pub fn new(message: impl Into<String>) -> LoadError {
+        LoadError {
+            message: message.into(),
+            ..LoadError::default()
+        }
+    }
+
+    pub fn vnum(mut self, vnum: u32) -> LoadError {
+        self.vnum = Some(vnum);
+        self
+    }
+
+    // Which file it's in, unless that's already known. Readers only see the text of a file, so the
+    // loader fills this in afterwards.
+    pub fn in_file(mut self, file: &Path) -> LoadError {
+        self.file.get_or_insert_with(|| file.to_path_buf());
+        self
+    }
+
+    // Point at a byte offset into the text of the file
+    pub fn at(mut self, text: &str, offset: usize) -> LoadError {
+        let before = &text[..offset.min(text.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        self.position = Some((line, column));
+        self
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}", file.display())?;
+            if let Some((line, column)) = self.position {
+                write!(f, ":{}:{}", line, column)?;
+            }
+            write!(f, ": ")?;
+        }
+        if let Some(section) = &self.section {
+            write!(f, "{}: ", section)?;
+        }
+        if let Some(vnum) = self.vnum {
+            write!(f, "vnum {}: ", vnum)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
@@ -0,0 +1,162 @@
+// Our own area format, which is just the area as we hold it. Compared to ROM's it's easy to read,
+// diff and write by hand or with other tools.
+use super::{AreaFormat, AreaLoader, LoadError};
+use crate::{
+    area::{Area, AreaMetadata},
+    mobiles::Mobile,
+    objects::Object,
+    programs::{MobProgram, ProgramKind},
+    reset::ResetCommand,
+    room::Room,
+};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+#[derive(Deserialize, Serialize)]
+struct NativeArea {
+    name: String,
+    author: String,
+    vnums: (u32, u32),
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    levels: Option<(u32, u32)>,
+    #[serde(default)]
+    rooms: Vec<Room>,
+    #[serde(default)]
+    mobiles: Vec<Mobile>,
+    #[serde(default)]
+    objects: Vec<Object>,
+    #[serde(default)]
+    resets: Vec<ResetCommand>,
+    #[serde(default)]
+    programs: Vec<MobProgram>,
+    #[serde(default)]
+    object_programs: Vec<MobProgram>,
+    #[serde(default)]
+    room_programs: Vec<MobProgram>,
+}
+
+pub struct NativeLoader(pub AreaFormat);
+
+impl AreaLoader for NativeLoader {
+    // Everything in a native area is checked by serde, so there's nothing to warn about
+    fn load(&self, path: &Path, _warnings: &mut Vec<LoadError>) -> Result<Area, LoadError> {
+        let file = File::open(path).map_err(|error| {
+            LoadError::new(format!("Couldn't open the file: {}", error)).in_file(path)
+        })?;
+        load(file, self.0).map_err(|error| error.in_file(path))
+    }
+}
+
+fn load<R: Read>(mut file: R, format: AreaFormat) -> Result<Area, LoadError> {
+    let mut buffer = String::new();
+    file.read_to_string(&mut buffer)
+        .map_err(|error| LoadError::new(format!("Couldn't read the file: {}", error)))?;
+
+    // Each format has its own way of saying where the mistake is
+    let native: NativeArea = match format {
+        AreaFormat::Toml => toml::from_str(&buffer).map_err(|error| {
+            let error_at = LoadError::new(error.message());
+            match error.span() {
+                Some(span) => error_at.at(&buffer, span.start),
+                None => error_at,
+            }
+        })?,
+        AreaFormat::Ron => ron::from_str(&buffer).map_err(|error| LoadError {
+            position: Some((error.position.line, error.position.col)),
+            ..LoadError::new(error.code.to_string())
+        })?,
+        AreaFormat::Json => serde_json::from_str(&buffer).map_err(|error| {
+            // Its message ends with where it is, which we already say
+            let message = error.to_string();
+            let suffix = format!(" at line {} column {}", error.line(), error.column());
+            LoadError {
+                position: Some((error.line(), error.column())),
+                ..LoadError::new(message.strip_suffix(&suffix).unwrap_or(&message))
+            }
+        })?,
+        AreaFormat::Rom | AreaFormat::Circle => {
+            return Err(LoadError::new(format!(
+                "{:?} areas aren't in the native format",
+                format
+            )))
+        }
+    };
+    tracing::info!(
+        area_name = native.name,
+        area_author = native.author,
+        "Loaded native area file"
+    );
+
+    let mut area = Area::new(AreaMetadata {
+        name: native.name,
+        author: native.author,
+        vnums: native.vnums,
+        levels: native.levels,
+        file: None,
+        format: None,
+        credits: None,
+        areadata: None,
+    });
+    for room in native.rooms {
+        area.add_room(room);
+    }
+    for mobile in native.mobiles {
+        area.add_mobile(mobile);
+    }
+    for object in native.objects {
+        area.add_object(object);
+    }
+    let programs = [
+        (ProgramKind::Mobile, native.programs),
+        (ProgramKind::Object, native.object_programs),
+        (ProgramKind::Room, native.room_programs),
+    ];
+    for (kind, programs) in programs {
+        for program in programs {
+            area.add_program(kind, program);
+        }
+    }
+    area.resets = native.resets;
+    Ok(area)
+}
+
+pub fn write<W: Write>(area: &Area, format: AreaFormat, out: &mut W) -> io::Result<()> {
+    let mut rooms: Vec<Room> = area.rooms.values().cloned().collect();
+    rooms.sort_by_key(|r| r.id);
+    let mut mobiles: Vec<Mobile> = area.mobiles.values().cloned().collect();
+    mobiles.sort_by_key(|m| m.id);
+    let mut objects: Vec<Object> = area.objects.values().cloned().collect();
+    objects.sort_by_key(|o| o.id);
+    let programs =
+        |kind| -> Vec<MobProgram> { area.programs_of(kind).into_iter().cloned().collect() };
+
+    let native = NativeArea {
+        name: area.metadata.name.clone(),
+        author: area.metadata.author.clone(),
+        vnums: area.metadata.vnums,
+        levels: area.metadata.levels,
+        rooms,
+        mobiles,
+        objects,
+        resets: area.resets.clone(),
+        programs: programs(ProgramKind::Mobile),
+        object_programs: programs(ProgramKind::Object),
+        room_programs: programs(ProgramKind::Room),
+    };
+
+    let invalid = |error: String| io::Error::new(io::ErrorKind::InvalidData, error);
+    let text = match format {
+        AreaFormat::Toml => toml::to_string_pretty(&native).map_err(|e| invalid(e.to_string()))?,
+        AreaFormat::Ron => ron::ser::to_string_pretty(&native, ron::ser::PrettyConfig::default())
+            .map_err(|e| invalid(e.to_string()))?,
+        AreaFormat::Json => {
+            serde_json::to_string_pretty(&native).map_err(|e| invalid(e.to_string()))?
+        }
+        AreaFormat::Rom | AreaFormat::Circle => {
+            return Err(invalid(format!("{:?} isn't a native format", format)))
+        }
+    };
+    out.write_all(text.as_bytes())
+}
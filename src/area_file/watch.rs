@@ -0,0 +1,47 @@
+// Watching area files for changes, so an area edited outside the game can be reloaded without a
+// restart. There are few enough files that checking their modification times every so often is
+// plenty, rather than needing OS file notifications.
+use super::{modified, AreaFormat};
+use crate::message::ConnectionMessage;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// Tell the game loop when an area changes. An area is only reloaded once its files have stopped
+// changing for a whole poll, so one being written out slowly isn't loaded half-finished.
+pub async fn watch(
+    areas: Vec<(PathBuf, Option<AreaFormat>)>,
+    sender: mpsc::Sender<ConnectionMessage>,
+) {
+    let mut seen: HashMap<PathBuf, Option<SystemTime>> = areas
+        .iter()
+        .map(|(path, format)| (path.clone(), modified(path, *format)))
+        .collect();
+    let mut pending = HashSet::new();
+    tracing::info!(count = areas.len(), "Watching area files for changes");
+
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        for (path, format) in &areas {
+            let time = modified(path, *format);
+            if seen.get(path) != Some(&time) {
+                seen.insert(path.clone(), time);
+                pending.insert(path.clone());
+            } else if pending.remove(path) {
+                tracing::info!(file = %path.display(), "Area file changed");
+                if sender
+                    .send(ConnectionMessage::ReloadArea(path.clone()))
+                    .await
+                    .is_err()
+                {
+                    // The game loop has gone away
+                    return;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Players and mobiles are kept in different places, so things that can happen to either of them,
+// like spells, refer to them with one of these
+use crate::{
+    actions::capitalize,
+    affects::{self, Affect},
+    player::Players,
+    world::World,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharacterId {
+    Player(u32),
+    Mobile(u32),
+}
+
+impl CharacterId {
+    // How the character is referred to, eg "alice" or "the hound"
+    pub fn name(self, players: &Players, world: &World) -> String {
+        match self {
+            CharacterId::Player(id) => players
+                .read()
+                .get(&id)
+                .map_or_else(|| String::from("someone"), |p| p.username.clone()),
+            CharacterId::Mobile(id) => world.mobiles.get(&id).map_or_else(
+                || String::from("something"),
+                |m| m.template.room_description.clone(),
+            ),
+        }
+    }
+
+    // The name at the start of a sentence
+    pub fn capitalized_name(self, players: &Players, world: &World) -> String {
+        capitalize(&self.name(players, world))
+    }
+
+    pub fn room(self, players: &Players, world: &World) -> Option<u32> {
+        match self {
+            CharacterId::Player(id) => players.read().get(&id).map(|p| p.current_room),
+            CharacterId::Mobile(id) => world.mobiles.get(&id).map(|m| m.current_room),
+        }
+    }
+
+    pub fn affected_by(self, players: &Players, world: &World) -> u64 {
+        match self {
+            CharacterId::Player(id) => players.read().get(&id).map_or(0, |p| p.affected_by()),
+            CharacterId::Mobile(id) => world.mobiles.get(&id).map_or(0, |m| m.affected_by()),
+        }
+    }
+
+    // Mobiles have nobody to tell, so only players get the message
+    pub fn send_message(self, players: &Players, message: String) {
+        if let CharacterId::Player(id) = self {
+            if let Some(player) = players.read().get(&id) {
+                player.send_message(message);
+            }
+        }
+    }
+
+    // Run something on whatever the character's affects are kept in
+    pub fn with_affects<T>(
+        self,
+        players: &Players,
+        world: &mut World,
+        f: impl FnOnce(&mut Vec<Affect>) -> T,
+    ) -> Option<T> {
+        match self {
+            CharacterId::Player(id) => players.write().get_mut(&id).map(|p| f(&mut p.affects)),
+            CharacterId::Mobile(id) => world.mobiles.get_mut(&id).map(|m| f(&mut m.affects)),
+        }
+    }
+}
+
+// Everyone can see themselves, otherwise it depends on what they're both affected by
+pub fn can_see(players: &Players, world: &World, looker: CharacterId, target: CharacterId) -> bool {
+    looker == target
+        || affects::can_see(
+            looker.affected_by(players, world),
+            target.affected_by(players, world),
+        )
+}
+
+// Someone in a room by name, the way ROM's get_char_room finds them. Players are looked at before
+// mobiles, "self" is whoever is looking, and nobody they can't see counts.
+pub fn find_in_room(
+    players: &Players,
+    world: &World,
+    looker: CharacterId,
+    room_id: u32,
+    name: &str,
+) -> Option<CharacterId> {
+    let name = name.to_lowercase();
+    if name == "self" {
+        return Some(looker);
+    }
+    let sight = looker.affected_by(players, world);
+    let player = players
+        .read()
+        .values()
+        .filter(|p| p.current_room == room_id)
+        .filter(|p| p.username.to_lowercase().starts_with(&name))
+        .filter(|p| looker == CharacterId::Player(p.id) || affects::can_see(sight, p.affected_by()))
+        .map(|p| p.id)
+        .min();
+    if let Some(id) = player {
+        return Some(CharacterId::Player(id));
+    }
+    let mut mobiles: Vec<_> = world
+        .mobiles
+        .values()
+        .filter(|m| m.current_room == room_id && m.matches(&name))
+        .filter(|m| looker == CharacterId::Mobile(m.id) || affects::can_see(sight, m.affected_by()))
+        .map(|m| m.id)
+        .collect();
+    mobiles.sort();
+    mobiles.first().map(|&id| CharacterId::Mobile(id))
+}
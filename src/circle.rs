@@ -0,0 +1,157 @@
+// Implement for compatability with CircleMUD and tbaMUD style zones, which are split over a world
+// file for each of rooms (.wld), mobiles (.mob), objects (.obj), resets (.zon) and shops (.shp).
+// Circle's fields are mapped onto ours as closely as they go, and anything without a counterpart
+// (rent, thac0, zone command conditions, etc.) is dropped.
+use crate::{
+    area::{Area, AreaMetadata},
+    area_file::{AreaLoader, LoadError},
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod mobiles;
+mod objects;
+mod rooms;
+mod shops;
+mod zones;
+
+const EXTENSIONS: [&str; 5] = ["wld", "mob", "obj", "zon", "shp"];
+
+// Circle's attack types, by number, as the closest ROM damage noun
+const ATTACKS: [&str; 15] = [
+    "hit", "sting", "whip", "slash", "bite", "beating", "crush", "pound", "claw", "beating",
+    "beating", "pierce", "blast", "punch", "stab",
+];
+
+pub struct CircleLoader;
+
+impl AreaLoader for CircleLoader {
+    // The path can be any one of a zone's files, or their name without an extension
+    fn load(&self, path: &Path, warnings: &mut Vec<LoadError>) -> Result<Area, LoadError> {
+        let read = |extension| match zone_file(path, extension) {
+            Some(file) => match fs::read_to_string(&file) {
+                Ok(text) => Ok(Some((file, text))),
+                Err(error) => {
+                    Err(LoadError::new(format!("Couldn't read the file: {}", error)).in_file(&file))
+                }
+            },
+            None => Ok(None),
+        };
+        let world = read("wld")?;
+        let mobs = read("mob")?;
+        let objs = read("obj")?;
+        let zone = read("zon")?;
+        let shops = read("shp")?;
+        if world.is_none() && zone.is_none() {
+            return Err(
+                LoadError::new("Couldn't find a .wld or .zon file for the zone").in_file(path),
+            );
+        }
+
+        // Read one of the files, if the zone has it, saying which file any warnings are about
+        fn parse<T: Default>(
+            file: Option<(PathBuf, String)>,
+            warnings: &mut Vec<LoadError>,
+            read: fn(&str, &mut Vec<LoadError>) -> T,
+        ) -> T {
+            let Some((path, text)) = file else {
+                return T::default();
+            };
+            let mut file_warnings = Vec::new();
+            let result = read(&text, &mut file_warnings);
+            warnings.extend(file_warnings.into_iter().map(|w| w.in_file(&path)));
+            result
+        }
+
+        let zone = parse(zone, warnings, zones::load_zone);
+        tracing::info!(
+            area_name = zone.name,
+            area_author = zone.builders,
+            "Loaded CircleMUD zone"
+        );
+
+        let mut area = Area::new(AreaMetadata {
+            name: zone.name,
+            author: zone.builders,
+            vnums: zone.vnums.unwrap_or_default(),
+            levels: None,
+            file: None,
+            format: None,
+            credits: None,
+            areadata: None,
+        });
+        for room in parse(world, warnings, rooms::load_rooms) {
+            area.add_room(room);
+        }
+        let mut mobiles = parse(mobs, warnings, mobiles::load_mobiles);
+        for shop in parse(shops, warnings, shops::load_shops) {
+            match mobiles.iter_mut().find(|m| m.id == shop.keeper) {
+                Some(keeper) => keeper.shop = Some(shop),
+                None => warnings.push(
+                    LoadError::new("The shop keeper isn't in this zone")
+                        .vnum(shop.keeper)
+                        .in_file(path),
+                ),
+            }
+        }
+        for mobile in mobiles {
+            area.add_mobile(mobile);
+        }
+        for object in parse(objs, warnings, objects::load_objects) {
+            area.add_object(object);
+        }
+        area.resets = zone.resets;
+
+        if zone.vnums.is_none() {
+            area.metadata.vnums = area.used_vnums().unwrap_or_default();
+        }
+        Ok(area)
+    }
+}
+
+// All the files a zone has
+pub fn zone_files(path: &Path) -> Vec<PathBuf> {
+    EXTENSIONS
+        .iter()
+        .filter_map(|extension| zone_file(path, extension))
+        .collect()
+}
+
+// Find one of a zone's files. Stock CircleMUD keeps each kind in its own directory
+// (lib/world/wld/30.wld, lib/world/mob/30.mob, ...) but they can also sit side by side.
+fn zone_file(path: &Path, extension: &str) -> Option<PathBuf> {
+    let beside = path.with_extension(extension);
+    if beside.is_file() {
+        return Some(beside);
+    }
+
+    let directory = path.parent()?;
+    let kind = directory.file_name()?.to_str()?;
+    if !EXTENSIONS.contains(&kind) {
+        return None;
+    }
+    let name = Path::new(path.file_stem()?).with_extension(extension);
+    let world_file = directory.parent()?.join(extension).join(name);
+    world_file.is_file().then_some(world_file)
+}
+
+// Circle and ROM number some flags differently, so move each of Circle's bits to where ours is
+fn map_bits(flags: u64, table: &[(u32, u64)]) -> u64 {
+    table
+        .iter()
+        .filter(|(circle, _)| flags & (1 << circle) != 0)
+        .fold(0, |mapped, (_, ours)| mapped | ours)
+}
+
+// Circle's asciiflag_conv. Flags are numbers or letters like ROM's, but lowercase letters come
+// first, so "a" is the lowest bit rather than "A".
+fn parse_flags(field: &str) -> Option<u64> {
+    if field.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+        return field.parse::<i64>().ok().map(|flags| flags as u64);
+    }
+    field.chars().try_fold(0, |flags, c| match c {
+        'a'..='z' => Some(flags | 1 << (c as u64 - 'a' as u64)),
+        'A'..='Z' => Some(flags | 1 << (26 + c as u64 - 'A' as u64)),
+        _ => None,
+    })
+}
@@ -0,0 +1,173 @@
+// Reading a .mob file
+use super::{map_bits, parse_flags, ATTACKS};
+use crate::{
+    area_file::LoadError,
+    dice::Dice,
+    merc::reader::AreaReader,
+    mobiles::{ActFlag, AffectFlag, Mobile},
+};
+
+// MOB_* bits in Circle, and our ACT_* flag for each
+const ACT_FLAGS: [(u32, ActFlag); 6] = [
+    (1, ActFlag::Sentinel),
+    (2, ActFlag::Scavenger),
+    (3, ActFlag::IsNpc),
+    (5, ActFlag::Aggressive),
+    (6, ActFlag::StayArea),
+    (7, ActFlag::Wimpy),
+];
+
+// AFF_* bits in CircleMUD. tbaMUD moved each of them up by one.
+const AFFECT_FLAGS: [(u32, AffectFlag); 16] = [
+    (0, AffectFlag::Blind),
+    (1, AffectFlag::Invisible),
+    (2, AffectFlag::DetectEvil),
+    (3, AffectFlag::DetectInvis),
+    (4, AffectFlag::DetectMagic),
+    (5, AffectFlag::DetectHidden),
+    (7, AffectFlag::Sanctuary),
+    (9, AffectFlag::Curse),
+    (10, AffectFlag::Infrared),
+    (11, AffectFlag::Poison),
+    (12, AffectFlag::ProtectEvil),
+    (13, AffectFlag::ProtectGood),
+    (14, AffectFlag::Sleep),
+    (18, AffectFlag::Sneak),
+    (19, AffectFlag::Hide),
+    (21, AffectFlag::Charm),
+];
+
+pub fn load_mobiles(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<Mobile> {
+    let mut reader = AreaReader::new(buffer);
+    let mut mobiles = Vec::new();
+
+    loop {
+        match reader.read_letter() {
+            Some('#') => (),
+            Some('$') | None => break,
+            Some(other) => {
+                reader.warn(
+                    None,
+                    format!("Expected '#' while reading mobiles, got '{}'", other),
+                );
+                break;
+            }
+        }
+        let Some(vnum) = reader.read_number().and_then(|v| u32::try_from(v).ok()) else {
+            reader.warn(
+                None,
+                "Bad mobile vnum, giving up on the rest of the mobiles",
+            );
+            break;
+        };
+        match read_mobile(&mut reader, vnum) {
+            Some(mobile) => mobiles.push(mobile),
+            None => {
+                reader.warn(Some(vnum), "Failed to read mobile, giving up on the rest");
+                break;
+            }
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    mobiles
+}
+
+fn position(position: &str) -> String {
+    let position = match position {
+        "0" => "dead",
+        "1" => "mort",
+        "2" => "incap",
+        "3" => "stun",
+        "4" => "sleep",
+        "5" => "rest",
+        "6" => "sit",
+        "7" => "fight",
+        _ => "stand",
+    };
+    String::from(position)
+}
+
+fn read_mobile(reader: &mut AreaReader, vnum: u32) -> Option<Mobile> {
+    let mut mobile = Mobile::new(vnum);
+    mobile.keywords = reader
+        .read_string()?
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    mobile.room_description = reader.read_string()?;
+    mobile.long_description = reader.read_string()?.trim_end().to_string();
+    mobile.description = reader.read_string()?;
+    mobile.damage_type = String::from("hit");
+
+    // "act affect alignment type" in CircleMUD, or with four words each for act and affect in
+    // tbaMUD
+    let words = reader.read_line_words()?;
+    let (act, affect, tba) = match words.len() {
+        4 => (&words[0], &words[1], false),
+        10 => (&words[0], &words[4], true),
+        _ => return None,
+    };
+    let act_table = ACT_FLAGS.map(|(circle, ours)| (circle, ours.bit()));
+    mobile.act_flags = map_bits(parse_flags(act)?, &act_table) | ActFlag::IsNpc.bit();
+    let mut affect = parse_flags(affect)?;
+    if tba {
+        affect >>= 1;
+    }
+    let affect_table = AFFECT_FLAGS.map(|(circle, ours)| (circle, ours.bit()));
+    mobile.affected_by = map_bits(affect, &affect_table);
+    // Detect alignment covers both of ours
+    if mobile.affected_by & AffectFlag::DetectEvil.bit() != 0 {
+        mobile.affected_by |= AffectFlag::DetectGood.bit();
+    }
+    mobile.alignment = words[words.len() - 2].parse().ok()?;
+    let enhanced = words[words.len() - 1] == "E";
+
+    // "level thac0 ac hitdice damdice". Circle's armor class is in tens like ROM's.
+    let words = reader.read_line_words()?;
+    let [level, thac0, armor, hit_dice, damage_dice] = words.get(..5)? else {
+        return None;
+    };
+    mobile.level = level.parse().ok()?;
+    mobile.hitroll = 20 - thac0.parse::<i32>().ok()?;
+    mobile.armor = [armor.parse().ok()?; 4];
+    mobile.hit_dice = hit_dice.parse::<Dice>().ok()?;
+    mobile.damage_dice = damage_dice.parse::<Dice>().ok()?;
+
+    // "gold experience"
+    mobile.wealth = reader.read_number()?;
+    let _experience = reader.read_number()?;
+
+    // "load position, default position, sex"
+    mobile.start_position = position(&reader.read_word()?);
+    mobile.default_position = position(&reader.read_word()?);
+    mobile.sex = match reader.read_number()? {
+        1 => String::from("male"),
+        2 => String::from("female"),
+        _ => String::from("none"),
+    };
+
+    // Enhanced mobiles have "Name: value" lines up to an E
+    if enhanced {
+        loop {
+            let line = reader.read_line_words()?;
+            match line.first().map(String::as_str) {
+                Some("E") => break,
+                Some("BareHandAttack:") => {
+                    let attack: usize = line.get(1)?.parse().ok()?;
+                    if let Some(noun) = ATTACKS.get(attack) {
+                        mobile.damage_type = noun.to_string();
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    // tbaMUD attaches DG scripts after the mobile, which we don't run
+    while reader.peek_letter() == Some('T') {
+        reader.read_line_words()?;
+    }
+
+    Some(mobile)
+}
@@ -0,0 +1,154 @@
+// Reading a .obj file
+use super::{map_bits, parse_flags, ATTACKS};
+use crate::{
+    area_file::LoadError,
+    merc::reader::AreaReader,
+    objects::{ExtraFlag, ItemType, Object, ObjectAffect},
+    room::ExtraDescription,
+    stats::Apply,
+};
+
+// ITEM_* extra flags in Circle that ROM also has, at the same bits
+const EXTRA_FLAGS: [(u32, ExtraFlag); 9] = [
+    (0, ExtraFlag::Glow),
+    (1, ExtraFlag::Hum),
+    (5, ExtraFlag::Invisible),
+    (6, ExtraFlag::Magic),
+    (7, ExtraFlag::NoDrop),
+    (8, ExtraFlag::Bless),
+    (9, ExtraFlag::AntiGood),
+    (10, ExtraFlag::AntiEvil),
+    (11, ExtraFlag::AntiNeutral),
+];
+
+// Circle's ITEM_* types mostly share ROM's numbers, apart from the ones ROM dropped or moved
+pub fn item_type(item_type: i64) -> ItemType {
+    match item_type {
+        // Worn
+        11 => ItemType::Clothing,
+        23 => ItemType::Fountain,
+        // Fire weapons, missiles, "other", traps, notes and pens
+        6 | 7 | 12 | 14 | 16 | 21 => ItemType::Trash,
+        _ => ItemType::from_rom(item_type).unwrap_or(ItemType::Trash),
+    }
+}
+
+fn apply(location: i64) -> Option<Apply> {
+    match location {
+        // Charisma, which we don't have
+        6 => None,
+        10 => Some(Apply::Weight),
+        11 => Some(Apply::Height),
+        _ => Apply::from_rom(location),
+    }
+}
+
+pub fn load_objects(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<Object> {
+    let mut reader = AreaReader::new(buffer);
+    let mut objects = Vec::new();
+
+    loop {
+        match reader.read_letter() {
+            Some('#') => (),
+            Some('$') | None => break,
+            Some(other) => {
+                reader.warn(
+                    None,
+                    format!("Expected '#' while reading objects, got '{}'", other),
+                );
+                break;
+            }
+        }
+        let Some(vnum) = reader.read_number().and_then(|v| u32::try_from(v).ok()) else {
+            reader.warn(
+                None,
+                "Bad object vnum, giving up on the rest of the objects",
+            );
+            break;
+        };
+        match read_object(&mut reader, vnum) {
+            Some(object) => objects.push(object),
+            None => {
+                reader.warn(Some(vnum), "Failed to read object, giving up on the rest");
+                break;
+            }
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    objects
+}
+
+fn read_object(reader: &mut AreaReader, vnum: u32) -> Option<Object> {
+    let mut object = Object::new(vnum);
+    object.keywords = reader
+        .read_string()?
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    object.short_description = reader.read_string()?;
+    object.long_description = reader.read_string()?.trim_end().to_string();
+    let _action_description = reader.read_string()?;
+
+    // "type extra wear" in CircleMUD, or with four words each for extra, wear and permanent
+    // affects in tbaMUD. Wear flags are at the same bits as ROM's.
+    let words = reader.read_line_words()?;
+    let (extra, wear) = match words.len() {
+        13 => (&words[1], &words[5]),
+        len if len >= 3 => (&words[1], &words[2]),
+        _ => return None,
+    };
+    object.item_type = item_type(words[0].parse().ok()?);
+    let table = EXTRA_FLAGS.map(|(circle, ours)| (circle, ours.bit()));
+    object.extra_flags = map_bits(parse_flags(extra)?, &table);
+    object.wear_flags = parse_flags(wear)?;
+
+    // Four values where ROM has five
+    let mut values = reader.read_line_words()?;
+    values.resize(5, String::from("0"));
+    if object.item_type == ItemType::Weapon {
+        // ROM wants the weapon class first and the damage noun rather than a number
+        values[0] = String::from("exotic");
+        let attack: usize = values[3].parse().unwrap_or(0);
+        values[3] = ATTACKS.get(attack).unwrap_or(&"hit").to_string();
+    }
+    object.values = values;
+
+    // "weight cost rent", and in tbaMUD "level timer" after those
+    let words = reader.read_line_words()?;
+    object.weight = words.first()?.parse().ok()?;
+    object.cost = words.get(1)?.parse().ok()?;
+    if let Some(level) = words.get(3) {
+        object.level = level.parse().ok()?;
+    }
+
+    loop {
+        match reader.peek_letter() {
+            Some('E') => {
+                reader.read_letter()?;
+                let keyword = reader.read_string()?;
+                let description = reader.read_string()?;
+                object.extra_descriptions.push(ExtraDescription {
+                    keyword,
+                    description,
+                });
+            }
+            Some('A') => {
+                reader.read_letter()?;
+                let location = reader.read_number()?;
+                let modifier = i32::try_from(reader.read_number()?).ok()?;
+                match apply(location) {
+                    Some(location) => object.affects.push(ObjectAffect { location, modifier }),
+                    None => tracing::debug!(vnum = object.id, location, "Skipping an affect"),
+                }
+            }
+            // tbaMUD's DG scripts, which we don't run
+            Some('T') => {
+                reader.read_line_words()?;
+            }
+            _ => break,
+        }
+    }
+
+    Some(object)
+}
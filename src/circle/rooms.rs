@@ -0,0 +1,130 @@
+// Reading a .wld file
+use super::{map_bits, parse_flags};
+use crate::{
+    area_file::LoadError,
+    merc::reader::AreaReader,
+    message::Direction,
+    room::{Door, ExtraDescription, Room, RoomFlag, Sector},
+};
+
+// ROOM_* bits in Circle, and the flag of ours closest to each
+const ROOM_FLAGS: [(u32, RoomFlag); 6] = [
+    (0, RoomFlag::Dark),
+    (2, RoomFlag::NoMob),
+    (3, RoomFlag::Indoors),
+    (4, RoomFlag::Safe),
+    (9, RoomFlag::Private),
+    (10, RoomFlag::GodsOnly),
+];
+
+pub fn load_rooms(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<Room> {
+    let mut reader = AreaReader::new(buffer);
+    let mut rooms = Vec::new();
+
+    loop {
+        match reader.read_letter() {
+            Some('#') => (),
+            Some('$') | None => break,
+            Some(other) => {
+                reader.warn(
+                    None,
+                    format!("Expected '#' while reading rooms, got '{}'", other),
+                );
+                break;
+            }
+        }
+        let Some(vnum) = reader.read_number().and_then(|v| u32::try_from(v).ok()) else {
+            reader.warn(None, "Bad room vnum, giving up on the rest of the rooms");
+            break;
+        };
+        match read_room(&mut reader, vnum) {
+            Some(room) => rooms.push(room),
+            None => {
+                reader.warn(Some(vnum), "Failed to read room, giving up on the rest");
+                break;
+            }
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    rooms
+}
+
+fn read_room(reader: &mut AreaReader, vnum: u32) -> Option<Room> {
+    let name = reader.read_string()?;
+    let description = reader.read_string()?;
+    let mut room = Room::new(vnum, name.trim(), &description);
+
+    // "zone flags sector" in CircleMUD, or "zone flags flags flags flags sector" in tbaMUD where
+    // only the first set of flags is used by stock rooms
+    let words = reader.read_line_words()?;
+    let flags = parse_flags(words.get(1)?)?;
+    let table = ROOM_FLAGS.map(|(circle, ours)| (circle, ours.bit()));
+    room.flags = map_bits(flags, &table);
+    room.sector = match words.last()?.parse::<i64>().ok()? {
+        // Flying, then underwater
+        8 => Sector::Air,
+        9 => Sector::WaterNoSwim,
+        sector => Sector::from_rom(sector).unwrap_or(Sector::Inside),
+    };
+
+    loop {
+        match reader.read_letter()? {
+            'S' => break,
+            'D' => {
+                let direction = reader.read_number()?;
+                let description = reader.read_string()?;
+                let keyword = reader.read_string()?;
+                let words = reader.read_line_words()?;
+                let [locks, key, destination] = words.get(..3)? else {
+                    return None;
+                };
+                let (locks, key) = (locks.parse().ok()?, key.parse().ok()?);
+                let destination: i64 = destination.parse().ok()?;
+
+                let Some(direction) = u8::try_from(direction)
+                    .ok()
+                    .and_then(Direction::from_number)
+                else {
+                    let message = format!("Skipping an exit in direction {}", direction);
+                    reader.warn(Some(vnum), message);
+                    continue;
+                };
+                // Circle uses -1 (or NOWHERE) for exits that don't lead anywhere
+                let Ok(destination) = u32::try_from(destination) else {
+                    continue;
+                };
+                let direction = direction.to_string();
+                room.add_exit(&direction, destination);
+                room.doors.insert(
+                    direction,
+                    Door {
+                        description,
+                        keyword,
+                        locks,
+                        key,
+                    },
+                );
+            }
+            'E' => {
+                let keyword = reader.read_string()?;
+                let description = reader.read_string()?;
+                room.extra_descriptions.push(ExtraDescription {
+                    keyword,
+                    description,
+                });
+            }
+            other => {
+                reader.warn(Some(vnum), format!("Unknown room field '{}'", other));
+                return None;
+            }
+        }
+    }
+
+    // tbaMUD attaches DG scripts after the room, which we don't run
+    while reader.peek_letter() == Some('T') {
+        reader.read_line_words()?;
+    }
+
+    Some(room)
+}
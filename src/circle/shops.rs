@@ -0,0 +1,96 @@
+// Reading a .shp file. Only the v3 format that CircleMUD 3.0 and tbaMUD write is understood.
+use super::objects::item_type;
+use crate::{area_file::LoadError, merc::reader::AreaReader, shops::Shop};
+
+pub fn load_shops(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<Shop> {
+    let mut reader = AreaReader::new(buffer);
+    let mut shops = Vec::new();
+
+    let version = reader.read_string().unwrap_or_default();
+    if !version.contains("v3.0") {
+        reader.warn(
+            None,
+            format!("Only v3.0 shop files are supported, not '{}'", version),
+        );
+        warnings.extend(reader.into_warnings());
+        return shops;
+    }
+
+    loop {
+        match reader.read_letter() {
+            Some('#') => (),
+            Some('$') | None => break,
+            Some(other) => {
+                reader.warn(
+                    None,
+                    format!("Expected '#' while reading shops, got '{}'", other),
+                );
+                break;
+            }
+        }
+        // The shop's number is written as "#3000~"
+        let number = reader.read_string().unwrap_or_default();
+        match read_shop(&mut reader) {
+            Some(shop) => shops.push(shop),
+            None => {
+                let message = format!("Failed to read shop {}, giving up on the rest", number);
+                reader.warn(None, message);
+                break;
+            }
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    shops
+}
+
+// Read numbers, one per line, up to a -1
+fn read_list(reader: &mut AreaReader) -> Option<Vec<i64>> {
+    let mut list = Vec::new();
+    loop {
+        // Lines can have a keyword after the number, eg "5 sword"
+        let words = reader.read_line_words()?;
+        match words.first()?.parse().ok()? {
+            -1 => return Some(list),
+            n => list.push(n),
+        }
+    }
+}
+
+fn read_profit(reader: &mut AreaReader) -> Option<i32> {
+    let profit: f64 = reader.read_word()?.parse().ok()?;
+    Some((profit * 100.0).round() as i32)
+}
+
+fn read_shop(reader: &mut AreaReader) -> Option<Shop> {
+    // What the shop makes itself, which we don't have
+    let _producing = read_list(reader)?;
+    let profit_buy = read_profit(reader)?;
+    let profit_sell = read_profit(reader)?;
+    let buy_types = read_list(reader)?.into_iter().map(item_type).collect();
+
+    // What the keeper says in different situations
+    for _ in 0..7 {
+        reader.read_string()?;
+    }
+    let _temper = reader.read_word()?;
+    let _bitvector = reader.read_word()?;
+    let keeper = u32::try_from(reader.read_number()?).ok()?;
+    let _trade_with = reader.read_line_words()?;
+    let _rooms = read_list(reader)?;
+
+    // Circle shops can open twice a day, and their hours run past midnight up to 28
+    let open_hour = u32::try_from(reader.read_number()?).ok()?;
+    let close_hour = u32::try_from(reader.read_number()?).ok()?;
+    let _second_open = reader.read_number()?;
+    let _second_close = reader.read_number()?;
+
+    Some(Shop {
+        keeper,
+        buy_types,
+        profit_buy,
+        profit_sell,
+        open_hour: open_hour.min(23),
+        close_hour: close_hour.min(23),
+    })
+}
@@ -0,0 +1,134 @@
+// Reading a .zon file, which has the zone's name and range and its reset commands
+use crate::{
+    area_file::LoadError, equipment::WearLocation, merc::reader::AreaReader, reset::ResetCommand,
+};
+
+#[derive(Default)]
+pub struct Zone {
+    pub name: String,
+    // Only tbaMUD records who built a zone
+    pub builders: String,
+    pub vnums: Option<(u32, u32)>,
+    pub resets: Vec<ResetCommand>,
+}
+
+// Circle numbers the fingers the other way round to ROM
+fn wear_location(location: i64) -> Option<WearLocation> {
+    match location {
+        1 => Some(WearLocation::FingerRight),
+        2 => Some(WearLocation::FingerLeft),
+        _ => WearLocation::from_rom(location),
+    }
+}
+
+pub fn load_zone(buffer: &str, warnings: &mut Vec<LoadError>) -> Zone {
+    let mut zone = Zone::default();
+    let mut reader = AreaReader::new(buffer);
+    if read_header(&mut reader, &mut zone).is_none() {
+        reader.warn(None, "Couldn't read the zone header");
+        warnings.extend(reader.into_warnings());
+        return zone;
+    }
+
+    // One command per line, up to an S. Anything after the arguments is a comment.
+    while let Some(words) = reader.read_line_words() {
+        let command = words.first().map_or("", String::as_str);
+        if command == "S" || command == "$" {
+            break;
+        }
+        if command.starts_with('*') || command.is_empty() {
+            continue;
+        }
+        // Each command has an "if the last one happened" flag first, which we ignore since our
+        // resets already attach G, E and P to whatever was loaded last
+        let numbers: Vec<i64> = words[1..]
+            .iter()
+            .map_while(|word| word.parse().ok())
+            .collect();
+        let vnum = |n: usize| numbers.get(n).and_then(|&v| u32::try_from(v).ok());
+        let limit = |n: usize| vnum(n).filter(|&max| max > 0);
+
+        let reset = match command {
+            "M" => vnum(1)
+                .zip(vnum(3))
+                .map(|(mobile_id, room_id)| ResetCommand::LoadMobile {
+                    mobile_id,
+                    room_id,
+                    world_limit: limit(2),
+                    room_limit: None,
+                }),
+            "O" => vnum(1)
+                .zip(vnum(3))
+                .map(|(object_id, room_id)| ResetCommand::LoadObject { object_id, room_id }),
+            "G" => vnum(1).map(|object_id| ResetCommand::GiveObject { object_id }),
+            "E" => vnum(1)
+                .zip(numbers.get(3).and_then(|&l| wear_location(l)))
+                .map(|(object_id, wear_location)| ResetCommand::EquipObject {
+                    object_id,
+                    wear_location,
+                }),
+            "P" => vnum(1)
+                .zip(vnum(3))
+                .map(|(object_id, container_id)| ResetCommand::PutObject {
+                    object_id,
+                    container_id,
+                    world_limit: limit(2),
+                    count: None,
+                }),
+            "D" => vnum(1).and_then(|room_id| {
+                Some(ResetCommand::SetDoor {
+                    room_id,
+                    direction: u8::try_from(*numbers.get(2)?).ok()?,
+                    state: u8::try_from(*numbers.get(3)?).ok()?,
+                })
+            }),
+            // Removing objects from rooms, and tbaMUD's triggers and variables
+            "R" | "T" | "V" => {
+                tracing::debug!("Skipping zone command {}", command);
+                continue;
+            }
+            _ => None,
+        };
+        match reset {
+            Some(reset) => zone.resets.push(reset),
+            None => reader.warn(
+                None,
+                format!("Couldn't read zone command: {}", words.join(" ")),
+            ),
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    zone
+}
+
+// "#number", then the name, then "top lifespan mode" in CircleMUD. tbaMUD puts the builders
+// before the name, and the bottom of the range before the top.
+fn read_header(reader: &mut AreaReader, zone: &mut Zone) -> Option<()> {
+    if reader.read_letter()? != '#' {
+        return None;
+    }
+    let number = reader.read_number()?;
+    let first = reader.read_string()?;
+    reader.peek_letter()?;
+    let line = reader.read_line()?;
+    let line = match line.strip_suffix('~') {
+        Some(name) => {
+            zone.builders = first;
+            zone.name = name.to_string();
+            reader.read_line_words()?
+        }
+        None => {
+            zone.name = first;
+            line.split_whitespace().map(String::from).collect()
+        }
+    };
+
+    let numbers: Vec<u32> = line.iter().filter_map(|word| word.parse().ok()).collect();
+    zone.vnums = match numbers.as_slice() {
+        [top, _, _] => Some((u32::try_from(number).ok()? * 100, *top)),
+        [bottom, top, ..] => Some((*bottom, *top)),
+        _ => None,
+    };
+    Some(())
+}
@@ -1,7 +1,10 @@
 use crate::{
+    creation::CreationData,
     message::{ConnectionMessage, GameMessage},
-    player::{Player, Players},
+    player::{self, Player, Players},
 };
+use std::path::Path;
+use std::sync::Arc;
 use tokio::{net::TcpStream, sync::mpsc};
 
 use tokio_stream::StreamExt;
@@ -9,6 +12,9 @@ use tokio_util::codec::Framed;
 
 use futures::SinkExt;
 
+mod creation;
+use creation::create_character;
+
 mod login;
 use login::login_prompt;
 
@@ -23,6 +29,8 @@ pub async fn handle_connection(
     players: Players,
     stream: TcpStream,
     game_sender: mpsc::Sender<ConnectionMessage>,
+    creation: Arc<CreationData>,
+    player_directory: &Path,
 ) {
     // Generate a communication channel
     let (player_sender, mut player_receiver) = mpsc::unbounded_channel();
@@ -38,14 +46,28 @@ pub async fn handle_connection(
         // Start logging events with the player name after login
         tracing::Span::current().record("username", &username);
 
+        // New players make a character first. Everyone else gets what they saved when the game
+        // loop loads them.
+        let character = if player::is_saved(player_directory, &username) {
+            creation.default_character()
+        } else {
+            match create_character(&mut telnet, &creation).await {
+                Ok(Some(character)) => character,
+                _ => {
+                    tracing::info!("Client disconnected during character creation");
+                    return;
+                }
+            }
+        };
+
         // Create a new player instance and send it to the game_loop to add to the list of current
         // players
         // TODO: Right now everyone ALWAYS starts in the same room. Also, if Room 1 doesn't
         // exist... not sure what happens but presumably bad
-        let player = Player::new(username, &players, player_sender, 3001);
+        let player = Player::new(username, &players, player_sender, 3001, character);
         // Reserve a copy of the ID for downstream usage
         let player_id = player.id;
-        let create_player_command = ConnectionMessage::AddPlayer(player);
+        let create_player_command = ConnectionMessage::AddPlayer(Box::new(player));
         let _ = game_sender.send(create_player_command).await;
 
         loop {
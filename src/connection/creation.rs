@@ -0,0 +1,202 @@
+use crate::{
+    actions::capitalize,
+    creation::{self, Alignment, Class, CreationData, NewCharacter, Race, Sex},
+};
+use futures::SinkExt;
+use std::error::Error;
+use std::sync::Arc;
+use tokio::net::TcpStream;
+use tokio_stream::StreamExt;
+use tokio_util::codec::Framed;
+
+use crate::connection::{Prompt, TelnetCodec};
+
+// Where new players are in making their character, like the CON_GET_NEW_* states of ROM's nanny
+enum Step {
+    Sex,
+    Race,
+    Class(Arc<Race>),
+    Alignment(Arc<Race>, Arc<Class>),
+    Stats(Arc<Race>, Arc<Class>, Alignment),
+    Confirm(NewCharacter, Alignment),
+}
+
+// Ask something and wait for the answer, or None if the player went away
+async fn ask(telnet: &mut Framed<TcpStream, TelnetCodec>, question: &str) -> Option<String> {
+    let _ = telnet.send(Prompt::new(question)).await;
+    match telnet.next().await {
+        Some(Ok(line)) => Some(line.trim().to_string()),
+        _ => None,
+    }
+}
+
+// "a mage" or "an elf"
+fn with_article(word: &str) -> String {
+    let article = match word.chars().next() {
+        Some('a' | 'e' | 'i' | 'o' | 'u') => "an",
+        _ => "a",
+    };
+    format!("{} {}", article, word)
+}
+
+// "An elf" at the start of a sentence
+fn title(word: &str) -> String {
+    capitalize(&with_article(word))
+}
+
+fn describe(name: &str, description: &str) -> String {
+    format!("  {:<10} {}", name, description)
+}
+
+// Walk a new player through picking their sex, race, class and alignment and rolling their stats.
+// Saying no at the end starts again from the top.
+pub async fn create_character(
+    telnet: &mut Framed<TcpStream, TelnetCodec>,
+    data: &CreationData,
+) -> Result<Option<NewCharacter>, Box<dyn Error + Send>> {
+    let mut sex = Sex::default();
+    let mut step = Step::Sex;
+    loop {
+        // Each step either moves on to the next or stays where it is to ask again
+        step = match step {
+            Step::Sex => {
+                let Some(answer) = ask(telnet, "What is your sex (M/F)? ").await else {
+                    return Ok(None);
+                };
+                match Sex::from_word(&answer) {
+                    Some(chosen) => {
+                        sex = chosen;
+                        Step::Race
+                    }
+                    None => {
+                        let _ = telnet.send("That's not a sex.").await;
+                        Step::Sex
+                    }
+                }
+            }
+            Step::Race => {
+                let mut lines = vec![String::from("The following races are available:")];
+                lines.extend(data.races.iter().map(|r| describe(&r.name, &r.description)));
+                let _ = telnet.send(lines.join("\n")).await;
+                let Some(answer) = ask(telnet, "What is your race? ").await else {
+                    return Ok(None);
+                };
+                match data.find_race(&answer) {
+                    Some(race) => Step::Class(race),
+                    None => {
+                        let _ = telnet.send("That's not a race.").await;
+                        Step::Race
+                    }
+                }
+            }
+            Step::Class(race) => {
+                let mut lines = vec![format!(
+                    "{} can be one of these classes:",
+                    title(&race.name)
+                )];
+                lines.extend(
+                    data.classes
+                        .iter()
+                        .filter(|c| race.allows_class(c))
+                        .map(|c| describe(&c.name, &c.description)),
+                );
+                let _ = telnet.send(lines.join("\n")).await;
+                let Some(answer) = ask(telnet, "What is your class? ").await else {
+                    return Ok(None);
+                };
+                match data.find_class(&answer) {
+                    Some(class) if race.allows_class(&class) => Step::Alignment(race, class),
+                    Some(class) => {
+                        let message = format!(
+                            "{} can't be {}.",
+                            title(&race.name),
+                            with_article(&class.name)
+                        );
+                        let _ = telnet.send(message).await;
+                        Step::Class(race)
+                    }
+                    None => {
+                        let _ = telnet.send("That's not a class.").await;
+                        Step::Class(race)
+                    }
+                }
+            }
+            Step::Alignment(race, class) => {
+                let allowed: Vec<String> = [Alignment::Good, Alignment::Neutral, Alignment::Evil]
+                    .into_iter()
+                    .filter(|&a| class.allows_alignment(a))
+                    .map(|a| a.to_string())
+                    .collect();
+                let choices = match allowed.split_last() {
+                    Some((last, rest)) if !rest.is_empty() => {
+                        format!("{} or {}", rest.join(", "), last)
+                    }
+                    _ => allowed.join(""),
+                };
+                let question = format!("You may be {}. Which alignment? ", choices);
+                let Some(answer) = ask(telnet, &question).await else {
+                    return Ok(None);
+                };
+                match Alignment::from_word(&answer) {
+                    Some(alignment) if class.allows_alignment(alignment) => {
+                        Step::Stats(race, class, alignment)
+                    }
+                    Some(alignment) => {
+                        let message = format!("{} can't be {}.", title(&class.name), alignment);
+                        let _ = telnet.send(message).await;
+                        Step::Alignment(race, class)
+                    }
+                    None => {
+                        let _ = telnet.send("That's not an alignment.").await;
+                        Step::Alignment(race, class)
+                    }
+                }
+            }
+            Step::Stats(race, class, alignment) => {
+                let stats = creation::roll_stats(&race, &class);
+                let rolled = format!(
+                    "Str: {}  Int: {}  Wis: {}  Dex: {}  Con: {}",
+                    stats.strength,
+                    stats.intelligence,
+                    stats.wisdom,
+                    stats.dexterity,
+                    stats.constitution
+                );
+                let _ = telnet.send(rolled).await;
+                let Some(answer) = ask(telnet, "Keep these stats (Y/N)? ").await else {
+                    return Ok(None);
+                };
+                if answer.to_lowercase().starts_with('y') {
+                    let character = NewCharacter {
+                        sex,
+                        race,
+                        class,
+                        alignment: alignment.value(),
+                        stats,
+                    };
+                    Step::Confirm(character, alignment)
+                } else {
+                    Step::Stats(race, class, alignment)
+                }
+            }
+            Step::Confirm(character, alignment) => {
+                let summary = format!(
+                    "You will be {} {} {} {}.",
+                    with_article(&alignment.to_string()),
+                    character.sex,
+                    character.race.name,
+                    character.class.name
+                );
+                let _ = telnet.send(summary).await;
+                let Some(answer) = ask(telnet, "Is this correct (Y/N)? ").await else {
+                    return Ok(None);
+                };
+                if answer.to_lowercase().starts_with('y') {
+                    return Ok(Some(character));
+                }
+                let _ = telnet.send("Then let's start again.").await;
+                Step::Sex
+            }
+        };
+    }
+}
@@ -1,4 +1,4 @@
-use crate::player::Players;
+use crate::player::{is_valid_username, Players};
 use futures::SinkExt;
 use std::error::Error;
 use tokio::net::TcpStream;
@@ -27,7 +27,15 @@ pub async fn login_prompt(
             }
         };
 
-        if players.read().values().any(|p| p.username == username) {
+        if !is_valid_username(&username) {
+            let _ = telnet
+                .send("Usernames can only have letters, numbers, '-' and '_'. Try again.")
+                .await;
+        } else if players
+            .read()
+            .values()
+            .any(|p| p.username.eq_ignore_ascii_case(&username))
+        {
             tracing::warn!("Client attempted to use existing username {}", username);
             let _ = telnet.send("Username already taken. Try again.").await;
         } else {
@@ -0,0 +1,271 @@
+// The races and classes players pick between when they create a character, along the lines of
+// pc_race_table and class_table in ROM's const.c. They're read from data files rather than being
+// built in, so they can be changed without a rebuild.
+use crate::{
+    random::number_range,
+    skills,
+    stats::{Apply, Stats},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, strum_macros::Display,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Sex {
+    // Players from before characters had a sex
+    #[default]
+    Neutral,
+    Male,
+    Female,
+}
+
+impl Sex {
+    pub fn from_word(word: &str) -> Option<Sex> {
+        let word = word.to_lowercase();
+        if word.is_empty() {
+            None
+        } else if "male".starts_with(&word) {
+            Some(Sex::Male)
+        } else if "female".starts_with(&word) {
+            Some(Sex::Female)
+        } else {
+            None
+        }
+    }
+
+    // He, him and his, for $e, $m and $s in programs
+    pub fn pronouns(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Sex::Neutral => ("it", "it", "its"),
+            Sex::Male => ("he", "him", "his"),
+            Sex::Female => ("she", "her", "her"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, strum_macros::Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Alignment {
+    Good,
+    Neutral,
+    Evil,
+}
+
+impl Alignment {
+    pub fn from_word(word: &str) -> Option<Alignment> {
+        let word = word.to_lowercase();
+        [Alignment::Good, Alignment::Neutral, Alignment::Evil]
+            .into_iter()
+            .find(|a| !word.is_empty() && a.to_string().starts_with(&word))
+    }
+
+    // Where a new character starts on ROM's scale of -1000 to 1000
+    pub fn value(self) -> i32 {
+        match self {
+            Alignment::Good => 750,
+            Alignment::Neutral => 0,
+            Alignment::Evil => -750,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Race {
+    pub name: String,
+    pub description: String,
+    // Added to the stats a character of this race rolls
+    #[serde(default)]
+    pub modifiers: HashMap<Apply, i32>,
+    // Skills every character of this race knows from level 1, eg elves seeing invisible things
+    #[serde(default)]
+    pub skills: Vec<String>,
+    // The classes this race can be. Races that don't list any can be anything.
+    #[serde(default)]
+    pub classes: Vec<String>,
+}
+
+impl Race {
+    pub fn allows_class(&self, class: &Class) -> bool {
+        self.classes.is_empty() || self.classes.contains(&class.name)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Class {
+    pub name: String,
+    pub description: String,
+    // The stat that gets a bonus when stats are rolled, like ROM's attr_prime
+    pub prime: Apply,
+    // The level the class can learn each skill at. Skills that aren't listed can't be learnt.
+    #[serde(default)]
+    pub skills: BTreeMap<String, u32>,
+    // The alignments the class can have. Classes that don't list any can be anything.
+    #[serde(default)]
+    pub alignments: Vec<Alignment>,
+}
+
+impl Class {
+    pub fn allows_alignment(&self, alignment: Alignment) -> bool {
+        self.alignments.is_empty() || self.alignments.contains(&alignment)
+    }
+}
+
+#[derive(Deserialize)]
+struct RaceFile {
+    race: Vec<Race>,
+}
+
+#[derive(Deserialize)]
+struct ClassFile {
+    class: Vec<Class>,
+}
+
+// Everything there is to pick from. Loading makes sure there's at least one race and class.
+#[derive(Debug, Default)]
+pub struct CreationData {
+    pub races: Vec<Arc<Race>>,
+    pub classes: Vec<Arc<Class>>,
+}
+
+impl CreationData {
+    // Read races.toml and classes.toml from the data directory
+    pub fn load(directory: &Path) -> Result<CreationData, String> {
+        let read = |name: &str| {
+            let path = directory.join(name);
+            fs::read_to_string(&path)
+                .map_err(|error| format!("Couldn't read {}: {}", path.display(), error))
+                .map(|text| (path, text))
+        };
+        let (path, text) = read("races.toml")?;
+        let races: RaceFile =
+            toml::from_str(&text).map_err(|error| format!("{}: {}", path.display(), error))?;
+        let (path, text) = read("classes.toml")?;
+        let classes: ClassFile =
+            toml::from_str(&text).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+        let data = CreationData {
+            races: races.race.into_iter().map(Arc::new).collect(),
+            classes: classes.class.into_iter().map(Arc::new).collect(),
+        };
+        data.check()?;
+        Ok(data)
+    }
+
+    // Catch typos in the data files before anyone tries to play one of them
+    fn check(&self) -> Result<(), String> {
+        if self.races.is_empty() || self.classes.is_empty() {
+            return Err(String::from("There has to be at least one race and class"));
+        }
+        for race in &self.races {
+            for name in &race.skills {
+                if skills::lookup(name).is_none() {
+                    return Err(format!(
+                        "The {} race has unknown skill '{}'",
+                        race.name, name
+                    ));
+                }
+            }
+            for name in &race.classes {
+                if self.class(name).is_none() {
+                    return Err(format!(
+                        "The {} race has unknown class '{}'",
+                        race.name, name
+                    ));
+                }
+            }
+        }
+        for class in &self.classes {
+            for name in class.skills.keys() {
+                if skills::lookup(name).is_none() {
+                    return Err(format!(
+                        "The {} class has unknown skill '{}'",
+                        class.name, name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn race(&self, name: &str) -> Option<Arc<Race>> {
+        self.races
+            .iter()
+            .find(|r| r.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    pub fn class(&self, name: &str) -> Option<Arc<Class>> {
+        self.classes
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    // Races and classes can be picked by the start of their name
+    pub fn find_race(&self, word: &str) -> Option<Arc<Race>> {
+        let word = word.to_lowercase();
+        self.races
+            .iter()
+            .find(|r| !word.is_empty() && r.name.to_lowercase().starts_with(&word))
+            .cloned()
+    }
+
+    pub fn find_class(&self, word: &str) -> Option<Arc<Class>> {
+        let word = word.to_lowercase();
+        self.classes
+            .iter()
+            .find(|c| !word.is_empty() && c.name.to_lowercase().starts_with(&word))
+            .cloned()
+    }
+
+    // What players who were saved before they had a race or class get. Their file fills in
+    // whatever it has.
+    pub fn default_character(&self) -> NewCharacter {
+        NewCharacter {
+            sex: Sex::default(),
+            race: self.races[0].clone(),
+            class: self.classes[0].clone(),
+            alignment: 0,
+            stats: Stats::new_player(),
+        }
+    }
+}
+
+// What a player picked while creating their character
+#[derive(Clone, Debug)]
+pub struct NewCharacter {
+    pub sex: Sex,
+    pub race: Arc<Race>,
+    pub class: Arc<Class>,
+    pub alignment: i32,
+    pub stats: Stats,
+}
+
+const ATTRIBUTES: [Apply; 5] = [
+    Apply::Strength,
+    Apply::Intelligence,
+    Apply::Wisdom,
+    Apply::Dexterity,
+    Apply::Constitution,
+];
+
+// A few points either side of ROM's starting stats, with ROM's bonus of 3 to the class's prime stat
+// and the race's modifiers on top
+pub fn roll_stats(race: &Race, class: &Class) -> Stats {
+    let mut stats = Stats::new_player();
+    for apply in ATTRIBUTES {
+        stats.apply(apply, number_range(-3, 3));
+    }
+    stats.apply(class.prime, 3);
+    for (&apply, &modifier) in &race.modifiers {
+        stats.apply(apply, modifier);
+    }
+    stats
+}
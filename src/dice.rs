@@ -0,0 +1,74 @@
+// Dice as ROM writes them in area files, eg "3d8+10" for three eight-sided dice plus ten
+use crate::random::number_range;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+// The most dice, and the most sides on them, that can be rolled at once. Every die is rolled
+// separately, and ROM areas stay well under this.
+const MAX_DICE: u32 = 1000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dice {
+    pub number: u32,
+    pub size: u32,
+    pub bonus: i32,
+}
+
+impl Dice {
+    pub fn roll(&self) -> i32 {
+        let size = self.size.min(MAX_DICE) as i32;
+        let total: i64 = (0..self.number.min(MAX_DICE))
+            .map(|_| i64::from(number_range(1, size)))
+            .sum();
+        let total = total.saturating_add(i64::from(self.bonus));
+        i32::try_from(total).unwrap_or(if total < 0 { i32::MIN } else { i32::MAX })
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}d{}{:+}", self.number, self.size, self.bonus)
+    }
+}
+
+impl FromStr for Dice {
+    type Err = String;
+
+    // Takes "XdY+Z", "XdY-Z" or just "XdY"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("{} isn't dice, expected something like 3d8+10", s);
+        let (number, rest) = s.trim().split_once(['d', 'D']).ok_or_else(error)?;
+        let (size, bonus) = match rest.find(['+', '-']) {
+            Some(at) => (&rest[..at], rest[at..].trim_start_matches('+')),
+            None => (rest, "0"),
+        };
+        let dice = Dice {
+            number: number.parse().map_err(|_| error())?,
+            size: size.parse().map_err(|_| error())?,
+            bonus: bonus.parse().map_err(|_| error())?,
+        };
+        if dice.number > MAX_DICE || dice.size > MAX_DICE {
+            return Err(format!(
+                "{} is too many dice, it can be at most {}d{}",
+                s, MAX_DICE, MAX_DICE
+            ));
+        }
+        Ok(dice)
+    }
+}
+
+// Saved the same way they're written in area files
+impl Serialize for Dice {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dice {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
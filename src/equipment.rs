@@ -0,0 +1,210 @@
+use crate::objects::{ItemType, Object, ObjectInstance, WearFlag};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+
+// The slots a character can use objects in. The order here is the order they're listed in by the
+// `equipment` command.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+    Deserialize,
+    Serialize,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WearLocation {
+    Light,
+    FingerLeft,
+    FingerRight,
+    NeckOne,
+    NeckTwo,
+    Body,
+    Head,
+    Legs,
+    Feet,
+    Hands,
+    Arms,
+    Shield,
+    About,
+    Waist,
+    WristLeft,
+    WristRight,
+    Wield,
+    Hold,
+    Float,
+}
+
+impl WearLocation {
+    // Map the WEAR_* numbering used by ROM 'E' resets
+    pub fn from_rom(location: i64) -> Option<WearLocation> {
+        WearLocation::iter().nth(usize::try_from(location).ok()?)
+    }
+
+    pub fn to_rom(self) -> i64 {
+        WearLocation::iter().position(|l| l == self).unwrap_or(0) as i64
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WearLocation::Light => "<used as light>",
+            WearLocation::FingerLeft | WearLocation::FingerRight => "<worn on finger>",
+            WearLocation::NeckOne | WearLocation::NeckTwo => "<worn around neck>",
+            WearLocation::Body => "<worn on torso>",
+            WearLocation::Head => "<worn on head>",
+            WearLocation::Legs => "<worn on legs>",
+            WearLocation::Feet => "<worn on feet>",
+            WearLocation::Hands => "<worn on hands>",
+            WearLocation::Arms => "<worn on arms>",
+            WearLocation::Shield => "<worn as shield>",
+            WearLocation::About => "<worn about body>",
+            WearLocation::Waist => "<worn about waist>",
+            WearLocation::WristLeft | WearLocation::WristRight => "<worn around wrist>",
+            WearLocation::Wield => "<wielded>",
+            WearLocation::Hold => "<held>",
+            WearLocation::Float => "<floating nearby>",
+        }
+    }
+
+    // What the wearer is told when they start using something here
+    pub fn wear_message(&self, object_name: &str) -> String {
+        match self {
+            WearLocation::Light => format!("You light {} and hold it.", object_name),
+            WearLocation::FingerLeft => format!("You wear {} on your left finger.", object_name),
+            WearLocation::FingerRight => format!("You wear {} on your right finger.", object_name),
+            WearLocation::NeckOne | WearLocation::NeckTwo => {
+                format!("You wear {} around your neck.", object_name)
+            }
+            WearLocation::Body => format!("You wear {} on your torso.", object_name),
+            WearLocation::Head => format!("You wear {} on your head.", object_name),
+            WearLocation::Legs => format!("You wear {} on your legs.", object_name),
+            WearLocation::Feet => format!("You wear {} on your feet.", object_name),
+            WearLocation::Hands => format!("You wear {} on your hands.", object_name),
+            WearLocation::Arms => format!("You wear {} on your arms.", object_name),
+            WearLocation::Shield => format!("You wear {} as a shield.", object_name),
+            WearLocation::About => format!("You wear {} about your torso.", object_name),
+            WearLocation::Waist => format!("You wear {} about your waist.", object_name),
+            WearLocation::WristLeft => format!("You wear {} around your left wrist.", object_name),
+            WearLocation::WristRight => {
+                format!("You wear {} around your right wrist.", object_name)
+            }
+            WearLocation::Wield => format!("You wield {}.", object_name),
+            WearLocation::Hold => format!("You hold {} in your hand.", object_name),
+            WearLocation::Float => {
+                format!("You release {} to float next to you.", object_name)
+            }
+        }
+    }
+
+    // The verb other people in the room see (eg "Bob wields a sword.")
+    pub fn verb(&self) -> &'static str {
+        match self {
+            WearLocation::Wield => "wields",
+            WearLocation::Light | WearLocation::Hold => "holds",
+            _ => "wears",
+        }
+    }
+}
+
+// The order ROM's wear_obj checks wear flags in
+const WEAR_ORDER: [WearFlag; 15] = [
+    WearFlag::Finger,
+    WearFlag::Neck,
+    WearFlag::Body,
+    WearFlag::Head,
+    WearFlag::Legs,
+    WearFlag::Feet,
+    WearFlag::Hands,
+    WearFlag::Arms,
+    WearFlag::About,
+    WearFlag::Waist,
+    WearFlag::Wrist,
+    WearFlag::Shield,
+    WearFlag::Wield,
+    WearFlag::Hold,
+    WearFlag::Float,
+];
+
+// The slots that an object with the given wear flag goes in. Paired slots (fingers, neck, wrists)
+// return both options.
+pub fn flag_locations(flag: WearFlag) -> &'static [WearLocation] {
+    match flag {
+        WearFlag::Finger => &[WearLocation::FingerLeft, WearLocation::FingerRight],
+        WearFlag::Neck => &[WearLocation::NeckOne, WearLocation::NeckTwo],
+        WearFlag::Body => &[WearLocation::Body],
+        WearFlag::Head => &[WearLocation::Head],
+        WearFlag::Legs => &[WearLocation::Legs],
+        WearFlag::Feet => &[WearLocation::Feet],
+        WearFlag::Hands => &[WearLocation::Hands],
+        WearFlag::Arms => &[WearLocation::Arms],
+        WearFlag::About => &[WearLocation::About],
+        WearFlag::Waist => &[WearLocation::Waist],
+        WearFlag::Wrist => &[WearLocation::WristLeft, WearLocation::WristRight],
+        WearFlag::Shield => &[WearLocation::Shield],
+        WearFlag::Wield => &[WearLocation::Wield],
+        WearFlag::Hold => &[WearLocation::Hold],
+        WearFlag::Float => &[WearLocation::Float],
+        WearFlag::Take | WearFlag::NoSacrifice => &[],
+    }
+}
+
+// Which slots an object can go in, based on the first of its wear flags that ROM would use
+pub fn wear_locations(object: &Object) -> &'static [WearLocation] {
+    if object.item_type == ItemType::Light {
+        return &[WearLocation::Light];
+    }
+
+    WEAR_ORDER
+        .iter()
+        .find(|flag| object.can_wear(**flag))
+        .map_or(&[], |flag| flag_locations(*flag))
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Equipment(HashMap<WearLocation, ObjectInstance>);
+
+impl Equipment {
+    pub fn new() -> Self {
+        Equipment(HashMap::new())
+    }
+
+    pub fn get(&self, location: WearLocation) -> Option<&ObjectInstance> {
+        self.0.get(&location)
+    }
+
+    // Put an object in a slot, handing back whatever was there before
+    pub fn equip(
+        &mut self,
+        location: WearLocation,
+        object: ObjectInstance,
+    ) -> Option<ObjectInstance> {
+        self.0.insert(location, object)
+    }
+
+    pub fn unequip(&mut self, location: WearLocation) -> Option<ObjectInstance> {
+        self.0.remove(&location)
+    }
+
+    // Find the slot of a worn object by name
+    pub fn find(&self, name: &str) -> Option<WearLocation> {
+        self.iter()
+            .find(|(_, object)| object.matches(name))
+            .map(|(location, _)| location)
+    }
+
+    // Everything currently in use, in display order
+    pub fn iter(&self) -> impl Iterator<Item = (WearLocation, &ObjectInstance)> {
+        WearLocation::iter().filter_map(|location| self.0.get(&location).map(|o| (location, o)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
@@ -0,0 +1,165 @@
+// Hurting and killing things, along the lines of damage and dam_message in ROM's fight.c
+// TODO: There's no combat yet, so nothing fights back. Players can only be hurt once there's
+// something to do when they die.
+use crate::{
+    actions::{capitalize, send_targeted_message},
+    mobiles::AffectFlag,
+    player::Players,
+    programs,
+    random::number_range,
+    world::World,
+};
+
+// How damage is described, by the most damage each word covers, from ROM's dam_message
+const DAMAGE_VERBS: &[(i32, &str)] = &[
+    (0, "misses"),
+    (4, "scratches"),
+    (8, "grazes"),
+    (12, "hits"),
+    (16, "injures"),
+    (20, "wounds"),
+    (24, "mauls"),
+    (28, "decimates"),
+    (32, "devastates"),
+    (36, "maims"),
+    (40, "MUTILATES"),
+    (44, "DISEMBOWELS"),
+    (48, "DISMEMBERS"),
+    (52, "MASSACRES"),
+    (56, "MANGLES"),
+    (60, "*** DEMOLISHES ***"),
+    (75, "*** DEVASTATES ***"),
+    (100, "=== OBLITERATES ==="),
+    (125, ">>> ANNIHILATES <<<"),
+    (150, "<<< ERADICATES >>>"),
+];
+
+fn damage_verb(damage: i32) -> &'static str {
+    DAMAGE_VERBS
+        .iter()
+        .find(|&&(most, _)| damage <= most)
+        .map_or("does UNSPEAKABLE things to", |&(_, verb)| verb)
+}
+
+// A player hurts a mobile with something, eg a spell. The mobile dies if it runs out of hit
+// points, leaving what it had on the floor.
+pub fn damage_mobile(
+    players: &Players,
+    world: &mut World,
+    attacker: u32,
+    mobile_id: u32,
+    damage: i32,
+    noun: &str,
+) {
+    let Some(attacker_name) = players.read().get(&attacker).map(|p| p.username.clone()) else {
+        return;
+    };
+    let Some(mobile) = world.mobiles.get_mut(&mobile_id) else {
+        return;
+    };
+    let mut damage = damage.max(0);
+    if damage > 1 && mobile.is_affected(AffectFlag::Sanctuary) {
+        damage /= 2;
+    }
+    mobile.hit -= damage;
+    let room_id = mobile.current_room;
+    let victim = mobile.template.room_description.clone();
+
+    let verb = damage_verb(damage);
+    let punctuation = if damage <= 24 { '.' } else { '!' };
+    if let Some(player) = players.read().get(&attacker) {
+        player.send_message(format!("Your {} {} {}{}", noun, verb, victim, punctuation));
+    }
+    send_targeted_message(
+        players,
+        format!(
+            "{}'s {} {} {}{}",
+            attacker_name, noun, verb, victim, punctuation
+        ),
+        |&(id, p)| p.current_room == room_id && *id != attacker,
+    );
+
+    if world.mobiles.get(&mobile_id).is_some_and(|m| m.hit <= 0) {
+        kill_mobile(players, world, attacker, mobile_id);
+    } else {
+        programs::fight_trigger(players, world, mobile_id, attacker);
+    }
+}
+
+// Experience for killing something, by how much higher level it is than the character, from ROM's
+// xp_compute
+fn base_exp(level_range: i32) -> i32 {
+    match level_range {
+        -9 => 1,
+        -8 => 2,
+        -7 => 5,
+        -6 => 9,
+        -5 => 11,
+        -4 => 22,
+        -3 => 33,
+        -2 => 50,
+        -1 => 66,
+        0 => 83,
+        1 => 99,
+        2 => 121,
+        3 => 143,
+        4 => 165,
+        range if range > 4 => 160 + 20 * (range - 4),
+        _ => 0,
+    }
+}
+
+// Everyone in the killer's group who was there gets experience, split between them by level, like
+// ROM's group_gain
+// TODO: Levelling up, and ROM's adjustments for alignment
+fn group_gain(players: &Players, killer: u32, room_id: u32, victim_level: u32) {
+    let mut guard = players.write();
+    let Some(killer) = guard.get(&killer) else {
+        return;
+    };
+    let members: Vec<u32> = guard
+        .values()
+        .filter(|p| p.current_room == room_id && p.is_same_group(killer))
+        .map(|p| p.id)
+        .collect();
+    let total_levels: u32 = members
+        .iter()
+        .filter_map(|id| guard.get(id))
+        .map(|p| p.level)
+        .sum();
+
+    for id in members {
+        let Some(member) = guard.get_mut(&id) else {
+            continue;
+        };
+        let exp = base_exp(victim_level as i32 - member.level as i32);
+        let exp = number_range(exp * 3 / 4, exp * 5 / 4);
+        let share = exp.max(0) as u32 * member.level / total_levels.max(1);
+        member.exp += share;
+        member.send_message(format!("You receive {} experience points.", share));
+    }
+}
+
+fn kill_mobile(players: &Players, world: &mut World, killer: u32, mobile_id: u32) {
+    programs::death_trigger(players, world, mobile_id, killer);
+    let Some(mobile) = world.mobiles.remove(&mobile_id) else {
+        return;
+    };
+    tracing::debug!(mobile_id, vnum = mobile.template.id, "Mobile killed");
+    send_targeted_message(
+        players,
+        format!(
+            "{} is DEAD!!",
+            capitalize(&mobile.template.room_description)
+        ),
+        |&(_, p)| p.current_room == mobile.current_room,
+    );
+    group_gain(players, killer, mobile.current_room, mobile.template.level);
+
+    // TODO: Make a corpse to put these in once objects can hold other objects
+    let mut dropped = mobile.inventory;
+    dropped.extend(mobile.equipment.iter().map(|(_, object)| object.clone()));
+    if let Some(room) = world.get_room_mut(mobile.current_room) {
+        room.objects.extend(dropped);
+    }
+}
@@ -4,21 +4,26 @@ use tokio::sync::mpsc;
 
 mod read_commands;
 mod tick;
+mod update;
 
-use read_commands::read_commands;
+use read_commands::{read_commands, run_queued_commands};
 use tick::tick;
+use update::update;
 
 #[tracing::instrument(skip_all)]
 pub async fn game_loop(
     players: Players,
-    world: World,
+    mut world: World,
     mut receiver: mpsc::Receiver<ConnectionMessage>,
 ) {
     tracing::info!("Game loop spawned");
     loop {
         tokio::select! {
-            _game_clock = tick() => {},
-            _commands = read_commands(&players, &world, &mut receiver) => {},
+            _game_clock = tick() => {
+                update(&players, &mut world);
+                run_queued_commands(&players, &mut world);
+            }
+            _commands = read_commands(&players, &mut world, &mut receiver) => {},
         }
     }
 }
@@ -1,6 +1,9 @@
 use crate::{
     actions::{self, PlayerAction},
+    area_file, input,
     message::{ConnectionMessage, PlayerMessage, RawCommand},
+    objects::WearFlag,
+    olc,
     player::Players,
     world::World,
 };
@@ -8,7 +11,7 @@ use tokio::sync::mpsc;
 
 pub async fn read_commands(
     players: &Players,
-    world: &World,
+    world: &mut World,
     receiver: &mut mpsc::Receiver<ConnectionMessage>,
 ) {
     // Game logic goes here
@@ -16,10 +19,17 @@ pub async fn read_commands(
     // Access players map using players.read().unwrap()
     while let Some(msg) = receiver.recv().await {
         match msg {
-            ConnectionMessage::AddPlayer(player) => {
+            ConnectionMessage::AddPlayer(mut player) => {
                 let player_id = player.id;
                 tracing::info!("Adding new player {}: '{}'", player_id, player.username);
-                players.write().insert(player_id, player.clone());
+                if let Err(error) = player.load(&world.player_directory, &world.creation) {
+                    tracing::error!(
+                        username = player.username,
+                        "Couldn't load player: {}",
+                        error
+                    );
+                }
+                players.write().insert(player_id, *player);
             }
             ConnectionMessage::RemovePlayer(player_id) => {
                 // Remove the player from the connected players map when the connection is closed
@@ -27,77 +37,456 @@ pub async fn read_commands(
                 // in the map
                 let player = players.write().remove(&player_id).unwrap();
                 tracing::info!("Removed player {}: '{}'", player_id, player.username);
+                actions::die_follower(players, &player);
+                if let Err(error) = player.save(&world.player_directory) {
+                    tracing::error!(
+                        username = player.username,
+                        "Couldn't save player: {}",
+                        error
+                    );
+                }
+            }
+            ConnectionMessage::ReloadArea(path) => {
+                let area_id = world
+                    .areas()
+                    .iter()
+                    .position(|area| area.metadata.file.as_ref() == Some(&path));
+                let Some(area_id) = area_id else {
+                    tracing::warn!(file = %path.display(), "Changed file isn't a loaded area");
+                    continue;
+                };
+                let area = &world.areas()[area_id];
+                // Our own asave changes the file too, but then there's nothing new in it
+                if area_file::modified(&path, area.metadata.format) == area.modified {
+                    continue;
+                }
+                if area.changed {
+                    tracing::warn!(
+                        area = area.metadata.name,
+                        "Not reloading the changed file, the area has unsaved edits"
+                    );
+                    continue;
+                }
+                match olc::reload_area(area_id, players, world) {
+                    Ok(response) => tracing::info!("{}", response),
+                    Err(response) => tracing::error!("{}", response),
+                }
             }
             ConnectionMessage::PlayerCommand(sender_id, message) => {
-                // A potential command from the player we need to interpret
-                let command = RawCommand::new(sender_id, message);
-
-                tracing::debug!("Received possible command '{:?}'", command);
-
-                if let Some(player_message) = command.interpret() {
-                    // TODO: Could we further standardize the interface? Make every action take a sender
-                    // and a list of arguments, then somehow do a lookup to a function in a HashMap or
-                    // something?
-                    match player_message {
-                        PlayerMessage::Gossip(content) => {
-                            let action = actions::GossipAction {
-                                sender: sender_id,
-                                content,
-                            };
-                            action.perform(&players, &world);
-                        }
-                        PlayerMessage::Say(content) => {
-                            let action = actions::SayAction {
-                                sender: sender_id,
-                                content,
-                            };
-                            action.perform(&players, &world);
-                        }
-                        PlayerMessage::Look => {
-                            let action = actions::LookAction { sender: sender_id };
-                            action.perform(&players, &world);
-                        }
-                        PlayerMessage::Move(direction) => {
-                            let move_action = actions::MoveAction {
-                                sender: sender_id,
-                                direction,
-                            };
-                            // TODO: Make this optional
-                            let look_action = actions::LookAction { sender: sender_id };
-                            move_action.perform(&players, &world);
-                            look_action.perform(&players, &world);
-                        }
-                        PlayerMessage::Mobiles => {
-                            let action = actions::MobileAction { sender: sender_id };
+                let Some(editing) = players.read().get(&sender_id).map(|p| p.olc.is_some()) else {
+                    continue;
+                };
+                if !editing && message.trim().eq_ignore_ascii_case("clear") {
+                    clear_queue(players, sender_id);
+                    send_prompt(players, world, sender_id);
+                    continue;
+                }
 
-                            action.perform(&players, &world);
-                        }
-                        PlayerMessage::Contextual(command, arguments) => {
-                            tracing::debug!(
-                                "Failed to parse potential contextual player message: {} {}",
-                                command,
-                                arguments
-                            );
-                            if let Some(sending_player) = players.read().get(&sender_id) {
-                                // TODO: Reintroduce consolidation here, somehow
-                                let response = String::from("Arglebargle, glop-glyf!?!?!");
-                                sending_player.send_message(response);
-                            }
-                        }
+                // Builders with an editor open get the line as it is, so text they're writing
+                // isn't split up or expanded
+                let commands = match players.read().get(&sender_id) {
+                    Some(player) if player.olc.is_some() => Ok(vec![message]),
+                    Some(player) => input::expand(&message, &player.aliases),
+                    None => continue,
+                };
+                // Everything else waits its turn in the queue, which is run by run_queued_commands
+                let response = match commands {
+                    // Nothing but a new prompt for an empty line
+                    Ok(commands) if commands.is_empty() => {
+                        send_prompt(players, world, sender_id);
+                        continue;
+                    }
+                    Ok(commands) => {
+                        let mut guard = players.write();
+                        let Some(player) = guard.get_mut(&sender_id) else {
+                            continue;
+                        };
+                        (!player.queue_commands(commands)).then(|| {
+                            String::from(
+                                "You have too many commands waiting. Type clear to forget them.",
+                            )
+                        })
                     }
-                } else {
-                    if let Some(sending_player) = players.read().get(&sender_id) {
-                        tracing::debug!("Failed to parse player message: {:?}", command);
-                        let response = String::from("Arglebargle, glop-glyf!?!?!");
-                        sending_player.send_message(response);
+                    Err(error) => Some(error),
+                };
+                if let Some(response) = response {
+                    if let Some(player) = players.read().get(&sender_id) {
+                        player.send_message(response);
                     }
+                    send_prompt(players, world, sender_id);
                 }
-                // Send a new prompt to the player
+            }
+        }
+    }
+}
+
+// Run the next command of each player who isn't being held up by a wait state, once a pulse
+pub fn run_queued_commands(players: &Players, world: &mut World) {
+    let mut ready = Vec::new();
+    for player in players.write().values_mut() {
+        if player.wait > 0 {
+            player.wait -= 1;
+        } else if let Some(command) = player.queue.pop_front() {
+            ready.push((player.id, command));
+        }
+    }
+    ready.sort_by_key(|(player_id, _)| *player_id);
+
+    for (player_id, command) in ready {
+        run_command(players, world, player_id, command);
+        send_prompt(players, world, player_id);
+    }
+}
+
+// Forget everything the player has queued up. This skips the queue, since it's what you use when
+// the queue is stuck behind a wait.
+fn clear_queue(players: &Players, player_id: u32) {
+    if let Some(player) = players.write().get_mut(&player_id) {
+        let response = if player.queue.is_empty() {
+            String::from("You have no commands waiting.")
+        } else {
+            player.queue.clear();
+            String::from("Your waiting commands are forgotten.")
+        };
+        player.send_message(response);
+    }
+}
+
+// Run one command from a player
+fn run_command(players: &Players, world: &mut World, sender_id: u32, message: String) {
+    // Builders with an editor open get the editor's commands first
+    let editing = players
+        .read()
+        .get(&sender_id)
+        .is_some_and(|p| p.olc.is_some());
+    if editing && olc::interpret(sender_id, &message, players, world) {
+        return;
+    }
+
+    // A potential command from the player we need to interpret
+    let command = RawCommand::new(sender_id, message);
+
+    tracing::debug!("Received possible command '{:?}'", command);
+
+    if let Some(player_message) = command.interpret() {
+        // Sleeping players can't walk around, and so on
+        let refusal = players
+            .read()
+            .get(&sender_id)
+            .map(|p| p.position)
+            .filter(|&position| position < player_message.min_position())
+            .map(|position| position.refusal());
+        if let Some(refusal) = refusal {
+            if let Some(player) = players.read().get(&sender_id) {
+                player.send_message(String::from(refusal));
+            }
+            return;
+        }
+
+        // TODO: Could we further standardize the interface? Make every action take a sender
+        // and a list of arguments, then somehow do a lookup to a function in a HashMap or
+        // something?
+        match player_message {
+            PlayerMessage::Gossip(content) => {
+                let action = actions::GossipAction {
+                    sender: sender_id,
+                    content,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Say(content) => {
+                let action = actions::SayAction {
+                    sender: sender_id,
+                    content,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Look => {
+                let action = actions::LookAction { sender: sender_id };
+                action.perform(players, world);
+            }
+            PlayerMessage::Map(argument) => {
+                let action = actions::MapAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Track(target) => {
+                let action = actions::TrackAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Where(target) => {
+                let action = actions::WhereAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Path(argument) => {
+                let action = actions::PathAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Position(position) => {
+                let action = actions::PositionAction {
+                    sender: sender_id,
+                    position,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Wake(target) => {
+                let action = actions::WakeAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Follow(target) => {
+                let action = actions::FollowAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Group(target) => {
+                let action = actions::GroupAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Ungroup => {
+                let action = actions::UngroupAction { sender: sender_id };
+                action.perform(players, world);
+            }
+            PlayerMessage::Gtell(content) => {
+                let action = actions::GtellAction {
+                    sender: sender_id,
+                    content,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Cast(argument) => {
+                let action = actions::CastAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Practice(argument) => {
+                let action = actions::PracticeAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Alias(argument) => {
+                let action = actions::AliasAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Unalias(word) => {
+                let action = actions::UnaliasAction {
+                    sender: sender_id,
+                    word,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Move(direction) => {
+                let action = actions::MoveAction {
+                    sender: sender_id,
+                    direction,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Get(target) => {
+                let action = actions::GetAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Drop(target) => {
+                let action = actions::DropAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Give(arguments) => {
+                let action = actions::GiveAction {
+                    sender: sender_id,
+                    arguments,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Inventory => {
+                let action = actions::InventoryAction { sender: sender_id };
+                action.perform(players, world);
+            }
+            PlayerMessage::Wear(target) => {
+                let action = actions::WearAction {
+                    sender: sender_id,
+                    target,
+                    required_flag: None,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Wield(target) => {
+                let action = actions::WearAction {
+                    sender: sender_id,
+                    target,
+                    required_flag: Some(WearFlag::Wield),
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Hold(target) => {
+                let action = actions::WearAction {
+                    sender: sender_id,
+                    target,
+                    required_flag: Some(WearFlag::Hold),
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Remove(target) => {
+                let action = actions::RemoveAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Equipment => {
+                let action = actions::EquipmentAction { sender: sender_id };
+                action.perform(players, world);
+            }
+            PlayerMessage::Score => {
+                let action = actions::ScoreAction { sender: sender_id };
+                action.perform(players, world);
+            }
+            PlayerMessage::Affects => {
+                let action = actions::AffectsAction { sender: sender_id };
+                action.perform(players, world);
+            }
+            PlayerMessage::Areas(target) => {
+                let action = actions::AreasAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Redit(argument) => {
+                let action = actions::ReditAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Medit(argument) => {
+                let action = actions::MeditAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Oedit(argument) => {
+                let action = actions::OeditAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Resets(argument) => {
+                let action = actions::ResetsAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Asave(argument) => {
+                let action = actions::AsaveAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Areload(argument) => {
+                let action = actions::AreloadAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Graph(argument) => {
+                let action = actions::GraphAction {
+                    sender: sender_id,
+                    argument,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::List => {
+                let action = actions::ListAction { sender: sender_id };
+                action.perform(players, world);
+            }
+            PlayerMessage::Buy(target) => {
+                let action = actions::BuyAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Sell(target) => {
+                let action = actions::SellAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Value(target) => {
+                let action = actions::ValueAction {
+                    sender: sender_id,
+                    target,
+                };
+                action.perform(players, world);
+            }
+            PlayerMessage::Mobiles => {
+                let action = actions::MobileAction { sender: sender_id };
+
+                action.perform(players, world);
+            }
+            PlayerMessage::Contextual(command, arguments) => {
+                tracing::debug!(
+                    "Failed to parse potential contextual player message: {} {}",
+                    command,
+                    arguments
+                );
                 if let Some(sending_player) = players.read().get(&sender_id) {
-                    let prompt = sending_player.prompt_str(&world);
-                    sending_player.send_prompt(prompt);
+                    // TODO: Reintroduce consolidation here, somehow
+                    let response = String::from("Arglebargle, glop-glyf!?!?!");
+                    sending_player.send_message(response);
                 }
             }
         }
+    } else {
+        if let Some(sending_player) = players.read().get(&sender_id) {
+            tracing::debug!("Failed to parse player message: {:?}", command);
+            let response = String::from("Arglebargle, glop-glyf!?!?!");
+            sending_player.send_message(response);
+        }
+    }
+}
+
+// Send a new prompt to the player
+fn send_prompt(players: &Players, world: &World, player_id: u32) {
+    if let Some(sending_player) = players.read().get(&player_id) {
+        let prompt = sending_player.prompt_str(world);
+        sending_player.send_prompt(prompt);
     }
 }
@@ -1,7 +1,7 @@
+use crate::time::PULSES_PER_SECOND;
 use tokio::time::{sleep, Duration};
 
 pub async fn tick() {
-    // TODO: Figure out proper tick length
     tracing::trace!("Tick!");
-    sleep(Duration::from_millis(1000)).await
+    sleep(Duration::from_millis(1000 / PULSES_PER_SECOND)).await
 }
@@ -0,0 +1,187 @@
+use crate::{
+    actions::{capitalize, send_targeted_message},
+    affects,
+    pathfind::{self, PathOptions},
+    player::Players,
+    position::Position,
+    programs,
+    time::PULSES_PER_SECOND,
+    world::World,
+};
+
+// How often mobiles get to act, in pulses
+const PULSES_PER_MOBILE_UPDATE: u64 = 4 * PULSES_PER_SECOND;
+// How often areas age, in pulses
+const PULSES_PER_AREA_UPDATE: u64 = 60 * PULSES_PER_SECOND;
+
+// Everything that happens in the world on its own, run once per pulse
+pub fn update(players: &Players, world: &mut World) {
+    // Like ROM, an hour of game time is also a tick, when characters recover
+    if world.time.advance() {
+        tracing::debug!(hour = world.time.hour, "An hour has passed");
+        regen_update(players, world);
+    }
+
+    if world.time.pulse().is_multiple_of(PULSES_PER_MOBILE_UPDATE) {
+        mobile_update(players, world);
+    }
+
+    if world.time.pulse().is_multiple_of(PULSES_PER_AREA_UPDATE) {
+        area_update(players, world);
+    }
+}
+
+// The heal and mana rates of a room, as percentages
+fn room_rates(world: &World, room_id: u32) -> (i32, i32) {
+    world
+        .get_room(room_id)
+        .map_or((100, 100), |room| (room.heal_rate, room.mana_rate))
+}
+
+// What happens to characters each tick
+fn regen_update(players: &Players, world: &mut World) {
+    for player in players.write().values_mut() {
+        let (heal_rate, mana_rate) = room_rates(world, player.current_room);
+        player.regenerate(heal_rate, mana_rate);
+        for message in affects::tick(&mut player.affects) {
+            player.send_message(String::from(message));
+        }
+        // Whatever wore off might have been raising their maximums
+        player.clamp_points();
+    }
+
+    let rates: Vec<_> = world
+        .mobiles
+        .values()
+        .map(|m| (m.id, room_rates(world, m.current_room)))
+        .collect();
+    for (mobile_id, (heal_rate, mana_rate)) in rates {
+        if let Some(mobile) = world.mobiles.get_mut(&mobile_id) {
+            mobile.regenerate(heal_rate, mana_rate);
+            // Mobiles have nobody to tell what wore off
+            affects::tick(&mut mobile.affects);
+        }
+    }
+}
+
+// Reset areas as they age, along the lines of ROM. Areas with nobody around reset sooner, but
+// everywhere resets eventually.
+fn area_update(players: &Players, world: &mut World) {
+    let occupied: Vec<_> = players
+        .read()
+        .values()
+        .filter_map(|p| world.room_area(p.current_room))
+        .collect();
+
+    for area_id in 0..world.areas().len() {
+        let Some(area) = world.get_area_mut(area_id) else {
+            continue;
+        };
+        area.age += 1;
+        let empty = !occupied.contains(&area_id);
+        if (empty && area.age >= 15) || area.age >= 31 {
+            world.reset_area(area_id);
+        }
+    }
+}
+
+fn mobile_update(players: &Players, world: &mut World) {
+    let mobiles: Vec<_> = world
+        .mobiles
+        .values()
+        .map(|m| (m.id, m.template.special))
+        .collect();
+
+    for (mobile_id, special) in mobiles {
+        // An earlier mobile might have done something to this one
+        let Some(mobile) = world.mobiles.get(&mobile_id) else {
+            continue;
+        };
+        // Sleeping mobiles don't do anything, and like ROM, random programs only run for mobiles
+        // where the area put them
+        if !mobile.position.is_awake() {
+            continue;
+        }
+        let at_default = mobile.position == mobile.default_position();
+
+        if hunt(players, world, mobile_id) {
+            continue;
+        }
+
+        if let Some(special) = special {
+            if (special.function)(mobile_id, players, world) {
+                tracing::trace!(mobile_id, special = special.name, "Special procedure acted");
+                continue;
+            }
+        }
+
+        if at_default {
+            programs::random_trigger(players, world, mobile_id);
+        }
+    }
+
+    // Rooms and objects only bother with random programs when there's a player around to see them
+    let mut occupied: Vec<u32> = players.read().values().map(|p| p.current_room).collect();
+    occupied.sort_unstable();
+    occupied.dedup();
+    for room_id in occupied {
+        programs::room_random_trigger(players, world, room_id);
+    }
+}
+
+// Take a step towards whoever the mobile is hunting. Returns whether it moved. Mobiles give up
+// when their prey leaves the game or gets somewhere they can't follow.
+fn hunt(players: &Players, world: &mut World, mobile_id: u32) -> bool {
+    let Some(mobile) = world.mobiles.get(&mobile_id) else {
+        return false;
+    };
+    let Some(target) = mobile.hunting else {
+        return false;
+    };
+    // TODO: Mobiles should stand up to give chase
+    if mobile.position != Position::Standing {
+        return false;
+    }
+    let from = mobile.current_room;
+    let name = capitalize(&mobile.template.room_description);
+
+    let Some(to) = players.read().get(&target).map(|p| p.current_room) else {
+        if let Some(mobile) = world.mobiles.get_mut(&mobile_id) {
+            mobile.hunting = None;
+        }
+        return false;
+    };
+    let options = PathOptions::mobile(world, mobile_id);
+    let Some(path) = pathfind::find_path(world, from, to, &options) else {
+        tracing::debug!(mobile_id, target, "Lost the trail");
+        if let Some(mobile) = world.mobiles.get_mut(&mobile_id) {
+            mobile.hunting = None;
+        }
+        return false;
+    };
+    // Already there
+    let Some(&direction) = path.first() else {
+        return false;
+    };
+    let Some(next) = world
+        .get_room(from)
+        .and_then(|room| room.get_exit(&direction.to_string()))
+        .copied()
+    else {
+        return false;
+    };
+
+    if let Some(mobile) = world.mobiles.get_mut(&mobile_id) {
+        mobile.current_room = next;
+    }
+    send_targeted_message(
+        players,
+        format!("{} leaves {}.", name, direction),
+        |&(_, p)| p.current_room == from,
+    );
+    send_targeted_message(players, format!("{} has arrived.", name), |&(_, p)| {
+        p.current_room == next
+    });
+    programs::entry_trigger(players, world, mobile_id);
+    true
+}
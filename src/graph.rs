@@ -0,0 +1,245 @@
+// Exporting the rooms and exits as a graph, to draw maps outside the game. DOT is for Graphviz,
+// and the JSON nodes and edges are what the web map reads.
+use crate::{
+    area::Area,
+    message::Direction,
+    room::{Room, Sector},
+    world::{AreaId, World},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    Dot,
+    Json,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" | "gv" => Ok(GraphFormat::Dot),
+            "json" => Ok(GraphFormat::Json),
+            _ => Err(format!("'{}' isn't a graph format, use dot or json", s)),
+        }
+    }
+}
+
+impl GraphFormat {
+    pub fn from_path(path: &Path) -> Option<GraphFormat> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Json => "json",
+        }
+    }
+}
+
+// Roughly what the sector looks like on a map
+fn sector_color(sector: Sector) -> &'static str {
+    match sector {
+        Sector::Inside => "#d9d9d9",
+        Sector::City => "#bfbfbf",
+        Sector::Field => "#c7e9a0",
+        Sector::Forest => "#5aa05a",
+        Sector::Hills => "#c2a878",
+        Sector::Mountain => "#9c7f5c",
+        Sector::WaterSwim => "#9ecae1",
+        Sector::WaterNoSwim => "#4a90c8",
+        Sector::Air => "#eef6ff",
+        Sector::Desert => "#f3dc8c",
+    }
+}
+
+// Whether the room the exit leads to has an exit straight back
+fn two_way(world: &World, from: &Room, direction: &str, to: u32) -> bool {
+    let Some(back) = Direction::from_word(direction).map(|d| d.reverse().to_string()) else {
+        return false;
+    };
+    world
+        .get_room(to)
+        .and_then(|room| room.exits.get(&back))
+        .is_some_and(|&id| id == from.id)
+}
+
+// An exit that's the same going both ways is only drawn once, from the lower vnum. If the room it
+// leads to isn't being drawn, the way back won't be either, so it's always drawn from this side.
+fn draw_exit(
+    world: &World,
+    included: &HashSet<u32>,
+    from: &Room,
+    direction: &str,
+    to: u32,
+) -> Option<bool> {
+    let two_way = two_way(world, from, direction, to);
+    (!two_way || from.id <= to || !included.contains(&to)).then_some(two_way)
+}
+
+fn sorted_rooms(area: &Area) -> Vec<&Room> {
+    let mut rooms: Vec<&Room> = area.rooms.values().collect();
+    rooms.sort_by_key(|room| room.id);
+    rooms
+}
+
+fn sorted_exits(room: &Room) -> Vec<(&String, u32)> {
+    let mut exits: Vec<(&String, u32)> = room.exits.iter().map(|(d, &to)| (d, to)).collect();
+    exits.sort();
+    exits
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// The graph of one area, or the whole world if no area is given
+pub fn export(world: &World, area_id: Option<AreaId>, format: GraphFormat) -> String {
+    let areas: Vec<&Area> = match area_id {
+        Some(area_id) => world.get_area(area_id).into_iter().collect(),
+        None => world.areas().iter().collect(),
+    };
+    match format {
+        GraphFormat::Dot => dot(world, &areas),
+        GraphFormat::Json => json(world, &areas),
+    }
+}
+
+fn dot(world: &World, areas: &[&Area]) -> String {
+    let mut out = String::from("digraph rooms {\n");
+    out.push_str("    node [shape=box, style=filled, fontsize=10];\n");
+    out.push_str("    edge [fontsize=8];\n");
+
+    let mut included = HashSet::new();
+    for (n, area) in areas.iter().enumerate() {
+        let _ = writeln!(out, "    subgraph cluster_{} {{", n);
+        let _ = writeln!(out, "        label=\"{}\";", escape(&area.metadata.name));
+        for room in sorted_rooms(area) {
+            included.insert(room.id);
+            let _ = writeln!(
+                out,
+                "        {} [label=\"{}\\n{}\", fillcolor=\"{}\"];",
+                room.id,
+                room.id,
+                escape(&room.name),
+                sector_color(room.sector)
+            );
+        }
+        out.push_str("    }\n");
+    }
+
+    let mut outside = HashSet::new();
+    for area in areas {
+        for room in sorted_rooms(area) {
+            for (direction, to) in sorted_exits(room) {
+                let Some(two_way) = draw_exit(world, &included, room, direction, to) else {
+                    continue;
+                };
+                if two_way {
+                    let _ = writeln!(
+                        out,
+                        "    {} -> {} [label=\"{}\", dir=both];",
+                        room.id, to, direction
+                    );
+                } else {
+                    let _ = writeln!(
+                        out,
+                        "    {} -> {} [label=\"{}\", color=red, fontcolor=red];",
+                        room.id, to, direction
+                    );
+                }
+                if !included.contains(&to) {
+                    outside.insert(to);
+                }
+            }
+        }
+    }
+
+    // Rooms in other areas that exits lead to, and ones that don't exist at all
+    let mut outside: Vec<u32> = outside.into_iter().collect();
+    outside.sort_unstable();
+    for id in outside {
+        let label = match world.get_room(id) {
+            Some(room) => format!("{}\\n{}", id, escape(&room.name)),
+            None => format!("{}\\n(missing)", id),
+        };
+        let _ = writeln!(
+            out,
+            "    {} [label=\"{}\", style=dashed, fillcolor=white];",
+            id, label
+        );
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+#[derive(Serialize)]
+struct JsonGraph {
+    areas: Vec<JsonArea>,
+    nodes: Vec<JsonNode>,
+    edges: Vec<JsonEdge>,
+}
+
+#[derive(Serialize)]
+struct JsonArea {
+    name: String,
+    vnums: (u32, u32),
+}
+
+#[derive(Serialize)]
+struct JsonNode {
+    id: u32,
+    name: String,
+    area: String,
+    sector: Sector,
+    color: &'static str,
+}
+
+// Every exit is its own edge here, so the map can draw them however it likes
+#[derive(Serialize)]
+struct JsonEdge {
+    from: u32,
+    to: u32,
+    direction: String,
+    one_way: bool,
+}
+
+fn json(world: &World, areas: &[&Area]) -> String {
+    let mut graph = JsonGraph {
+        areas: Vec::new(),
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    for area in areas {
+        graph.areas.push(JsonArea {
+            name: area.metadata.name.clone(),
+            vnums: area.metadata.vnums,
+        });
+        for room in sorted_rooms(area) {
+            graph.nodes.push(JsonNode {
+                id: room.id,
+                name: room.name.clone(),
+                area: area.metadata.name.clone(),
+                sector: room.sector,
+                color: sector_color(room.sector),
+            });
+            for (direction, to) in sorted_exits(room) {
+                graph.edges.push(JsonEdge {
+                    from: room.id,
+                    to,
+                    direction: direction.clone(),
+                    one_way: !two_way(world, room, direction, to),
+                });
+            }
+        }
+    }
+    serde_json::to_string_pretty(&graph).unwrap_or_default()
+}
@@ -0,0 +1,152 @@
+// Turning a line a player typed into the commands it stands for. Commands can be stacked on one
+// line with `;`, a player's aliases are expanded, and speedwalks like `3n2e` become one move per
+// step.
+use crate::message::Direction;
+use std::collections::BTreeMap;
+
+pub const SEPARATOR: char = ';';
+// The most commands one line can turn into, so a speedwalk or alias can't flood the game
+const MAX_COMMANDS: usize = 50;
+// How deep aliases can use other aliases
+const MAX_ALIAS_DEPTH: usize = 10;
+
+pub fn expand(line: &str, aliases: &BTreeMap<String, String>) -> Result<Vec<String>, String> {
+    let mut commands = Vec::new();
+    expand_into(line, aliases, &mut Vec::new(), &mut commands)?;
+    Ok(commands)
+}
+
+fn expand_into(
+    line: &str,
+    aliases: &BTreeMap<String, String>,
+    chain: &mut Vec<String>,
+    commands: &mut Vec<String>,
+) -> Result<(), String> {
+    // Defining an alias takes the rest of the line as it is, separators and all
+    let first = line.split_whitespace().next().unwrap_or_default();
+    if first.eq_ignore_ascii_case("alias") {
+        commands.push(line.trim().to_string());
+        return Ok(());
+    }
+
+    for part in line.split(SEPARATOR) {
+        let part = part.trim();
+        let (word, arguments) = part.split_once(' ').unwrap_or((part, ""));
+        let word = word.to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+
+        // An alias that's already being expanded is taken as the command it shadows, so that
+        // eg `alias look look;exits` works and aliases can't loop forever
+        if let Some(body) = aliases.get(&word).filter(|_| !chain.contains(&word)) {
+            if chain.len() >= MAX_ALIAS_DEPTH {
+                return Err(format!("The alias '{}' goes too deep.", word));
+            }
+            chain.push(word);
+            expand_into(
+                &substitute(body, arguments.trim()),
+                aliases,
+                chain,
+                commands,
+            )?;
+            chain.pop();
+        } else if word == "run" {
+            let Some(steps) = parse_speedwalk(arguments.trim()) else {
+                return Err(format!(
+                    "Run where? eg run 3n2e, up to {} steps",
+                    MAX_COMMANDS
+                ));
+            };
+            commands.extend(steps.into_iter().map(|d| d.to_string()));
+        } else if let Some(steps) = part
+            .contains(|c: char| c.is_ascii_digit())
+            .then(|| parse_speedwalk(part))
+            .flatten()
+        {
+            commands.extend(steps.into_iter().map(|d| d.to_string()));
+        } else {
+            commands.push(part.to_string());
+        }
+
+        if commands.len() > MAX_COMMANDS {
+            return Err(String::from("That's too many commands at once."));
+        }
+    }
+    Ok(())
+}
+
+// Fill in an alias's arguments: $1 to $9 are the words after the alias and $* is all of them.
+// If the alias doesn't use any, they go on the end.
+fn substitute(body: &str, arguments: &str) -> String {
+    let words: Vec<&str> = arguments.split_whitespace().collect();
+    let mut out = String::new();
+    let mut used = false;
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some('*') => {
+                chars.next();
+                out.push_str(arguments);
+                used = true;
+            }
+            Some(&digit @ '1'..='9') => {
+                chars.next();
+                let n = digit as usize - '1' as usize;
+                out.push_str(words.get(n).copied().unwrap_or_default());
+                used = true;
+            }
+            _ => out.push(c),
+        }
+    }
+    if !used && !arguments.is_empty() {
+        out.push(' ');
+        out.push_str(arguments);
+    }
+    out
+}
+
+// The steps of a speedwalk like "3n2eu", or None if it isn't one
+pub fn parse_speedwalk(walk: &str) -> Option<Vec<Direction>> {
+    let mut steps = Vec::new();
+    let mut count = String::new();
+    for c in walk.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            count.push(c);
+            continue;
+        }
+        let direction = Direction::from_word(&c.to_string())?;
+        let times: usize = match count.as_str() {
+            "" => 1,
+            count => count.parse().ok().filter(|&n| n > 0)?,
+        };
+        if steps.len() + times > MAX_COMMANDS {
+            return None;
+        }
+        steps.extend(std::iter::repeat_n(direction, times));
+        count.clear();
+    }
+    (count.is_empty() && !steps.is_empty()).then_some(steps)
+}
+
+// Split the first argument off a command, like ROM's one_argument. An argument in quotes can have
+// spaces in it, eg the spell in cast 'magic missile' hound.
+pub fn one_argument(argument: &str) -> (&str, &str) {
+    let argument = argument.trim_start();
+    let quote = argument.chars().next().filter(|c| *c == '\'' || *c == '"');
+    match quote {
+        Some(quote) => {
+            let rest = &argument[1..];
+            let (first, rest) = rest.split_once(quote).unwrap_or((rest, ""));
+            (first.trim(), rest.trim_start())
+        }
+        None => {
+            let (first, rest) = argument.split_once(' ').unwrap_or((argument, ""));
+            (first, rest.trim_start())
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// Looking over the world from the command line without starting the server, so builders can check
+// their areas before they go live. Used by the `check`, `stats` and `dump` subcommands.
+use crate::{area::Area, programs::ProgramKind, validation::references, world::World};
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+use strum::IntoEnumIterator;
+
+// Every room that can be walked to from the start room, following exits
+pub fn reachable_rooms(world: &World, start: u32) -> HashSet<u32> {
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    if world.get_room(start).is_some() {
+        seen.insert(start);
+        queue.push_back(start);
+    }
+    while let Some(room_id) = queue.pop_front() {
+        let Some(room) = world.get_room(room_id) else {
+            continue;
+        };
+        for &destination in room.exits.values() {
+            if world.get_room(destination).is_some() && seen.insert(destination) {
+                queue.push_back(destination);
+            }
+        }
+    }
+    seen
+}
+
+// Rooms that no exit from any other room leads to
+fn orphan_rooms(world: &World) -> HashSet<u32> {
+    let entered: HashSet<u32> = world
+        .areas()
+        .iter()
+        .flat_map(|area| area.rooms.values())
+        .flat_map(|room| room.exits.values().filter(move |&&to| to != room.id))
+        .copied()
+        .collect();
+    world
+        .areas()
+        .iter()
+        .flat_map(|area| area.rooms.keys())
+        .filter(|id| !entered.contains(id))
+        .copied()
+        .collect()
+}
+
+// The area's rooms that match, in order
+fn area_rooms(area: &Area, matches: impl Fn(&u32) -> bool) -> Vec<u32> {
+    let mut vnums: Vec<u32> = area.rooms.keys().copied().filter(matches).collect();
+    vnums.sort_unstable();
+    vnums
+}
+
+fn vnum_list(vnums: &[u32]) -> String {
+    let list: Vec<String> = vnums.iter().map(u32::to_string).collect();
+    list.join(", ")
+}
+
+// Counts of what's in each area, and the rooms nobody can get to
+pub fn stats(world: &World, start: u32) -> String {
+    let orphans = orphan_rooms(world);
+    let reachable = reachable_rooms(world, start);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<30} {:>11} {:>6} {:>7} {:>7} {:>6} {:>7} {:>11}",
+        "Area", "Vnums", "Rooms", "Mobiles", "Objects", "Resets", "Orphans", "Unreachable"
+    );
+    let mut totals = [0; 6];
+    let mut details = String::new();
+    for area in world.areas() {
+        let area_orphans = area_rooms(area, |id| orphans.contains(id));
+        let unreachable = area_rooms(area, |id| !reachable.contains(id));
+        let counts = [
+            area.rooms.len(),
+            area.mobiles.len(),
+            area.objects.len(),
+            area.resets.len(),
+            area_orphans.len(),
+            unreachable.len(),
+        ];
+        let (low, high) = area.metadata.vnums;
+        let _ = writeln!(
+            out,
+            "{:<30} {:>11} {:>6} {:>7} {:>7} {:>6} {:>7} {:>11}",
+            area.metadata.name,
+            format!("{}-{}", low, high),
+            counts[0],
+            counts[1],
+            counts[2],
+            counts[3],
+            counts[4],
+            counts[5]
+        );
+        for (total, count) in totals.iter_mut().zip(counts) {
+            *total += count;
+        }
+
+        if !area_orphans.is_empty() {
+            let _ = writeln!(
+                details,
+                "{}: no exits lead to {}",
+                area.metadata.name,
+                vnum_list(&area_orphans)
+            );
+        }
+        if !unreachable.is_empty() {
+            let _ = writeln!(
+                details,
+                "{}: can't be reached from room {}: {}",
+                area.metadata.name,
+                start,
+                vnum_list(&unreachable)
+            );
+        }
+    }
+    let _ = writeln!(
+        out,
+        "{:<30} {:>11} {:>6} {:>7} {:>7} {:>6} {:>7} {:>11}",
+        "Total", "", totals[0], totals[1], totals[2], totals[3], totals[4], totals[5]
+    );
+    if world.get_room(start).is_none() {
+        let _ = writeln!(out, "\nThe start room {} isn't in any loaded area!", start);
+    }
+    if !details.is_empty() {
+        out.push('\n');
+        out.push_str(&details);
+    }
+    out
+}
+
+// Write one thing out in the native area format
+fn to_toml<T: Serialize>(value: &T) -> String {
+    toml::to_string_pretty(value)
+        .unwrap_or_else(|error| format!("Couldn't write it out: {}", error))
+}
+
+// Everything with a vnum: the room, mobile, object and program, whichever there are, along with
+// the area they're in and the resets that use them
+pub fn dump(world: &World, vnum: u32) -> Option<String> {
+    let mut out = String::new();
+    let area_name = |area_id: Option<usize>| {
+        area_id.and_then(|id| world.get_area(id)).map_or(
+            String::from("no area"),
+            |area| match &area.metadata.file {
+                Some(file) => format!("{} ({})", area.metadata.name, file.display()),
+                None => area.metadata.name.clone(),
+            },
+        )
+    };
+
+    if let Some(room) = world.get_room(vnum) {
+        let area = area_name(world.room_area(vnum));
+        let _ = writeln!(out, "# Room {} in {}\n{}", vnum, area, to_toml(room));
+    }
+    if let Some(mobile) = world.get_mobile_template(vnum) {
+        let area = area_name(world.vnum_area(vnum));
+        let _ = writeln!(out, "# Mobile {} in {}\n{}", vnum, area, to_toml(mobile));
+    }
+    if let Some(object) = world.get_object_template(vnum) {
+        let area = area_name(world.vnum_area(vnum));
+        let _ = writeln!(out, "# Object {} in {}\n{}", vnum, area, to_toml(object));
+    }
+    for kind in ProgramKind::iter() {
+        if let Some(program) = world.get_program(kind, vnum) {
+            let _ = writeln!(out, "# {} program {}\n{}", kind, vnum, program.code);
+        }
+    }
+    if out.is_empty() {
+        return None;
+    }
+
+    for area in world.areas() {
+        let resets: Vec<String> = area
+            .resets
+            .iter()
+            .filter(|reset| references(reset).iter().any(|&(_, v)| v == vnum))
+            .map(|reset| format!("{:?}", reset))
+            .collect();
+        if !resets.is_empty() {
+            let _ = writeln!(
+                out,
+                "# Resets in {}\n{}\n",
+                area.metadata.name,
+                resets.join("\n")
+            );
+        }
+    }
+    Some(out.trim_end().to_string())
+}
@@ -1,7 +1,8 @@
-use std::fs::File;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use figment::{
     providers::{Format, Toml},
     Figment,
@@ -13,74 +14,233 @@ use tokio::{net::TcpListener, sync::mpsc};
 extern crate merc_parser;
 
 mod actions;
+mod affects;
 mod area;
+mod area_file;
+mod character;
+mod circle;
 mod connection;
+mod creation;
+mod dice;
+mod equipment;
+mod fight;
 mod game_loop;
+mod graph;
+mod input;
+mod inspect;
+mod map;
 mod merc;
 mod message;
 mod mobiles;
+mod objects;
+mod olc;
+mod pathfind;
 mod player;
+mod position;
+mod programs;
+mod random;
+mod regen;
 mod reset;
 mod room;
+mod shops;
+mod skills;
+mod specials;
+mod stats;
+mod time;
+mod validation;
 mod world;
 
+use area_file::{AreaEntry, AreaFormat};
 use connection::handle_connection;
+use creation::CreationData;
 use game_loop::game_loop;
+use graph::GraphFormat;
+use validation::ValidationPolicy;
 use world::World;
 
 #[derive(Parser, Debug, Deserialize, Serialize)]
 #[command(version, about, long_about = None)]
 struct Args {
     // Path to config file
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     #[clap(value_parser)]
-    config_file: InputArg,
+    config_file: Option<InputArg>,
+    // Convert an area file to another format and exit, eg `--convert midgaard.are midgaard.toml`
+    #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+    convert: Option<Vec<PathBuf>>,
+    // Something to do with the areas in the config file instead of starting the server
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Deserialize, Serialize)]
+enum Command {
+    // Load and validate the areas, exiting with an error if there are any problems
+    Check,
+    // Count what's in each area, and list the rooms that can't be reached
+    Stats {
+        // The room players start in, the recall room if not given
+        #[arg(long)]
+        from: Option<u32>,
+    },
+    // Print the room, mobile, object and program with a vnum
+    Dump {
+        vnum: u32,
+    },
+    // Write the rooms and their exits as a graph, as DOT for Graphviz or JSON for the web map
+    Graph {
+        // Just this area rather than the whole world
+        #[arg(long)]
+        area: Option<String>,
+        // dot or json, going by the output file's extension if not given
+        #[arg(long)]
+        format: Option<GraphFormat>,
+        // Where to write it, or standard output if not given
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Debug, PartialEq, Deserialize, Serialize)]
 struct Config {
-    areas: Vec<PathBuf>,
+    areas: Vec<AreaEntry>,
     port: u16,
+    // Whether problems found checking the areas stop the server from starting
+    #[serde(default)]
+    area_validation: ValidationPolicy,
+    // Whether an area that fails to load is left out, rather than stopping the server starting
+    #[serde(default)]
+    skip_broken_areas: bool,
+    // The range of vnums each builder can edit online, by username
+    #[serde(default)]
+    builders: HashMap<String, (u32, u32)>,
+    // Where players are sent when the room they're in goes away, eg when an area is reloaded
+    #[serde(default = "default_recall_vnum")]
+    recall_vnum: u32,
+    // Whether to reload areas when their files change
+    #[serde(default)]
+    watch_areas: bool,
+    // Where players are saved between sessions
+    #[serde(default = "default_player_directory")]
+    player_directory: PathBuf,
+    // Where the races and classes players pick from are
+    #[serde(default = "default_data_directory")]
+    data_directory: PathBuf,
+}
+
+fn default_recall_vnum() -> u32 {
+    3001
+}
+
+fn default_player_directory() -> PathBuf {
+    PathBuf::from("players")
+}
+
+fn default_data_directory() -> PathBuf {
+    PathBuf::from("data")
 }
 
 #[tokio::main]
 async fn main() {
     // TODO: Implement shutdown via ctrl-c or a command from a wiz
     // https://tokio.rs/tokio/topics/shutdown
-    // Start logging
-    let subscriber = tracing_subscriber::fmt::init();
+    // Start logging, to stderr so that what the subcommands print can be piped on its own
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
 
     // Parse the CLI argument
     let args = Args::parse();
-    // Read the config file
-    let config: Config = Figment::new()
-        .merge(Toml::string(
-            // TODO: This is ugly
-            args.config_file.read_to_string().unwrap().as_str(),
-        ))
-        .extract()
-        .unwrap();
+    if let Some([from, to]) = args.convert.as_deref() {
+        convert(from, to);
+        return;
+    }
+    let Some(config_file) = args.config_file else {
+        tracing::error!("No config file given");
+        std::process::exit(1);
+    };
+    let config = read_config(&config_file);
+    match args.command {
+        Some(Command::Check) => std::process::exit(check(&config)),
+        Some(Command::Stats { from }) => {
+            let world = load_world(&config);
+            print!(
+                "{}",
+                inspect::stats(&world, from.unwrap_or(config.recall_vnum))
+            );
+            return;
+        }
+        Some(Command::Dump { vnum }) => {
+            let world = load_world(&config);
+            match inspect::dump(&world, vnum) {
+                Some(dump) => println!("{}", dump),
+                None => {
+                    eprintln!("Nothing has vnum {}", vnum);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(Command::Graph {
+            area,
+            format,
+            output,
+        }) => {
+            let world = load_world(&config);
+            export_graph(&world, area.as_deref(), format, output.as_deref());
+            return;
+        }
+        None => (),
+    }
 
     // TODO: Debug tracing of config load
 
-    let listener = TcpListener::bind(format!("127.0.0.1:{}", config.port))
-        .await
-        .unwrap();
+    let listener = match TcpListener::bind(format!("127.0.0.1:{}", config.port)).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            tracing::error!(
+                port = config.port,
+                "Couldn't listen for connections: {}",
+                error
+            );
+            std::process::exit(1);
+        }
+    };
     tracing::info!(port = config.port, "Starting Telnet server");
 
+    let creation = match CreationData::load(&config.data_directory) {
+        Ok(creation) => Arc::new(creation),
+        Err(error) => {
+            tracing::error!("Couldn't load the races and classes: {}", error);
+            std::process::exit(1);
+        }
+    };
+
     let players = player::Players::new();
     let mut world = World::new();
+    world.builders = config.builders.clone();
+    world.recall_vnum = config.recall_vnum;
+    world.player_directory = config.player_directory.clone();
+    world.creation = creation.clone();
 
     // TODO: If the area list is empty (or there is no area list in the config file), load the
     // default set of rooms we built
-    for area_file in config.areas {
-        tracing::info!(filename = ?area_file, "Loading area file");
-        let area_file = File::open(area_file).unwrap();
-        let area = merc::load_area_file(area_file);
-        world.add_area(area);
-    }
+    load_areas(&config, &mut world, config.skip_broken_areas);
 
-    // TODO: Validation of provided areas
+    let problems = validation::check_world(&world);
+    for problem in &problems {
+        match config.area_validation {
+            ValidationPolicy::Error => tracing::error!("{}", problem),
+            ValidationPolicy::Warn => tracing::warn!("{}", problem),
+        }
+    }
+    if config.area_validation == ValidationPolicy::Error && !problems.is_empty() {
+        tracing::error!(
+            count = problems.len(),
+            "Found problems in the areas, exiting"
+        );
+        std::process::exit(1);
+    }
 
     // Call an initial reset of the world to place all the mobs and objects
     world.reset();
@@ -90,14 +250,183 @@ async fn main() {
     // Channel shared among clients and the game loop
     let (game_sender, game_receiver) = mpsc::channel(32);
 
+    if config.watch_areas {
+        let areas = world
+            .areas()
+            .iter()
+            .filter_map(|area| Some((area.metadata.file.clone()?, area.metadata.format)))
+            .collect();
+        tokio::spawn(area_file::watch(areas, game_sender.clone()));
+    }
+
     tokio::spawn(game_loop(players.clone(), world, game_receiver));
 
     loop {
-        let (stream, _) = listener.accept().await.unwrap();
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(error) => {
+                tracing::warn!("Failed to accept a connection: {}", error);
+                continue;
+            }
+        };
         let sender_clone = game_sender.clone();
         let players_clone = players.clone();
+        let creation_clone = creation.clone();
+        let player_directory = config.player_directory.clone();
         tokio::spawn(async move {
-            handle_connection(players_clone, stream, sender_clone).await;
+            handle_connection(
+                players_clone,
+                stream,
+                sender_clone,
+                creation_clone,
+                &player_directory,
+            )
+            .await;
         });
     }
 }
+
+// Load an area file and save it again in the format of another, eg to turn a ROM area into TOML
+fn convert(from: &Path, to: &Path) {
+    let mut area = match area_file::load(from, None) {
+        Ok((area, warnings)) => {
+            for warning in &warnings {
+                tracing::warn!("{}", warning);
+            }
+            area
+        }
+        Err(error) => {
+            tracing::error!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    area.metadata.file = Some(to.to_path_buf());
+    area.metadata.format = AreaFormat::from_path(to);
+
+    let mut world = World::new();
+    let area_id = world.add_area(area);
+    let Some(area) = world.get_area(area_id) else {
+        return;
+    };
+    match area_file::save(area, &world, to, None) {
+        Ok(()) => tracing::info!("Converted {} to {}", from.display(), to.display()),
+        Err(error) => {
+            tracing::error!("Couldn't write {}: {}", to.display(), error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn read_config(config_file: &InputArg) -> Config {
+    // TODO: This is ugly
+    let config_text = match config_file.read_to_string() {
+        Ok(text) => text,
+        Err(error) => {
+            tracing::error!(config = ?config_file, "Couldn't read the config file: {}", error);
+            std::process::exit(1);
+        }
+    };
+    match Figment::new()
+        .merge(Toml::string(config_text.as_str()))
+        .extract()
+    {
+        Ok(config) => config,
+        Err(error) => {
+            tracing::error!(config = ?config_file, "Bad config file: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Load the areas listed in the settings into the world, returning how many couldn't be loaded.
+// Unless broken areas are skipped, the first one that fails to load stops the program.
+fn load_areas(config: &Config, world: &mut World, skip_broken: bool) -> usize {
+    let mut failed = 0;
+    for entry in &config.areas {
+        tracing::info!(filename = ?entry.path(), "Loading area file");
+        match area_file::load(entry.path(), entry.format()) {
+            Ok((area, warnings)) => {
+                for warning in &warnings {
+                    tracing::warn!("{}", warning);
+                }
+                world.add_area(area);
+            }
+            Err(error) if skip_broken => {
+                tracing::error!("{}", error);
+                tracing::warn!(filename = ?entry.path(), "Skipping the area");
+                failed += 1;
+            }
+            Err(error) => {
+                tracing::error!("{}", error);
+                tracing::error!("Set skip_broken_areas in the settings to start without it");
+                std::process::exit(1);
+            }
+        }
+    }
+    failed
+}
+
+// Just the areas, for looking at rather than playing in
+fn load_world(config: &Config) -> World {
+    let mut world = World::new();
+    world.recall_vnum = config.recall_vnum;
+    load_areas(config, &mut world, true);
+    world
+}
+
+// Load every area and run the checks, for builders to use before putting their areas live.
+// Returns the exit code.
+fn check(config: &Config) -> i32 {
+    let mut world = World::new();
+    let failed = load_areas(config, &mut world, true);
+    let problems = validation::check_world(&world);
+    for problem in &problems {
+        tracing::error!("{}", problem);
+    }
+
+    if failed == 0 && problems.is_empty() {
+        println!("Checked {} areas, no problems found", world.areas().len());
+        0
+    } else {
+        println!(
+            "Checked {} areas: {} failed to load, {} problems found",
+            config.areas.len(),
+            failed,
+            problems.len()
+        );
+        1
+    }
+}
+
+// Write the room graph for the graph subcommand
+fn export_graph(
+    world: &World,
+    area: Option<&str>,
+    format: Option<GraphFormat>,
+    output: Option<&Path>,
+) {
+    let area_id = match area {
+        Some(name) => match world.find_area(name) {
+            Some(area_id) => Some(area_id),
+            None => {
+                eprintln!("There's no area called {}", name);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let format = format
+        .or_else(|| output.and_then(GraphFormat::from_path))
+        .unwrap_or(GraphFormat::Dot);
+
+    let graph = graph::export(world, area_id, format);
+    match output {
+        Some(path) => {
+            if let Err(error) = std::fs::write(path, graph) {
+                tracing::error!("Couldn't write {}: {}", path.display(), error);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", graph),
+    }
+}
@@ -0,0 +1,150 @@
+// Drawing the rooms around a player as an ASCII map, laid out on a grid by following north, south,
+// east and west exits. Areas don't have to be laid out on a grid, so a room that would land on a
+// spot that's taken, or that was already drawn somewhere else, is left off and its exit marked.
+use crate::{message::Direction, world::World};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub const LEGEND: &str =
+    "@ you  * others  # room  + up/down  > < ^ v one-way  ? doesn't fit on the map";
+
+// How far each direction moves across the grid, with north at the top
+fn offset(direction: Direction) -> Option<(i32, i32)> {
+    match direction {
+        Direction::North => Some((0, -1)),
+        Direction::South => Some((0, 1)),
+        Direction::East => Some((1, 0)),
+        Direction::West => Some((-1, 0)),
+        Direction::Up | Direction::Down => None,
+    }
+}
+
+// The exits of a room that can be drawn on the grid
+fn flat_exits(world: &World, room_id: u32) -> Vec<(Direction, (i32, i32), u32)> {
+    let Some(room) = world.get_room(room_id) else {
+        return Vec::new();
+    };
+    let mut exits: Vec<_> = room
+        .exits
+        .iter()
+        .filter_map(|(word, &to)| {
+            let direction = Direction::from_word(word)?;
+            world.get_room(to)?;
+            Some((direction, offset(direction)?, to))
+        })
+        .collect();
+    exits.sort_by_key(|(direction, _, _)| direction.number());
+    exits
+}
+
+fn leads_back(world: &World, from: u32, direction: Direction, to: u32) -> bool {
+    world
+        .get_room(to)
+        .and_then(|room| room.exits.get(&direction.reverse().to_string()))
+        .is_some_and(|&id| id == from)
+}
+
+// The map as lines of text, (radius * 4 + 1) characters square. Rooms are on even rows and
+// columns, with the exits between them.
+pub fn draw(world: &World, center: u32, radius: u32, others: &HashSet<u32>) -> Vec<String> {
+    let radius = radius as i32;
+    let mut placed: HashMap<u32, (i32, i32)> = HashMap::new();
+    let mut occupied: HashMap<(i32, i32), u32> = HashMap::new();
+    let mut misfits: HashSet<(u32, u8)> = HashSet::new();
+
+    // Lay the rooms out breadth first, so the nearest ones get their spots first, as far as the
+    // edges of the map
+    let mut queue = VecDeque::new();
+    if world.get_room(center).is_some() {
+        placed.insert(center, (0, 0));
+        occupied.insert((0, 0), center);
+        queue.push_back((center, (0, 0)));
+    }
+    while let Some((room_id, (x, y))) = queue.pop_front() {
+        for (direction, (dx, dy), to) in flat_exits(world, room_id) {
+            let spot = (x + dx, y + dy);
+            if spot.0.abs() > radius || spot.1.abs() > radius {
+                continue;
+            }
+            let fits = match (placed.get(&to), occupied.get(&spot)) {
+                (Some(&at), _) => at == spot,
+                (None, Some(_)) => false,
+                (None, None) => {
+                    placed.insert(to, spot);
+                    occupied.insert(spot, to);
+                    queue.push_back((to, spot));
+                    true
+                }
+            };
+            if !fits {
+                misfits.insert((room_id, direction.number()));
+            }
+        }
+    }
+
+    let size = (radius * 4 + 1) as usize;
+    let mut canvas = vec![vec![' '; size]; size];
+    let to_canvas = |(x, y): (i32, i32)| ((x + radius) * 2, (y + radius) * 2);
+    for (&room_id, &spot) in &placed {
+        let (column, row) = to_canvas(spot);
+        let Some(room) = world.get_room(room_id) else {
+            continue;
+        };
+        canvas[row as usize][column as usize] = if room_id == center {
+            '@'
+        } else if others.contains(&room_id) {
+            '*'
+        } else if room.exits.contains_key("up") || room.exits.contains_key("down") {
+            '+'
+        } else {
+            '#'
+        };
+
+        for (direction, (dx, dy), to) in flat_exits(world, room_id) {
+            let (column, row) = (column + dx, row + dy);
+            if column < 0 || row < 0 || column >= size as i32 || row >= size as i32 {
+                continue;
+            }
+            let cell = &mut canvas[row as usize][column as usize];
+            let elsewhere = placed
+                .get(&to)
+                .is_some_and(|&at| at != (spot.0 + dx, spot.1 + dy));
+            if elsewhere || misfits.contains(&(room_id, direction.number())) {
+                *cell = '?';
+            } else if leads_back(world, room_id, direction, to) || !placed.contains_key(&to) {
+                // Exits off the edge of the map are drawn as if they're two-way
+                if *cell != '?' {
+                    *cell = if dx == 0 { '|' } else { '-' };
+                }
+            } else if *cell == ' ' {
+                *cell = match direction {
+                    Direction::North => '^',
+                    Direction::South => 'v',
+                    Direction::East => '>',
+                    _ => '<',
+                };
+            }
+        }
+    }
+
+    canvas
+        .into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect()
+}
+
+// Put the map to the left of some text, eg a room description
+pub fn beside(map: &[String], text: &str) -> String {
+    let width = map.first().map_or(0, String::len);
+    let lines: Vec<&str> = text.lines().collect();
+    let height = map.len().max(lines.len());
+    (0..height)
+        .map(|i| {
+            let left = map.get(i).map_or("", String::as_str);
+            let right = lines.get(i).copied().unwrap_or_default();
+            format!("{:width$}  {}", left, right, width = width)
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
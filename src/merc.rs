@@ -1,16 +1,66 @@
 // Implement for compatability with Diku/Merc style areas
-use crate::{area::Area, mobiles::Mobile, reset::ResetCommand, room::Room, world::World};
+use crate::area_file::{AreaLoader, LoadError};
+use crate::{
+    area::{Area, AreaMetadata},
+    message::Direction,
+    mobiles::Mobile,
+    programs::ProgramKind,
+    room::Room,
+};
+use std::fs::File;
 use std::io::Read;
+use std::path::Path;
+use strum::IntoEnumIterator;
 
-use merc_parser::{parse_area_file, RomResetCommand};
+use merc_parser::parse_area_file;
 
-pub fn load_area_file<R: Read>(mut area_file: R) -> Area {
-    let mut buffer = String::new();
-    area_file.read_to_string(&mut buffer);
+// Sections that merc_parser doesn't cover yet, which we read from the raw file ourselves
+mod header;
+mod mobiles;
+mod objects;
+mod programs;
+pub mod reader;
+mod resets;
+mod rooms;
+mod shops;
+mod specials;
+mod writer;
+
+pub use writer::write_area;
+
+pub struct MercLoader;
+
+impl AreaLoader for MercLoader {
+    fn load(&self, path: &Path, warnings: &mut Vec<LoadError>) -> Result<Area, LoadError> {
+        let file = File::open(path).map_err(|error| {
+            LoadError::new(format!("Couldn't open the file: {}", error)).in_file(path)
+        })?;
+        let mut file_warnings = Vec::new();
+        let area = load_area_file(file, &mut file_warnings).map_err(|error| error.in_file(path))?;
+        warnings.extend(file_warnings.into_iter().map(|w| w.in_file(path)));
+        Ok(area)
+    }
+}
 
-    let mut world = World::new();
+pub fn load_area_file<R: Read>(
+    mut area_file: R,
+    warnings: &mut Vec<LoadError>,
+) -> Result<Area, LoadError> {
+    let mut buffer = String::new();
+    area_file
+        .read_to_string(&mut buffer)
+        .map_err(|error| LoadError::new(format!("Couldn't read the file: {}", error)))?;
 
-    let (_, parsed_area) = parse_area_file(&buffer).unwrap();
+    let (rest, parsed_area) = parse_area_file(&buffer)
+        .map_err(|error| LoadError::new(format!("Couldn't parse the area: {:?}", error)))?;
+    // merc_parser stops at the first section it doesn't know, which we may still read ourselves
+    if !rest.trim().is_empty() {
+        let offset = buffer.len() - rest.len();
+        warnings.push(
+            LoadError::new("The area parser stopped early, anything after here may be missing")
+                .at(&buffer, offset),
+        );
+    }
     tracing::info!(
         area_name = parsed_area.metadata.display_name,
         area_author = parsed_area.metadata.author,
@@ -23,17 +73,13 @@ pub fn load_area_file<R: Read>(mut area_file: R) -> Area {
         let mut room = Room::new(r.vnum, &r.room_name, &r.description);
 
         for d in r.doors {
-            // Map door indices to directions
-            let direction = match d.direction {
-                0u8 => "north",
-                1u8 => "east",
-                2u8 => "south",
-                3u8 => "west",
-                4u8 => "up",
-                5u8 => "down",
-                _ => panic!(),
-            };
-            room.add_exit(direction, d.to_room);
+            match Direction::from_number(d.direction) {
+                Some(direction) => room.add_exit(&direction.to_string(), d.to_room),
+                None => warnings.push(
+                    LoadError::new(format!("Skipping an exit in direction {}", d.direction))
+                        .vnum(r.vnum),
+                ),
+            }
         }
 
         tracing::debug!(
@@ -47,11 +93,16 @@ pub fn load_area_file<R: Read>(mut area_file: R) -> Area {
     // Iterate over the mobs in the file and turn into our internal representation
     let mut mobiles: Vec<Mobile> = Vec::with_capacity(parsed_area.mobiles.len());
     for m in parsed_area.mobiles {
-        let mobile = Mobile {
-            id: u32::try_from(m.vnum).unwrap(),
-            keywords: m.keywords,
-            room_description: m.short_description,
+        let Ok(vnum) = u32::try_from(m.vnum) else {
+            warnings.push(LoadError::new(format!(
+                "Skipping a mobile with vnum {}",
+                m.vnum
+            )));
+            continue;
         };
+        let mut mobile = Mobile::new(vnum);
+        mobile.keywords = m.keywords;
+        mobile.room_description = m.short_description;
 
         tracing::debug!(
             mobile_id = mobile.id,
@@ -61,37 +112,89 @@ pub fn load_area_file<R: Read>(mut area_file: R) -> Area {
         mobiles.push(mobile);
     }
 
-    // Same with resets
-    let mut resets: Vec<ResetCommand> = Vec::with_capacity(parsed_area.resets.len());
-    for r in parsed_area.resets {
-        let reset = {
-            match r {
-                RomResetCommand::LoadMobile(lm) => Some(ResetCommand {
-                    mobile_id: u32::try_from(lm.mobile_vnum).unwrap(),
-                    room_id: u32::try_from(lm.room_vnum).unwrap(),
-                }),
-                _ => {
-                    tracing::warn!("Unimplemented reset {:?}", r);
-                    None
-                }
-            }
-        };
+    mobiles::load_mobile_properties(&buffer, &mut mobiles, warnings);
+
+    // Hand each shop to the mobile that runs it
+    for shop in shops::load_shops(&buffer, warnings) {
+        match mobiles.iter_mut().find(|m| m.id == shop.keeper) {
+            Some(keeper) => keeper.shop = Some(shop),
+            None => warnings.push(LoadError {
+                section: Some(String::from("#SHOPS")),
+                ..LoadError::new("The shop keeper isn't in this area").vnum(shop.keeper)
+            }),
+        }
+    }
 
-        if let Some(r) = reset {
-            tracing::debug!(
-                // TODO: Do everything different depending on the type of reset command we have
-                r.mobile_id,
-                r.room_id,
-                "Adding reset to world"
-            );
+    for (vnum, special) in specials::load_specials(&buffer, warnings) {
+        match mobiles.iter_mut().find(|m| m.id == vnum) {
+            Some(mobile) => mobile.special = Some(special),
+            None => warnings.push(LoadError {
+                section: Some(String::from("#SPECIALS")),
+                ..LoadError::new(format!(
+                    "Special {} is for a mobile which isn't in this area",
+                    special.name
+                ))
+                .vnum(vnum)
+            }),
+        }
+    }
 
-            resets.push(r);
+    for (vnum, trigger) in programs::load_triggers(&buffer) {
+        if let Some(mobile) = mobiles.iter_mut().find(|m| m.id == vnum) {
+            mobile.triggers.push(trigger);
         }
     }
 
-    Area {
-        rooms,
-        mobiles,
-        resets,
+    let header = header::load_header(&buffer);
+    let mut area = Area::new(AreaMetadata {
+        name: parsed_area.metadata.display_name.to_string(),
+        author: parsed_area.metadata.author.to_string(),
+        vnums: header.vnums.unwrap_or_default(),
+        levels: header.levels,
+        file: None,
+        format: None,
+        credits: header.credits,
+        areadata: header.areadata,
+    });
+
+    for room in rooms {
+        area.add_room(room);
+    }
+    for properties in rooms::load_room_properties(&buffer, warnings) {
+        if let Some(room) = area.rooms.get_mut(&properties.vnum) {
+            room.flags = properties.flags;
+            room.sector = properties.sector;
+            for (direction, door) in properties.doors {
+                if let Some(direction) = Direction::from_number(direction) {
+                    room.doors.insert(direction.to_string(), door);
+                }
+            }
+            room.extra_descriptions = properties.extra_descriptions;
+            room.heal_rate = properties.heal_rate;
+            room.mana_rate = properties.mana_rate;
+            room.clan = properties.clan;
+            room.owner = properties.owner;
+            room.triggers = properties.triggers;
+        }
     }
+    for mobile in mobiles {
+        area.add_mobile(mobile);
+    }
+    for object in objects::load_objects(&buffer, warnings) {
+        area.add_object(object);
+    }
+    for kind in ProgramKind::iter() {
+        for program in programs::load_programs(&buffer, kind, warnings) {
+            area.add_program(kind, program);
+        }
+    }
+    // merc_parser only understands mobile resets, and resets that give objects to mobiles depend on
+    // their order relative to those, so read the whole section ourselves
+    area.resets = resets::load_resets(&buffer, warnings);
+
+    if header.vnums.is_none() {
+        area.metadata.vnums = area.used_vnums().unwrap_or_default();
+    }
+
+    Ok(area)
 }
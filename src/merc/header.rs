@@ -0,0 +1,85 @@
+// Reading the ranges in an area's header, which merc_parser doesn't give us, along with what's
+// needed to write the header back out the way it was
+use super::reader::AreaReader;
+
+#[derive(Default)]
+pub struct Header {
+    pub vnums: Option<(u32, u32)>,
+    pub levels: Option<(u32, u32)>,
+    pub credits: Option<String>,
+    // Each key of an #AREADATA header and the rest of its line, if the area had one
+    pub areadata: Option<Vec<(String, String)>>,
+}
+
+pub fn load_header(buffer: &str) -> Header {
+    if let Some(reader) = AreaReader::section(buffer, "AREA") {
+        read_area(reader)
+    } else if let Some(reader) = AreaReader::section(buffer, "AREADATA") {
+        read_areadata(reader)
+    } else {
+        Header::default()
+    }
+}
+
+// The original format: filename, name and credits strings followed by the vnum range
+fn read_area(mut reader: AreaReader) -> Header {
+    let _filename = reader.read_string();
+    let _name = reader.read_string();
+    let credits = reader.read_string();
+    let vnums = reader
+        .read_number()
+        .zip(reader.read_number())
+        .and_then(|(low, high)| u32::try_from(low).ok().zip(u32::try_from(high).ok()));
+
+    Header {
+        vnums,
+        levels: credits.as_deref().and_then(parse_levels),
+        credits,
+        areadata: None,
+    }
+}
+
+// The OLC format, which is a list of keys and values ending with End
+fn read_areadata(mut reader: AreaReader) -> Header {
+    let mut header = Header::default();
+    let mut fields = Vec::new();
+    while let Some(key) = reader.read_word() {
+        let value =
+            match key.as_str() {
+                "End" => break,
+                "Name" | "Builders" | "Credits" => {
+                    let value = reader.read_string().unwrap_or_default();
+                    if key == "Credits" {
+                        header.levels = parse_levels(&value);
+                        header.credits = Some(value.clone());
+                    }
+                    format!("{}~", value)
+                }
+                // VNUMs, Security, Flags and so on
+                _ => {
+                    let value = reader.read_line().unwrap_or_default().trim().to_string();
+                    if key == "VNUMs" {
+                        let mut numbers = AreaReader::new(&value);
+                        header.vnums = numbers.read_number().zip(numbers.read_number()).and_then(
+                            |(low, high)| u32::try_from(low).ok().zip(u32::try_from(high).ok()),
+                        );
+                    }
+                    value
+                }
+            };
+        fields.push((key, value));
+    }
+    header.areadata = Some(fields);
+    header
+}
+
+// Credits start with the level range in braces, eg "{ 5 20} Hatchet  Mud School". Areas for
+// everyone have something like "{ All }" instead.
+fn parse_levels(credits: &str) -> Option<(u32, u32)> {
+    let inside = credits.strip_prefix('{')?.split('}').next()?;
+    let mut numbers = inside.split_whitespace().map(|n| n.parse::<u32>());
+    match (numbers.next(), numbers.next()) {
+        (Some(Ok(low)), Some(Ok(high))) => Some((low, high)),
+        _ => None,
+    }
+}
@@ -0,0 +1,121 @@
+// Reading the parts of the ROM #MOBILES section that merc_parser doesn't give us, straight into
+// the mobiles it did give us
+use super::reader::AreaReader;
+use crate::area_file::LoadError;
+use crate::{dice::Dice, mobiles::Mobile};
+
+pub fn load_mobile_properties(buffer: &str, mobiles: &mut [Mobile], warnings: &mut Vec<LoadError>) {
+    let Some(mut reader) = AreaReader::section(buffer, "MOBILES") else {
+        return;
+    };
+
+    loop {
+        if reader.read_letter() != Some('#') {
+            reader.warn(
+                None,
+                "Expected '#' while reading mobiles, giving up on the section",
+            );
+            break;
+        }
+        let vnum = match reader.read_number().map(u32::try_from) {
+            Some(Ok(0)) => break,
+            Some(Ok(vnum)) => vnum,
+            _ => {
+                reader.warn(None, "Bad mobile vnum, giving up on the section");
+                break;
+            }
+        };
+
+        // Still read mobiles merc_parser skipped, to get to the next one
+        let mut unknown = Mobile::new(vnum);
+        let mobile = match mobiles.iter_mut().find(|m| m.id == vnum) {
+            Some(mobile) => mobile,
+            None => &mut unknown,
+        };
+        if read_mobile(&mut reader, mobile).is_none() {
+            reader.warn(
+                Some(vnum),
+                "Failed to read mobile, giving up on the section",
+            );
+            break;
+        }
+    }
+    warnings.extend(reader.into_warnings());
+}
+
+fn read_dice(reader: &mut AreaReader, vnum: u32) -> Option<Dice> {
+    let word = reader.read_word()?;
+    match word.parse() {
+        Ok(dice) => Some(dice),
+        Err(error) => {
+            reader.warn(Some(vnum), error);
+            None
+        }
+    }
+}
+
+fn read_mobile(reader: &mut AreaReader, mobile: &mut Mobile) -> Option<()> {
+    let vnum = mobile.id;
+    let _keywords = reader.read_string()?;
+    let _short_description = reader.read_string()?;
+    mobile.long_description = reader.read_string()?.trim_end().to_string();
+    mobile.description = reader.read_string()?;
+    mobile.race = reader.read_string()?;
+
+    mobile.act_flags = reader.read_flags()?;
+    mobile.affected_by = reader.read_flags()?;
+    mobile.alignment = i32::try_from(reader.read_number()?).ok()?;
+    mobile.group = reader.read_number()?;
+
+    mobile.level = u32::try_from(reader.read_number()?).ok()?;
+    mobile.hitroll = i32::try_from(reader.read_number()?).ok()?;
+    mobile.hit_dice = read_dice(reader, vnum)?;
+    mobile.mana_dice = read_dice(reader, vnum)?;
+    mobile.damage_dice = read_dice(reader, vnum)?;
+    mobile.damage_type = reader.read_word()?;
+
+    for armor in mobile.armor.iter_mut() {
+        *armor = reader.read_number()?;
+    }
+    mobile.offense_flags = reader.read_flags()?;
+    mobile.immunity_flags = reader.read_flags()?;
+    mobile.resistance_flags = reader.read_flags()?;
+    mobile.vulnerability_flags = reader.read_flags()?;
+
+    mobile.start_position = reader.read_word()?;
+    mobile.default_position = reader.read_word()?;
+    mobile.sex = reader.read_word()?;
+    mobile.wealth = reader.read_number()?;
+
+    mobile.form = reader.read_flags()?;
+    mobile.parts = reader.read_flags()?;
+    mobile.size = reader.read_word()?;
+    mobile.material = reader.read_word()?;
+
+    // Flag removals and MOBprogram triggers run up to the next mobile
+    mobile.flag_removals.clear();
+    loop {
+        match reader.peek_letter()? {
+            '#' => break,
+            'F' => {
+                reader.read_letter()?;
+                let which = reader.read_word()?;
+                let flags = reader.read_flags()?;
+                mobile.flag_removals.push((which, flags));
+            }
+            // Triggers are read by programs::load_triggers
+            'M' => {
+                reader.read_letter()?;
+                let _trigger = reader.read_word()?;
+                let _program = reader.read_word()?;
+                let _phrase = reader.read_string()?;
+            }
+            other => {
+                reader.warn(Some(vnum), format!("Unknown mobile field '{}'", other));
+                return None;
+            }
+        }
+    }
+
+    Some(())
+}
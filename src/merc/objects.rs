@@ -0,0 +1,140 @@
+// Reading the ROM #OBJECTS section
+use super::{programs, reader::AreaReader};
+use crate::area_file::LoadError;
+use crate::{
+    objects::{ItemType, Object, ObjectAffect, ObjectFlagAffect},
+    room::ExtraDescription,
+    stats::Apply,
+};
+use std::str::FromStr;
+
+pub fn load_objects(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<Object> {
+    let mut objects = Vec::new();
+    let Some(mut reader) = AreaReader::section(buffer, "OBJECTS") else {
+        return objects;
+    };
+
+    loop {
+        if reader.read_letter() != Some('#') {
+            reader.warn(
+                None,
+                "Expected '#' while reading objects, giving up on the section",
+            );
+            break;
+        }
+
+        let vnum = match reader.read_number().map(u32::try_from) {
+            Some(Ok(0)) => break,
+            Some(Ok(vnum)) => vnum,
+            _ => {
+                reader.warn(None, "Bad object vnum, giving up on the section");
+                break;
+            }
+        };
+
+        match read_object(&mut reader, vnum) {
+            Some(object) => {
+                tracing::debug!(
+                    object_id = object.id,
+                    object_name = object.short_description,
+                    "Adding object template to world"
+                );
+                objects.push(object);
+            }
+            None => {
+                reader.warn(
+                    Some(vnum),
+                    "Failed to read object, giving up on the section",
+                );
+                break;
+            }
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    objects
+}
+
+fn read_object(reader: &mut AreaReader, vnum: u32) -> Option<Object> {
+    let keywords = reader.read_string()?;
+    let short_description = reader.read_string()?;
+    let long_description = reader.read_string()?;
+    let material = reader.read_string()?;
+
+    let item_type_name = reader.read_word()?;
+    let item_type = ItemType::from_str(&item_type_name).unwrap_or_else(|_| {
+        reader.warn(
+            Some(vnum),
+            format!("Unknown item type '{}'", item_type_name),
+        );
+        ItemType::Trash
+    });
+    let extra_flags = reader.read_flags()?;
+    let wear_flags = reader.read_flags()?;
+    let values = reader.read_line_words()?;
+
+    let level = u32::try_from(reader.read_number()?).ok()?;
+    let weight = i32::try_from(reader.read_number()?).ok()?;
+    let cost = i32::try_from(reader.read_number()?).ok()?;
+    // TODO: Condition, which we don't use yet
+    let condition = reader.read_word()?;
+
+    let mut affects = Vec::new();
+    let mut flag_affects = Vec::new();
+    let mut extra_descriptions = Vec::new();
+    let mut triggers = Vec::new();
+    loop {
+        match reader.peek_letter() {
+            Some('A') => {
+                reader.read_letter();
+                let location = reader.read_number()?;
+                let modifier = i32::try_from(reader.read_number()?).ok()?;
+                match Apply::from_rom(location) {
+                    Some(location) => affects.push(ObjectAffect { location, modifier }),
+                    None => reader.warn(Some(vnum), format!("Unknown apply location {}", location)),
+                }
+            }
+            Some('F') => {
+                reader.read_letter();
+                flag_affects.push(ObjectFlagAffect {
+                    target: reader.read_word()?,
+                    location: reader.read_number()?,
+                    modifier: reader.read_number()?,
+                    bitvector: reader.read_flags()?,
+                });
+            }
+            Some('E') => {
+                reader.read_letter();
+                extra_descriptions.push(ExtraDescription {
+                    keyword: reader.read_string()?,
+                    description: reader.read_string()?,
+                });
+            }
+            Some('O') => {
+                reader.read_letter();
+                programs::read_trigger(reader, vnum, &mut triggers)?;
+            }
+            _ => break,
+        }
+    }
+
+    Some(Object {
+        id: vnum,
+        keywords: keywords.split_whitespace().map(String::from).collect(),
+        short_description,
+        long_description,
+        material,
+        item_type,
+        extra_flags,
+        wear_flags,
+        values,
+        level,
+        weight,
+        cost,
+        condition,
+        affects,
+        flag_affects,
+        extra_descriptions,
+        triggers,
+    })
+}
@@ -0,0 +1,119 @@
+// Reading programs from the #MOBPROGS, #OBJPROGS and #ROOMPROGS sections, and the triggers that
+// mobiles, objects and rooms list for them
+use super::reader::AreaReader;
+use crate::area_file::LoadError;
+use crate::programs::{MobProgram, ProgramKind, ProgramTrigger, TriggerType};
+
+pub fn load_programs(
+    buffer: &str,
+    kind: ProgramKind,
+    warnings: &mut Vec<LoadError>,
+) -> Vec<MobProgram> {
+    let mut programs = Vec::new();
+    let Some(mut reader) = AreaReader::section(buffer, kind.section()) else {
+        return programs;
+    };
+
+    loop {
+        if reader.read_letter() != Some('#') {
+            reader.warn(
+                None,
+                "Expected '#' while reading programs, giving up on the section",
+            );
+            break;
+        }
+        let vnum = match reader.read_number().map(u32::try_from) {
+            Some(Ok(0)) => break,
+            Some(Ok(vnum)) => vnum,
+            _ => {
+                reader.warn(None, "Bad program vnum, giving up on the section");
+                break;
+            }
+        };
+        let Some(code) = reader.read_string() else {
+            reader.warn(Some(vnum), "Program is missing its terminating ~");
+            break;
+        };
+
+        tracing::debug!(program = vnum, "Adding {} program", kind);
+        programs.push(MobProgram { vnum, code });
+    }
+
+    warnings.extend(reader.into_warnings());
+    programs
+}
+
+// Returns the triggers for each mobile vnum that has some. In ROM these are the lines like
+// `M speech 3001 hello~` at the end of a mobile, which merc_parser skips over.
+pub fn load_triggers(buffer: &str) -> Vec<(u32, ProgramTrigger)> {
+    let mut result = Vec::new();
+    let Some(mut reader) = AreaReader::section(buffer, "MOBILES") else {
+        return result;
+    };
+
+    let mut vnum: Option<u32> = None;
+    while let Some(line) = reader.read_line() {
+        let line = line.trim();
+        if line == "#0" {
+            break;
+        }
+        if let Some(number) = line.strip_prefix('#') {
+            vnum = number.parse().ok();
+            continue;
+        }
+
+        // Only lines naming a trigger, so descriptions that happen to start with an M don't count
+        let mut words = line.splitn(4, char::is_whitespace);
+        let (Some("M"), Some(trigger), Some(program), Some(phrase)) =
+            (words.next(), words.next(), words.next(), words.next())
+        else {
+            continue;
+        };
+        let (Some(mobile_vnum), Ok(trigger), Ok(program)) =
+            (vnum, trigger.parse::<TriggerType>(), program.parse::<u32>())
+        else {
+            continue;
+        };
+
+        tracing::debug!(
+            mobile_id = mobile_vnum,
+            program,
+            "Adding {} trigger to mobile",
+            trigger
+        );
+        result.push((
+            mobile_vnum,
+            ProgramTrigger {
+                trigger,
+                program,
+                phrase: phrase.trim_end_matches('~').trim().to_string(),
+            },
+        ));
+    }
+
+    result
+}
+
+// The rest of an object's `O` line or a room's `R` line, eg `O wear 3001 100~`. Triggers that don't
+// make sense are skipped with a warning, and None means the line couldn't be read at all.
+pub fn read_trigger(
+    reader: &mut AreaReader,
+    vnum: u32,
+    triggers: &mut Vec<ProgramTrigger>,
+) -> Option<()> {
+    let name = reader.read_word()?;
+    let program = reader.read_number()?;
+    let phrase = reader.read_string()?;
+    match (name.parse::<TriggerType>(), u32::try_from(program)) {
+        (Ok(trigger), Ok(program)) => triggers.push(ProgramTrigger {
+            trigger,
+            program,
+            phrase: phrase.trim().to_string(),
+        }),
+        _ => reader.warn(
+            Some(vnum),
+            format!("Skipping {} trigger for program {}", name, program),
+        ),
+    }
+    Some(())
+}
@@ -0,0 +1,165 @@
+// A small cursor over the raw text of an area file, for reading the sections that merc_parser
+// doesn't handle yet. The methods mirror the fread_* helpers in ROM's db.c.
+use crate::area_file::LoadError;
+
+pub struct AreaReader<'a> {
+    input: &'a str,
+    pos: usize,
+    section: Option<String>,
+    // Where the last thing read started, for pointing at it in warnings
+    mark: usize,
+    warnings: Vec<LoadError>,
+}
+
+impl<'a> AreaReader<'a> {
+    // A reader over text that isn't split into sections, eg a header field or a whole file
+    pub fn new(input: &'a str) -> AreaReader<'a> {
+        AreaReader {
+            input,
+            pos: 0,
+            section: None,
+            mark: 0,
+            warnings: Vec::new(),
+        }
+    }
+
+    // Position a reader just after a section header (eg "#OBJECTS"), if the file has that section
+    pub fn section(buffer: &'a str, name: &str) -> Option<AreaReader<'a>> {
+        let header = format!("#{}", name);
+        let mut offset = 0;
+        for line in buffer.split_inclusive('\n') {
+            offset += line.len();
+            if line.trim_end() == header {
+                return Some(AreaReader {
+                    pos: offset,
+                    section: Some(header),
+                    mark: offset,
+                    ..AreaReader::new(buffer)
+                });
+            }
+        }
+        None
+    }
+
+    // An error pointing at the last thing read
+    pub fn error(&self, message: impl Into<String>) -> LoadError {
+        LoadError {
+            section: self.section.clone(),
+            ..LoadError::new(message).at(self.input, self.mark)
+        }
+    }
+
+    // Note something that was skipped or guessed at, without stopping
+    pub fn warn(&mut self, vnum: Option<u32>, message: impl Into<String>) {
+        let mut warning = self.error(message);
+        warning.vnum = vnum;
+        self.warnings.push(warning);
+    }
+
+    pub fn into_warnings(self) -> Vec<LoadError> {
+        self.warnings
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    // Look at the next non-whitespace character without consuming it
+    pub fn peek_letter(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    pub fn read_letter(&mut self) -> Option<char> {
+        let c = self.peek_letter()?;
+        self.mark = self.pos;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    // Read a whitespace-delimited word. As in ROM, a word can be quoted to include spaces.
+    pub fn read_word(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        self.mark = self.pos;
+        let rest = self.rest();
+        let (word, consumed) = match rest.chars().next()? {
+            quote @ ('\'' | '"') => {
+                let end = rest[1..].find(quote).map_or(rest.len(), |i| i + 1);
+                (&rest[1..end], (end + 1).min(rest.len()))
+            }
+            _ => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (&rest[..end], end)
+            }
+        };
+        self.pos += consumed;
+        Some(word.to_string())
+    }
+
+    pub fn read_number(&mut self) -> Option<i64> {
+        self.read_word()?.parse().ok()
+    }
+
+    // Read a flag field, which can be letters (A-Z then a-z), a plain number, or several of
+    // those joined with '|'
+    pub fn read_flags(&mut self) -> Option<u64> {
+        parse_flags(&self.read_word()?)
+    }
+
+    // Read a '~' terminated string
+    pub fn read_string(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        self.mark = self.pos;
+        let rest = self.rest();
+        let end = rest.find('~')?;
+        self.pos += end + 1;
+        Some(rest[..end].replace("\r", ""))
+    }
+
+    // Read up to the end of the current line
+    pub fn read_line(&mut self) -> Option<&'a str> {
+        self.mark = self.pos;
+        let rest = self.rest();
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest.find('\n').map_or(rest.len(), |i| i + 1);
+        self.pos += end;
+        Some(rest[..end].trim_end())
+    }
+
+    // Read the next non-empty line and split it into words
+    pub fn read_line_words(&mut self) -> Option<Vec<String>> {
+        self.skip_whitespace();
+        let line = self.read_line()?;
+        let mut reader = AreaReader::new(line);
+        let mut words = Vec::new();
+        while let Some(word) = reader.read_word() {
+            words.push(word);
+        }
+        Some(words)
+    }
+}
+
+pub fn parse_flags(field: &str) -> Option<u64> {
+    let mut flags = 0;
+    for part in field.split('|') {
+        if part.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+            flags |= part.parse::<i64>().ok()? as u64;
+        } else {
+            for c in part.chars() {
+                flags |= match c {
+                    'A'..='Z' => 1 << (c as u64 - 'A' as u64),
+                    'a'..='z' => 1 << (26 + c as u64 - 'a' as u64),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(flags)
+}
@@ -0,0 +1,83 @@
+// Reading the ROM #RESETS section
+use super::reader::AreaReader;
+use crate::area_file::LoadError;
+use crate::{equipment::WearLocation, reset::ResetCommand};
+
+pub fn load_resets(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<ResetCommand> {
+    let mut resets = Vec::new();
+    let Some(mut reader) = AreaReader::section(buffer, "RESETS") else {
+        return resets;
+    };
+
+    while let Some(line) = reader.read_line() {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some("S") => break,
+            // Blank lines and comments
+            None => continue,
+            Some(c) if c.starts_with('*') => continue,
+            Some(command) => command,
+        };
+
+        // Everything after the arguments is a comment, so stop at the first non-number
+        let args: Vec<i64> = words.map_while(|w| w.parse().ok()).collect();
+        // The first argument is the obsolete 'if' flag
+        let arg = |i: usize| args.get(i).copied().and_then(|a| u32::try_from(a).ok());
+
+        let reset = match command {
+            "M" => arg(1)
+                .zip(arg(3))
+                .map(|(mobile_id, room_id)| ResetCommand::LoadMobile {
+                    mobile_id,
+                    room_id,
+                    // Limits that are missing or negative mean there isn't one
+                    world_limit: arg(2),
+                    room_limit: arg(4),
+                }),
+            "O" => arg(1)
+                .zip(arg(3))
+                .map(|(object_id, room_id)| ResetCommand::LoadObject { object_id, room_id }),
+            "G" => arg(1).map(|object_id| ResetCommand::GiveObject { object_id }),
+            "E" => arg(1)
+                .zip(args.get(3).copied().and_then(WearLocation::from_rom))
+                .map(|(object_id, wear_location)| ResetCommand::EquipObject {
+                    object_id,
+                    wear_location,
+                }),
+            "P" => arg(1)
+                .zip(arg(3))
+                .map(|(object_id, container_id)| ResetCommand::PutObject {
+                    object_id,
+                    container_id,
+                    world_limit: arg(2),
+                    count: arg(4),
+                }),
+            "D" => arg(1)
+                .zip(arg(2).and_then(|d| u8::try_from(d).ok()))
+                .zip(arg(3).and_then(|s| u8::try_from(s).ok()))
+                .map(|((room_id, direction), state)| ResetCommand::SetDoor {
+                    room_id,
+                    direction,
+                    state,
+                }),
+            "R" => arg(1)
+                .zip(arg(2).and_then(|e| u8::try_from(e).ok()))
+                .map(|(room_id, exits)| ResetCommand::RandomizeExits { room_id, exits }),
+            _ => {
+                reader.warn(None, format!("Unimplemented reset '{}'", line));
+                continue;
+            }
+        };
+
+        match reset {
+            Some(r) => {
+                tracing::debug!("Adding reset to world {:?}", r);
+                resets.push(r);
+            }
+            None => reader.warn(None, format!("Malformed reset '{}'", line)),
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    resets
+}
@@ -0,0 +1,119 @@
+// Reading the parts of the ROM #ROOMS section that merc_parser doesn't give us
+use super::{programs, reader::AreaReader};
+use crate::area_file::LoadError;
+use crate::{
+    programs::ProgramTrigger,
+    room::{Door, ExtraDescription, Sector},
+};
+
+pub struct RoomProperties {
+    pub vnum: u32,
+    pub flags: u64,
+    pub sector: Sector,
+    // By ROM direction number
+    pub doors: Vec<(u8, Door)>,
+    pub extra_descriptions: Vec<ExtraDescription>,
+    pub heal_rate: i32,
+    pub mana_rate: i32,
+    pub clan: String,
+    pub owner: String,
+    pub triggers: Vec<ProgramTrigger>,
+}
+
+pub fn load_room_properties(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<RoomProperties> {
+    let mut result = Vec::new();
+    let Some(mut reader) = AreaReader::section(buffer, "ROOMS") else {
+        return result;
+    };
+
+    loop {
+        if reader.read_letter() != Some('#') {
+            reader.warn(
+                None,
+                "Expected '#' while reading rooms, giving up on the section",
+            );
+            break;
+        }
+        let vnum = match reader.read_number().map(u32::try_from) {
+            Some(Ok(0)) => break,
+            Some(Ok(vnum)) => vnum,
+            _ => {
+                reader.warn(None, "Bad room vnum, giving up on the section");
+                break;
+            }
+        };
+
+        match read_room(&mut reader, vnum) {
+            Some(properties) => result.push(properties),
+            None => {
+                reader.warn(Some(vnum), "Failed to read room, giving up on the section");
+                break;
+            }
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    result
+}
+
+fn read_room(reader: &mut AreaReader, vnum: u32) -> Option<RoomProperties> {
+    let _name = reader.read_string()?;
+    let _description = reader.read_string()?;
+    // The obsolete area number
+    let _area = reader.read_number()?;
+    let flags = reader.read_flags()?;
+    let sector_number = reader.read_number()?;
+    let sector = Sector::from_rom(sector_number).unwrap_or_else(|| {
+        reader.warn(Some(vnum), format!("Unknown sector type {}", sector_number));
+        Sector::Inside
+    });
+
+    let mut properties = RoomProperties {
+        vnum,
+        flags,
+        sector,
+        doors: Vec::new(),
+        extra_descriptions: Vec::new(),
+        heal_rate: 100,
+        mana_rate: 100,
+        clan: String::new(),
+        owner: String::new(),
+        triggers: Vec::new(),
+    };
+
+    // merc_parser gives us where the exits go, but not the rest of them
+    loop {
+        match reader.read_letter()? {
+            'S' => break,
+            'D' => {
+                let direction = reader.read_number()?;
+                let door = Door {
+                    description: reader.read_string()?,
+                    keyword: reader.read_string()?,
+                    locks: reader.read_number()?,
+                    key: reader.read_number()?,
+                };
+                let _to_room = reader.read_word()?;
+                match u8::try_from(direction) {
+                    Ok(direction) if direction < 6 => properties.doors.push((direction, door)),
+                    _ => reader.warn(Some(vnum), format!("Bad exit direction {}", direction)),
+                }
+            }
+            'E' => properties.extra_descriptions.push(ExtraDescription {
+                keyword: reader.read_string()?,
+                description: reader.read_string()?,
+            }),
+            'C' => properties.clan = reader.read_string()?,
+            'O' => properties.owner = reader.read_string()?,
+            'H' => properties.heal_rate = i32::try_from(reader.read_number()?).ok()?,
+            'M' => properties.mana_rate = i32::try_from(reader.read_number()?).ok()?,
+            'R' => programs::read_trigger(reader, vnum, &mut properties.triggers)?,
+            other => {
+                reader.warn(Some(vnum), format!("Unknown room field '{}'", other));
+                return None;
+            }
+        }
+    }
+
+    Some(properties)
+}
@@ -0,0 +1,55 @@
+// Reading the ROM #SHOPS section
+use super::reader::AreaReader;
+use crate::area_file::LoadError;
+use crate::{objects::ItemType, shops::Shop};
+
+pub fn load_shops(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<Shop> {
+    let mut shops = Vec::new();
+    let Some(mut reader) = AreaReader::section(buffer, "SHOPS") else {
+        return shops;
+    };
+
+    while let Some(line) = reader.read_line() {
+        // Everything after the numbers is a comment
+        let numbers: Vec<i64> = line
+            .split_whitespace()
+            .map_while(|w| w.parse().ok())
+            .collect();
+
+        match numbers.first() {
+            None => continue,
+            Some(0) => break,
+            Some(_) => match read_shop(&numbers) {
+                Some(shop) => {
+                    tracing::debug!(keeper = shop.keeper, "Adding shop to world");
+                    shops.push(shop);
+                }
+                None => reader.warn(None, format!("Malformed shop '{}'", line)),
+            },
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    shops
+}
+
+fn read_shop(numbers: &[i64]) -> Option<Shop> {
+    let [keeper, buy_types @ .., profit_buy, profit_sell, open_hour, close_hour] =
+        numbers.get(..10)?
+    else {
+        return None;
+    };
+
+    Some(Shop {
+        keeper: u32::try_from(*keeper).ok()?,
+        // Unused buy type slots are zero, which isn't an item type
+        buy_types: buy_types
+            .iter()
+            .filter_map(|t| ItemType::from_rom(*t))
+            .collect(),
+        profit_buy: i32::try_from(*profit_buy).ok()?,
+        profit_sell: i32::try_from(*profit_sell).ok()?,
+        open_hour: u32::try_from(*open_hour).ok()?,
+        close_hour: u32::try_from(*close_hour).ok()?,
+    })
+}
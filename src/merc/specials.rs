@@ -0,0 +1,45 @@
+// Reading the ROM #SPECIALS section
+use super::reader::AreaReader;
+use crate::area_file::LoadError;
+use crate::specials::{self, Special};
+
+// Returns the special procedure for each mobile vnum that has one
+pub fn load_specials(buffer: &str, warnings: &mut Vec<LoadError>) -> Vec<(u32, Special)> {
+    let mut result = Vec::new();
+    let Some(mut reader) = AreaReader::section(buffer, "SPECIALS") else {
+        return result;
+    };
+
+    while let Some(line) = reader.read_line() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("S") => break,
+            Some("M") => {}
+            // Blank lines and comments
+            None => continue,
+            Some(c) if c.starts_with('*') => continue,
+            Some(_) => {
+                reader.warn(None, format!("Unknown special line '{}'", line));
+                continue;
+            }
+        }
+
+        let vnum = words.next().and_then(|v| v.parse::<u32>().ok());
+        let name = words.next();
+        let (Some(vnum), Some(name)) = (vnum, name) else {
+            reader.warn(None, format!("Malformed special '{}'", line));
+            continue;
+        };
+
+        match specials::lookup(name) {
+            Some(special) => {
+                tracing::debug!(mobile_id = vnum, special = name, "Adding special to mobile");
+                result.push((vnum, special));
+            }
+            None => reader.warn(Some(vnum), format!("Unknown special procedure '{}'", name)),
+        }
+    }
+
+    warnings.extend(reader.into_warnings());
+    result
+}
@@ -0,0 +1,610 @@
+// Writing an area back out in the ROM format, so areas edited online can be saved and loaded
+// again. Sections are written in the order ROM's OLC saves them, with everything sorted by vnum.
+use crate::{
+    area::Area,
+    message::Direction,
+    programs::{ProgramKind, ProgramTrigger},
+    reset::ResetCommand,
+    room::Door,
+    world::World,
+};
+use std::io::{self, Write};
+use strum::IntoEnumIterator;
+
+pub fn write_area<W: Write>(area: &Area, world: &World, out: &mut W) -> io::Result<()> {
+    write_header(area, out)?;
+    write_mobiles(area, out)?;
+    write_objects(area, out)?;
+    write_rooms(area, out)?;
+    write_specials(area, out)?;
+    write_resets(area, world, out)?;
+    write_shops(area, out)?;
+    write_programs(area, out)?;
+    writeln!(out, "#$")
+}
+
+// The inverse of reader::parse_flags. ROM writes flags as letters, A-Z then a-z.
+fn flags(flags: u64) -> String {
+    if flags == 0 {
+        return String::from("0");
+    }
+    (0..52)
+        .filter(|bit| flags & (1 << bit) != 0)
+        .map(|bit| {
+            if bit < 26 {
+                (b'A' + bit as u8) as char
+            } else {
+                (b'a' + (bit - 26) as u8) as char
+            }
+        })
+        .collect()
+}
+
+// A string on a single line. A '~' in the middle would end it early.
+fn string(text: &str) -> String {
+    format!("{}~", text.replace('~', "-"))
+}
+
+// Text that runs over several lines, which ROM ends with a newline and puts the ~ on a line of its
+// own
+fn long_string(text: &str) -> String {
+    let text = text.trim_end();
+    if text.is_empty() {
+        String::from("~")
+    } else {
+        format!("{}\n~", text.replace('~', "-"))
+    }
+}
+
+// A word which has to be quoted if it has spaces in it, like the spell names in object values
+fn word(text: &str) -> String {
+    if text.is_empty() || text.contains(char::is_whitespace) {
+        format!("'{}'", text)
+    } else {
+        text.to_string()
+    }
+}
+
+fn limit(limit: Option<u32>) -> i64 {
+    limit.map_or(-1, i64::from)
+}
+
+fn sorted<T>(map: &std::collections::HashMap<u32, T>) -> Vec<(&u32, &T)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_key(|(vnum, _)| **vnum);
+    entries
+}
+
+fn write_header<W: Write>(area: &Area, out: &mut W) -> io::Result<()> {
+    let metadata = &area.metadata;
+    let filename = metadata
+        .file
+        .as_ref()
+        .and_then(|f| f.file_name())
+        .map_or(String::new(), |f| f.to_string_lossy().to_string());
+
+    // Nothing changes the header online, so an OLC header goes back out as it came in
+    if let Some(fields) = &metadata.areadata {
+        writeln!(out, "#AREADATA")?;
+        for (key, value) in fields {
+            writeln!(out, "{} {}", key, value)?;
+        }
+        writeln!(out, "End")?;
+        return writeln!(out);
+    }
+
+    let credits = metadata.credits.clone().unwrap_or_else(|| {
+        format!(
+            "{} {:<8}{}",
+            metadata.level_str(),
+            metadata.author,
+            metadata.name
+        )
+    });
+    writeln!(out, "#AREA")?;
+    writeln!(out, "{}", string(&filename))?;
+    writeln!(out, "{}", string(&metadata.name))?;
+    writeln!(out, "{}", string(&credits))?;
+    writeln!(out, "{} {}", metadata.vnums.0, metadata.vnums.1)?;
+    writeln!(out)
+}
+
+fn write_mobiles<W: Write>(area: &Area, out: &mut W) -> io::Result<()> {
+    writeln!(out, "#MOBILES")?;
+    for (vnum, mobile) in sorted(&area.mobiles) {
+        writeln!(out, "#{}", vnum)?;
+        writeln!(out, "{}", string(&mobile.keywords.join(" ")))?;
+        writeln!(out, "{}", string(&mobile.room_description))?;
+        writeln!(out, "{}", long_string(&mobile.long_description))?;
+        writeln!(out, "{}", long_string(&mobile.description))?;
+        writeln!(out, "{}", string(&mobile.race))?;
+        writeln!(
+            out,
+            "{} {} {} {}",
+            flags(mobile.act_flags),
+            flags(mobile.affected_by),
+            mobile.alignment,
+            mobile.group
+        )?;
+        writeln!(
+            out,
+            "{} {} {} {} {} {}",
+            mobile.level,
+            mobile.hitroll,
+            mobile.hit_dice,
+            mobile.mana_dice,
+            mobile.damage_dice,
+            mobile.damage_type
+        )?;
+        writeln!(
+            out,
+            "{} {} {} {}",
+            mobile.armor[0], mobile.armor[1], mobile.armor[2], mobile.armor[3]
+        )?;
+        writeln!(
+            out,
+            "{} {} {} {}",
+            flags(mobile.offense_flags),
+            flags(mobile.immunity_flags),
+            flags(mobile.resistance_flags),
+            flags(mobile.vulnerability_flags)
+        )?;
+        writeln!(
+            out,
+            "{} {} {} {}",
+            mobile.start_position, mobile.default_position, mobile.sex, mobile.wealth
+        )?;
+        writeln!(
+            out,
+            "{} {} {} {}",
+            flags(mobile.form),
+            flags(mobile.parts),
+            mobile.size,
+            mobile.material
+        )?;
+        for (which, removed) in &mobile.flag_removals {
+            writeln!(out, "F {} {}", which, flags(*removed))?;
+        }
+        write_triggers('M', &mobile.triggers, out)?;
+    }
+    writeln!(out, "#0")?;
+    writeln!(out)
+}
+
+fn write_objects<W: Write>(area: &Area, out: &mut W) -> io::Result<()> {
+    writeln!(out, "#OBJECTS")?;
+    for (vnum, object) in sorted(&area.objects) {
+        writeln!(out, "#{}", vnum)?;
+        writeln!(out, "{}", string(&object.keywords.join(" ")))?;
+        writeln!(out, "{}", string(&object.short_description))?;
+        writeln!(out, "{}", string(&object.long_description))?;
+        writeln!(out, "{}", string(&object.material))?;
+        writeln!(
+            out,
+            "{} {} {}",
+            object.item_type,
+            flags(object.extra_flags),
+            flags(object.wear_flags)
+        )?;
+        let values: Vec<String> = object.values.iter().map(|v| word(v)).collect();
+        writeln!(out, "{}", values.join(" "))?;
+        writeln!(
+            out,
+            "{} {} {} {}",
+            object.level, object.weight, object.cost, object.condition
+        )?;
+        for affect in &object.affects {
+            writeln!(out, "A\n{} {}", affect.location.to_rom(), affect.modifier)?;
+        }
+        for affect in &object.flag_affects {
+            writeln!(
+                out,
+                "F\n{} {} {} {}",
+                affect.target,
+                affect.location,
+                affect.modifier,
+                flags(affect.bitvector)
+            )?;
+        }
+        for extra in &object.extra_descriptions {
+            writeln!(out, "E")?;
+            writeln!(out, "{}", string(&extra.keyword))?;
+            writeln!(out, "{}", long_string(&extra.description))?;
+        }
+        write_triggers('O', &object.triggers, out)?;
+    }
+    writeln!(out, "#0")?;
+    writeln!(out)
+}
+
+fn write_rooms<W: Write>(area: &Area, out: &mut W) -> io::Result<()> {
+    writeln!(out, "#ROOMS")?;
+    for (vnum, room) in sorted(&area.rooms) {
+        writeln!(out, "#{}", vnum)?;
+        writeln!(out, "{}", string(&room.name))?;
+        writeln!(out, "{}", long_string(&room.description))?;
+        writeln!(out, "0 {} {}", flags(room.flags), room.sector as i64)?;
+
+        for direction in Direction::iter() {
+            let name = direction.to_string();
+            let Some(destination) = room.get_exit(&name) else {
+                continue;
+            };
+            let door = room.doors.get(&name).cloned().unwrap_or_else(Door::default);
+            writeln!(out, "D{}", direction.number())?;
+            writeln!(out, "{}", long_string(&door.description))?;
+            writeln!(out, "{}", string(&door.keyword))?;
+            writeln!(out, "{} {} {}", door.locks, door.key, destination)?;
+        }
+        for extra in &room.extra_descriptions {
+            writeln!(out, "E")?;
+            writeln!(out, "{}", string(&extra.keyword))?;
+            writeln!(out, "{}", long_string(&extra.description))?;
+        }
+        if room.heal_rate != 100 || room.mana_rate != 100 {
+            writeln!(out, "H {} M {}", room.heal_rate, room.mana_rate)?;
+        }
+        if !room.clan.is_empty() {
+            writeln!(out, "C {}", string(&room.clan))?;
+        }
+        if !room.owner.is_empty() {
+            writeln!(out, "O {}", string(&room.owner))?;
+        }
+        write_triggers('R', &room.triggers, out)?;
+        writeln!(out, "S")?;
+    }
+    writeln!(out, "#0")?;
+    writeln!(out)
+}
+
+fn write_specials<W: Write>(area: &Area, out: &mut W) -> io::Result<()> {
+    let specials: Vec<_> = sorted(&area.mobiles)
+        .into_iter()
+        .filter_map(|(vnum, m)| m.special.as_ref().map(|s| (vnum, s.name, m)))
+        .collect();
+    if specials.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "#SPECIALS")?;
+    for (vnum, name, mobile) in specials {
+        writeln!(out, "M {} {}\t* {}", vnum, name, mobile.room_description)?;
+    }
+    writeln!(out, "S")?;
+    writeln!(out)
+}
+
+fn write_resets<W: Write>(area: &Area, world: &World, out: &mut W) -> io::Result<()> {
+    // Comments saying what each reset is, like ROM's OLC writes
+    let mobile_name = |vnum| {
+        world
+            .get_mobile_template(vnum)
+            .map_or("", |m| m.room_description.as_str())
+    };
+    let object_name = |vnum| {
+        world
+            .get_object_template(vnum)
+            .map_or("", |o| o.short_description.as_str())
+    };
+
+    writeln!(out, "#RESETS")?;
+    for reset in &area.resets {
+        match *reset {
+            ResetCommand::LoadMobile {
+                mobile_id,
+                room_id,
+                world_limit,
+                room_limit,
+            } => writeln!(
+                out,
+                "M 0 {} {} {} {}\t* {}",
+                mobile_id,
+                limit(world_limit),
+                room_id,
+                limit(room_limit),
+                mobile_name(mobile_id)
+            )?,
+            ResetCommand::LoadObject { object_id, room_id } => writeln!(
+                out,
+                "O 0 {} 0 {}\t* {}",
+                object_id,
+                room_id,
+                object_name(object_id)
+            )?,
+            ResetCommand::GiveObject { object_id } => {
+                writeln!(out, "G 1 {} 0\t*   {}", object_id, object_name(object_id))?
+            }
+            ResetCommand::EquipObject {
+                object_id,
+                wear_location,
+            } => writeln!(
+                out,
+                "E 1 {} 0 {}\t*   {}",
+                object_id,
+                wear_location.to_rom(),
+                object_name(object_id)
+            )?,
+            ResetCommand::PutObject {
+                object_id,
+                container_id,
+                world_limit,
+                count,
+            } => writeln!(
+                out,
+                "P 1 {} {} {} {}\t*   {}",
+                object_id,
+                limit(world_limit),
+                container_id,
+                limit(count),
+                object_name(object_id)
+            )?,
+            ResetCommand::SetDoor {
+                room_id,
+                direction,
+                state,
+            } => writeln!(out, "D 0 {} {} {}", room_id, direction, state)?,
+            ResetCommand::RandomizeExits { room_id, exits } => {
+                writeln!(out, "R 0 {} {}", room_id, exits)?
+            }
+        }
+    }
+    writeln!(out, "S")?;
+    writeln!(out)
+}
+
+fn write_shops<W: Write>(area: &Area, out: &mut W) -> io::Result<()> {
+    let shops: Vec<_> = sorted(&area.mobiles)
+        .into_iter()
+        .filter_map(|(_, m)| m.shop.as_ref().map(|s| (s, m)))
+        .collect();
+    if shops.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "#SHOPS")?;
+    for (shop, mobile) in shops {
+        // Always five item types, with zero for the unused ones
+        let mut buy_types: Vec<i64> = shop.buy_types.iter().map(|t| t.to_rom()).collect();
+        buy_types.resize(5, 0);
+        let buy_types: Vec<String> = buy_types.iter().map(|t| t.to_string()).collect();
+        writeln!(
+            out,
+            "{} {} {} {} {} {}\t* {}",
+            shop.keeper,
+            buy_types.join(" "),
+            shop.profit_buy,
+            shop.profit_sell,
+            shop.open_hour,
+            shop.close_hour,
+            mobile.room_description
+        )?;
+    }
+    writeln!(out, "0")?;
+    writeln!(out)
+}
+
+// The M, O or R lines listing the programs a mobile, object or room runs
+fn write_triggers<W: Write>(
+    letter: char,
+    triggers: &[ProgramTrigger],
+    out: &mut W,
+) -> io::Result<()> {
+    for trigger in triggers {
+        writeln!(
+            out,
+            "{} {} {} {}",
+            letter,
+            trigger.trigger,
+            trigger.program,
+            string(&trigger.phrase)
+        )?;
+    }
+    Ok(())
+}
+
+fn write_programs<W: Write>(area: &Area, out: &mut W) -> io::Result<()> {
+    for kind in ProgramKind::iter() {
+        let programs = area.programs_of(kind);
+        if programs.is_empty() {
+            continue;
+        }
+
+        writeln!(out, "#{}", kind.section())?;
+        for program in programs {
+            writeln!(out, "#{}", program.vnum)?;
+            writeln!(out, "{}", long_string(&program.code))?;
+        }
+        writeln!(out, "#0")?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merc::load_area_file;
+    use serde::Serialize;
+
+    const AREA: &str = "#AREA
+test.are~
+Test Area~
+{ 5 10} Tester  Test Area~
+3000 3099
+
+#MOBILES
+#3000
+guard~
+a guard~
+A guard stands here.
+~
+He looks bored.
+~
+human~
+AB 0 0 0
+5 0 2d8+10 1d1+0 1d4+0 punch
+0 0 0 0
+0 0 0 0
+stand stand male 0
+0 0 medium unknown
+M speech 3000 hello~
+#0
+
+#OBJECTS
+#3010
+sword~
+a sword~
+A sword lies here.~
+steel~
+weapon 0 AN
+sword 2 6 slash 0
+1 10 100 P
+O get 3010 100~
+#0
+
+#ROOMS
+#3001
+The Hall~
+A long hall.
+~
+0 0 0
+D1
+~
+~
+0 0 3002
+R greet 3001 100~
+S
+#3002
+The Yard~
+An open yard.
+~
+0 0 2
+D3
+~
+~
+0 0 3001
+S
+#0
+
+#SPECIALS
+M 3000 spec_fido
+S
+
+#RESETS
+M 0 3000 1 3001 1
+E 1 3010 0 16
+O 0 3010 0 3002
+S
+
+#SHOPS
+3000 5 0 0 0 0 120 80 8 20
+0
+
+#MOBPROGS
+#3000
+say Hello $n.
+~
+#0
+
+#OBJPROGS
+#3010
+obj echo $I glints.
+~
+#0
+
+#ROOMPROGS
+#3001
+room echo Welcome, $n.
+~
+#0
+
+#$
+";
+
+    // Everything in a map, sorted so two areas can be compared
+    fn values<T: Serialize>(map: &std::collections::HashMap<u32, T>) -> Vec<serde_json::Value> {
+        sorted(map)
+            .into_iter()
+            .map(|(_, value)| serde_json::to_value(value).unwrap())
+            .collect()
+    }
+
+    fn write(area: &Area) -> String {
+        let mut written = Vec::new();
+        write_area(area, &World::new(), &mut written).unwrap();
+        String::from_utf8(written).unwrap()
+    }
+
+    fn programs(area: &Area) -> Vec<(ProgramKind, u32, String)> {
+        ProgramKind::iter()
+            .flat_map(|kind| {
+                area.programs_of(kind)
+                    .into_iter()
+                    .map(move |p| (kind, p.vnum, p.code.clone()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn written_areas_load_the_same() {
+        let mut warnings = Vec::new();
+        let area = load_area_file(AREA.as_bytes(), &mut warnings).unwrap();
+        assert_eq!(area.rooms.len(), 2);
+        assert_eq!(area.mobiles.len(), 1);
+        assert_eq!(area.objects.len(), 1);
+        assert_eq!(area.resets.len(), 3);
+
+        let written = write(&area);
+        assert!(written.contains("{ 5 10} Tester  Test Area~"));
+        let reloaded = load_area_file(written.as_bytes(), &mut warnings).unwrap();
+
+        // Anything lost on the way would be missing when the reloaded area is written out again
+        assert_eq!(written, write(&reloaded));
+        // Rooms include their exits and triggers, and mobiles their shops and specials
+        assert_eq!(values(&area.rooms), values(&reloaded.rooms));
+        assert_eq!(values(&area.mobiles), values(&reloaded.mobiles));
+        assert_eq!(values(&area.objects), values(&reloaded.objects));
+        assert_eq!(
+            format!("{:?}", area.resets),
+            format!("{:?}", reloaded.resets)
+        );
+        assert_eq!(programs(&area), programs(&reloaded));
+        assert_eq!(area.metadata.vnums, reloaded.metadata.vnums);
+        assert_eq!(area.metadata.levels, reloaded.metadata.levels);
+
+        let mobile = &reloaded.mobiles[&3000];
+        assert!(mobile.shop.is_some());
+        assert_eq!(mobile.special.map(|s| s.name), Some("spec_fido"));
+        assert_eq!(mobile.triggers.len(), 1);
+        assert_eq!(reloaded.objects[&3010].triggers.len(), 1);
+        assert_eq!(reloaded.rooms[&3001].triggers.len(), 1);
+        assert_eq!(reloaded.rooms[&3001].get_exit("east").copied(), Some(3002));
+        assert_eq!(programs(&reloaded).len(), 3);
+    }
+
+    #[test]
+    fn areadata_headers_are_kept() {
+        let header = crate::merc::header::load_header(
+            "#AREADATA\nName Test Area~\nBuilders None~\nVNUMs 3000 3099\nCredits { 5 10} Tester  Test Area~\nSecurity 9\nEnd\n",
+        );
+        assert_eq!(header.vnums, Some((3000, 3099)));
+        assert_eq!(header.levels, Some((5, 10)));
+
+        let area = Area::new(crate::area::AreaMetadata {
+            name: String::from("Test Area"),
+            author: String::from("Tester"),
+            vnums: (3000, 3099),
+            levels: header.levels,
+            file: None,
+            format: None,
+            credits: header.credits,
+            areadata: header.areadata,
+        });
+        let mut written = Vec::new();
+        write_header(&area, &mut written).unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "#AREADATA\nName Test Area~\nBuilders None~\nVNUMs 3000 3099\nCredits { 5 10} Tester  Test Area~\nSecurity 9\nEnd\n\n"
+        );
+    }
+}
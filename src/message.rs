@@ -1,11 +1,15 @@
-use crate::{connection::Prompt, player::Player};
+use crate::{connection::Prompt, player::Player, position::Position};
+use std::path::PathBuf;
+use strum::IntoEnumIterator;
 
 pub enum ConnectionMessage {
     // Control messages for adding and removing players
-    AddPlayer(Player),
+    AddPlayer(Box<Player>),
     RemovePlayer(u32),
     // A message from a player that we need to handle in the game logic
     PlayerCommand(u32, String),
+    // An area file changed on disk and should be loaded again
+    ReloadArea(PathBuf),
 }
 
 #[derive(Debug)]
@@ -56,6 +60,46 @@ impl RawCommand {
                         // well
                         Some(PlayerMessage::Look)
                     }
+                    "get" | "take" => rest_argument(parts).map(PlayerMessage::Get),
+                    "drop" => rest_argument(parts).map(PlayerMessage::Drop),
+                    "give" => rest_argument(parts).map(PlayerMessage::Give),
+                    "i" | "inv" | "inventory" => Some(PlayerMessage::Inventory),
+                    "wear" => rest_argument(parts).map(PlayerMessage::Wear),
+                    "wield" => rest_argument(parts).map(PlayerMessage::Wield),
+                    "hold" => rest_argument(parts).map(PlayerMessage::Hold),
+                    "rem" | "remove" => rest_argument(parts).map(PlayerMessage::Remove),
+                    "eq" | "equipment" => Some(PlayerMessage::Equipment),
+                    "sc" | "score" => Some(PlayerMessage::Score),
+                    "aff" | "affects" => Some(PlayerMessage::Affects),
+                    "map" => Some(PlayerMessage::Map(rest_argument(parts))),
+                    "track" => Some(PlayerMessage::Track(rest_argument(parts))),
+                    "where" => Some(PlayerMessage::Where(rest_argument(parts))),
+                    "path" => Some(PlayerMessage::Path(rest_argument(parts))),
+                    "sleep" => Some(PlayerMessage::Position(Position::Sleeping)),
+                    "rest" => Some(PlayerMessage::Position(Position::Resting)),
+                    "sit" => Some(PlayerMessage::Position(Position::Sitting)),
+                    "stand" => Some(PlayerMessage::Position(Position::Standing)),
+                    "wake" => Some(PlayerMessage::Wake(rest_argument(parts))),
+                    "fol" | "follow" => Some(PlayerMessage::Follow(rest_argument(parts))),
+                    "group" => Some(PlayerMessage::Group(rest_argument(parts))),
+                    "ungroup" => Some(PlayerMessage::Ungroup),
+                    "gt" | "gtell" => Some(PlayerMessage::Gtell(rest_argument(parts))),
+                    "cast" => Some(PlayerMessage::Cast(rest_argument(parts))),
+                    "prac" | "practice" => Some(PlayerMessage::Practice(rest_argument(parts))),
+                    "alias" => Some(PlayerMessage::Alias(rest_argument(parts))),
+                    "unalias" => rest_argument(parts).map(PlayerMessage::Unalias),
+                    "areas" => Some(PlayerMessage::Areas(rest_argument(parts))),
+                    "redit" => Some(PlayerMessage::Redit(rest_argument(parts))),
+                    "medit" => Some(PlayerMessage::Medit(rest_argument(parts))),
+                    "oedit" => Some(PlayerMessage::Oedit(rest_argument(parts))),
+                    "resets" => Some(PlayerMessage::Resets(rest_argument(parts))),
+                    "asave" => Some(PlayerMessage::Asave(rest_argument(parts))),
+                    "areload" => Some(PlayerMessage::Areload(rest_argument(parts))),
+                    "graph" => Some(PlayerMessage::Graph(rest_argument(parts))),
+                    "list" => Some(PlayerMessage::List),
+                    "buy" => rest_argument(parts).map(PlayerMessage::Buy),
+                    "sell" => rest_argument(parts).map(PlayerMessage::Sell),
+                    "value" => rest_argument(parts).map(PlayerMessage::Value),
                     "n" | "north" => Some(PlayerMessage::Move(Direction::North)),
                     "s" | "south" => Some(PlayerMessage::Move(Direction::South)),
                     "e" | "east" => Some(PlayerMessage::Move(Direction::East)),
@@ -78,8 +122,19 @@ impl RawCommand {
     }
 }
 
+// Collect the rest of the command as a single argument, if there is one
+fn rest_argument<'a>(parts: impl Iterator<Item = &'a str>) -> Option<String> {
+    let rest = parts.collect::<Vec<_>>().join(" ");
+    if rest.is_empty() {
+        //TODO: Should this alert somehow?
+        None
+    } else {
+        Some(rest)
+    }
+}
+
 // Room exits that we explicitly check for
-#[derive(Debug, strum_macros::Display, Clone, Copy)]
+#[derive(Debug, strum_macros::Display, strum_macros::EnumIter, Clone, Copy)]
 #[strum(serialize_all = "lowercase")]
 pub enum Direction {
     North,
@@ -90,6 +145,48 @@ pub enum Direction {
     Down,
 }
 
+impl Direction {
+    // Parse a direction the way a player would type it, eg "n" or "north"
+    pub fn from_word(word: &str) -> Option<Direction> {
+        match word.to_lowercase().as_str() {
+            "n" | "north" => Some(Direction::North),
+            "s" | "south" => Some(Direction::South),
+            "e" | "east" => Some(Direction::East),
+            "w" | "west" => Some(Direction::West),
+            "u" | "up" => Some(Direction::Up),
+            "d" | "down" => Some(Direction::Down),
+            _ => None,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+
+    // The number area files use for this direction
+    pub fn number(self) -> u8 {
+        match self {
+            Direction::North => 0,
+            Direction::East => 1,
+            Direction::South => 2,
+            Direction::West => 3,
+            Direction::Up => 4,
+            Direction::Down => 5,
+        }
+    }
+
+    pub fn from_number(number: u8) -> Option<Direction> {
+        Direction::iter().find(|d| d.number() == number)
+    }
+}
+
 // Messages that connections can send to the game loop
 pub enum PlayerMessage {
     // Global Chat
@@ -102,10 +199,110 @@ pub enum PlayerMessage {
     Contextual(String, String),
     // Predefined Movements
     Move(Direction),
+    // Objects
+    Get(String),
+    Drop(String),
+    Give(String),
+    Inventory,
+    // Equipment
+    Wear(String),
+    Wield(String),
+    Hold(String),
+    Remove(String),
+    Equipment,
+    Score,
+    Affects,
+    // A map of the rooms nearby, as far away as the radius given
+    Map(Option<String>),
+    // Finding the way to someone or somewhere
+    Track(Option<String>),
+    Where(Option<String>),
+    Path(Option<String>),
+    // Sleeping, resting, sitting and standing, and waking someone up
+    Position(Position),
+    Wake(Option<String>),
+    // Following other players and grouping up with them
+    Follow(Option<String>),
+    Group(Option<String>),
+    Ungroup,
+    Gtell(Option<String>),
+    // Skills and spells
+    Cast(Option<String>),
+    Practice(Option<String>),
+    // Words that stand for other commands
+    Alias(Option<String>),
+    Unalias(String),
+    // Area list, or details of a single area
+    Areas(Option<String>),
+    // Building
+    Redit(Option<String>),
+    Medit(Option<String>),
+    Oedit(Option<String>),
+    Resets(Option<String>),
+    Asave(Option<String>),
+    Areload(Option<String>),
+    Graph(Option<String>),
+    // Shops
+    List,
+    Buy(String),
+    Sell(String),
+    Value(String),
     // DEBUG STUFF
     Mobiles,
 }
 
+impl PlayerMessage {
+    // How awake a player has to be to do this, like the positions in ROM's command table
+    pub fn min_position(&self) -> Position {
+        match self {
+            PlayerMessage::Move(_) | PlayerMessage::Contextual(_, _) | PlayerMessage::Track(_) => {
+                Position::Standing
+            }
+            PlayerMessage::Say(_)
+            | PlayerMessage::Look
+            | PlayerMessage::Map(_)
+            | PlayerMessage::Where(_)
+            | PlayerMessage::Get(_)
+            | PlayerMessage::Drop(_)
+            | PlayerMessage::Give(_)
+            | PlayerMessage::Wear(_)
+            | PlayerMessage::Wield(_)
+            | PlayerMessage::Hold(_)
+            | PlayerMessage::Remove(_)
+            | PlayerMessage::List
+            | PlayerMessage::Buy(_)
+            | PlayerMessage::Sell(_)
+            | PlayerMessage::Value(_)
+            | PlayerMessage::Follow(_) => Position::Resting,
+            PlayerMessage::Cast(_) => Position::Fighting,
+            PlayerMessage::Gossip(_)
+            | PlayerMessage::Group(_)
+            | PlayerMessage::Ungroup
+            | PlayerMessage::Gtell(_)
+            | PlayerMessage::Position(_)
+            | PlayerMessage::Wake(_)
+            | PlayerMessage::Practice(_) => Position::Sleeping,
+            // Things about the player themselves and building, which work in any state
+            PlayerMessage::Inventory
+            | PlayerMessage::Equipment
+            | PlayerMessage::Score
+            | PlayerMessage::Affects
+            | PlayerMessage::Path(_)
+            | PlayerMessage::Alias(_)
+            | PlayerMessage::Unalias(_)
+            | PlayerMessage::Areas(_)
+            | PlayerMessage::Redit(_)
+            | PlayerMessage::Medit(_)
+            | PlayerMessage::Oedit(_)
+            | PlayerMessage::Resets(_)
+            | PlayerMessage::Asave(_)
+            | PlayerMessage::Areload(_)
+            | PlayerMessage::Graph(_)
+            | PlayerMessage::Mobiles => Position::Dead,
+        }
+    }
+}
+
 // Messages that the game loop can send to connections
 #[derive(Debug, Clone)]
 pub enum GameMessage {
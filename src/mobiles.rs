@@ -1,13 +1,272 @@
-#[derive(Clone)]
+use crate::{
+    affects::{self, Affect},
+    dice::Dice,
+    equipment::Equipment,
+    objects::ObjectInstance,
+    position::Position,
+    programs::ProgramTrigger,
+    shops::Shop,
+    specials::Special,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Mobile {
     // TODO: Type aliases for all these IDs...
     pub id: u32,
     pub keywords: Vec<String>,
+    // The mobile's short description (eg "the baker")
+    #[serde(rename = "short_description")]
     pub room_description: String,
+    // The line shown for the mobile standing in a room
+    pub long_description: String,
+    // What players see when they look at the mobile
+    pub description: String,
+    pub level: u32,
+    pub alignment: i32,
+    pub hitroll: i32,
+    pub hit_dice: Dice,
+    pub mana_dice: Dice,
+    pub damage_dice: Dice,
+    pub act_flags: u64,
+    pub affected_by: u64,
+    // The rest of a ROM mobile, which nothing uses yet but which is kept so the area can be saved
+    pub race: String,
+    pub group: i64,
+    pub damage_type: String,
+    // Armor class against pierce, bash, slash and magic
+    pub armor: [i64; 4],
+    pub offense_flags: u64,
+    pub immunity_flags: u64,
+    pub resistance_flags: u64,
+    pub vulnerability_flags: u64,
+    pub start_position: String,
+    pub default_position: String,
+    pub sex: String,
+    pub wealth: i64,
+    pub form: u64,
+    pub parts: u64,
+    pub size: String,
+    pub material: String,
+    // Flags to take away from what the race gives, as the kind of flag and the flags
+    pub flag_removals: Vec<(String, u64)>,
+    // Set if this mobile runs a shop
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shop: Option<Shop>,
+    // Set if this mobile has a special procedure run on the mobile pulse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub special: Option<Special>,
+    // Events that run MOBprograms
+    pub triggers: Vec<ProgramTrigger>,
+}
+
+impl Default for Mobile {
+    fn default() -> Self {
+        Mobile::new(0)
+    }
+}
+
+impl Mobile {
+    // A blank mobile, for builders to fill in
+    pub fn new(id: u32) -> Self {
+        Mobile {
+            id,
+            keywords: vec![String::from("mobile")],
+            room_description: String::from("a new mobile"),
+            long_description: String::from("A new mobile is standing here."),
+            description: String::new(),
+            level: 1,
+            alignment: 0,
+            hitroll: 0,
+            hit_dice: Dice::default(),
+            mana_dice: Dice::default(),
+            damage_dice: Dice::default(),
+            act_flags: ActFlag::IsNpc.bit(),
+            affected_by: 0,
+            race: String::from("human"),
+            group: 0,
+            damage_type: String::from("punch"),
+            armor: [0; 4],
+            offense_flags: 0,
+            immunity_flags: 0,
+            resistance_flags: 0,
+            vulnerability_flags: 0,
+            start_position: String::from("stand"),
+            default_position: String::from("stand"),
+            sex: String::from("none"),
+            wealth: 0,
+            form: 0,
+            parts: 0,
+            size: String::from("medium"),
+            material: String::from("unknown"),
+            flag_removals: Vec::new(),
+            shop: None,
+            special: None,
+            triggers: Vec::new(),
+        }
+    }
+}
+
+// How a mobile behaves, using the bit positions of the ACT_* flags in ROM
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum ActFlag {
+    IsNpc = 0,
+    // Never wanders
+    Sentinel = 1,
+    Scavenger = 2,
+    Aggressive = 5,
+    StayArea = 6,
+    Wimpy = 7,
+    Pet = 8,
+    Train = 9,
+    Practice = 10,
+    Undead = 14,
+    Cleric = 16,
+    Mage = 17,
+    Thief = 18,
+    Warrior = 19,
+    NoAlign = 20,
+    NoPurge = 21,
+    Outdoors = 22,
+    Indoors = 24,
+    IsHealer = 26,
+    Gain = 27,
+    UpdateAlways = 28,
+    IsChanger = 29,
+}
+
+impl ActFlag {
+    pub fn bit(self) -> u64 {
+        1 << (self as u64)
+    }
+}
+
+// Things a character is permanently affected by, using the bit positions of the AFF_* flags in ROM
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum AffectFlag {
+    Blind = 0,
+    Invisible = 1,
+    DetectEvil = 2,
+    DetectInvis = 3,
+    DetectMagic = 4,
+    DetectHidden = 5,
+    DetectGood = 6,
+    Sanctuary = 7,
+    FaerieFire = 8,
+    Infrared = 9,
+    Curse = 10,
+    Poison = 12,
+    ProtectEvil = 13,
+    ProtectGood = 14,
+    Sneak = 15,
+    Hide = 16,
+    Sleep = 17,
+    Charm = 18,
+    Flying = 19,
+    PassDoor = 20,
+    Haste = 21,
+    Calm = 22,
+    Plague = 23,
+    Weaken = 24,
+    DarkVision = 25,
+    Berserk = 26,
+    Swim = 27,
+    Regeneration = 28,
+    Slow = 29,
+}
+
+impl AffectFlag {
+    pub fn bit(self) -> u64 {
+        1 << (self as u64)
+    }
 }
 
 pub struct MobileInstance {
     pub id: u32,
     pub template: Mobile,
     pub current_room: u32,
+    pub inventory: Vec<ObjectInstance>,
+    pub equipment: Equipment,
+    pub gold: u32,
+    pub silver: u32,
+    // The player it's following the trail of, if any
+    pub hunting: Option<u32>,
+    pub position: Position,
+    // Rolled from the template's dice when the mobile is created
+    pub hit: i32,
+    pub max_hit: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    pub movement: i32,
+    pub max_move: i32,
+    pub affects: Vec<Affect>,
+}
+
+impl MobileInstance {
+    // Where the mobile is usually found, and goes back to. Anything unrecognised is standing.
+    pub fn default_position(&self) -> Position {
+        Position::from_word(&self.template.default_position).unwrap_or_default()
+    }
+
+    // The AffectFlag bits the area gives the mobile along with what its affects give it
+    pub fn affected_by(&self) -> u64 {
+        self.template.affected_by | affects::bits(&self.affects)
+    }
+
+    pub fn is_affected(&self, flag: AffectFlag) -> bool {
+        self.affected_by() & flag.bit() != 0
+    }
+
+    // All the mobile's money in silver
+    pub fn wealth(&self) -> u32 {
+        self.silver + self.gold * 100
+    }
+
+    // Pay for something in silver, the same way players do
+    pub fn deduct_cost(&mut self, cost: u32) {
+        if self.silver >= cost {
+            self.silver -= cost;
+        } else {
+            let gold_needed = (cost - self.silver).div_ceil(100).min(self.gold);
+            self.gold -= gold_needed;
+            self.silver = (self.silver + gold_needed * 100).saturating_sub(cost);
+        }
+    }
+
+    pub fn add_money(&mut self, amount: u32) {
+        self.gold += amount / 100;
+        self.silver += amount % 100;
+    }
+
+    // Whether a name someone typed refers to this mobile
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        !name.is_empty()
+            && self
+                .template
+                .keywords
+                .iter()
+                .any(|k| k.to_lowercase().starts_with(&name))
+    }
 }
@@ -0,0 +1,282 @@
+use crate::{programs::ProgramTrigger, room::ExtraDescription, stats::Apply};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Object {
+    pub id: u32,
+    pub keywords: Vec<String>,
+    // Shown when the object is in an inventory or being used (eg "a long sword")
+    pub short_description: String,
+    // Shown when the object is lying on the floor of a room
+    pub long_description: String,
+    pub material: String,
+    pub item_type: ItemType,
+    pub extra_flags: u64,
+    pub wear_flags: u64,
+    // NOTE: What these mean depends on the item type (weapon dice, armor class, spells, etc.) so
+    // for now we just hold onto them as they appeared in the area file
+    pub values: Vec<String>,
+    pub level: u32,
+    pub weight: i32,
+    pub cost: i32,
+    // How worn the object is, as a ROM condition letter (eg "P" for perfect) or a percentage
+    pub condition: String,
+    pub affects: Vec<ObjectAffect>,
+    pub flag_affects: Vec<ObjectFlagAffect>,
+    pub extra_descriptions: Vec<ExtraDescription>,
+    // Events that run object programs
+    pub triggers: Vec<ProgramTrigger>,
+}
+
+impl Default for Object {
+    fn default() -> Self {
+        Object::new(0)
+    }
+}
+
+impl Object {
+    // A blank object, for builders to fill in
+    pub fn new(id: u32) -> Self {
+        Object {
+            id,
+            keywords: vec![String::from("object")],
+            short_description: String::from("a new object"),
+            long_description: String::from("A new object is lying here."),
+            material: String::from("unknown"),
+            item_type: ItemType::Trash,
+            extra_flags: 0,
+            wear_flags: WearFlag::Take.bit(),
+            values: vec![String::from("0"); 5],
+            level: 0,
+            weight: 0,
+            cost: 0,
+            condition: String::from("P"),
+            affects: Vec::new(),
+            flag_affects: Vec::new(),
+            extra_descriptions: Vec::new(),
+            triggers: Vec::new(),
+        }
+    }
+
+    pub fn can_wear(&self, flag: WearFlag) -> bool {
+        self.wear_flags & flag.bit() != 0
+    }
+
+    pub fn has_flag(&self, flag: ExtraFlag) -> bool {
+        self.extra_flags & flag.bit() != 0
+    }
+}
+
+// A stat modifier applied to whoever is using the object
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct ObjectAffect {
+    pub location: Apply,
+    pub modifier: i32,
+}
+
+// An 'F' line on a ROM object, which gives whoever uses it a flag such as sanctuary, or an immunity,
+// resistance or vulnerability. Only the affects are used so far.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ObjectFlagAffect {
+    // A for affects, I for immunities, R for resistances and V for vulnerabilities
+    pub target: String,
+    pub location: i64,
+    pub modifier: i64,
+    pub bitvector: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ObjectInstance {
+    pub template: Object,
+}
+
+impl ObjectInstance {
+    // Whether the given name refers to this object. Like ROM, a prefix of any keyword will do.
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        !name.is_empty()
+            && self
+                .template
+                .keywords
+                .iter()
+                .any(|k| k.to_lowercase().starts_with(&name))
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+    Deserialize,
+    Serialize,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ItemType {
+    Light,
+    Scroll,
+    Wand,
+    Staff,
+    Weapon,
+    Treasure,
+    Armor,
+    Potion,
+    Clothing,
+    Furniture,
+    Trash,
+    Container,
+    #[strum(serialize = "drink")]
+    #[serde(rename = "drink")]
+    DrinkContainer,
+    Key,
+    Food,
+    Money,
+    Boat,
+    NpcCorpse,
+    PcCorpse,
+    Fountain,
+    Pill,
+    Protect,
+    Map,
+    Portal,
+    WarpStone,
+    RoomKey,
+    Gem,
+    Jewelry,
+    Jukebox,
+}
+
+impl ItemType {
+    pub fn to_rom(self) -> i64 {
+        (0..=34)
+            .find(|&n| ItemType::from_rom(n) == Some(self))
+            .unwrap_or(0)
+    }
+
+    // Map the ITEM_* numbering ROM uses in places like #SHOPS
+    pub fn from_rom(item_type: i64) -> Option<ItemType> {
+        let item_type = match item_type {
+            1 => ItemType::Light,
+            2 => ItemType::Scroll,
+            3 => ItemType::Wand,
+            4 => ItemType::Staff,
+            5 => ItemType::Weapon,
+            8 => ItemType::Treasure,
+            9 => ItemType::Armor,
+            10 => ItemType::Potion,
+            11 => ItemType::Clothing,
+            12 => ItemType::Furniture,
+            13 => ItemType::Trash,
+            15 => ItemType::Container,
+            17 => ItemType::DrinkContainer,
+            18 => ItemType::Key,
+            19 => ItemType::Food,
+            20 => ItemType::Money,
+            22 => ItemType::Boat,
+            23 => ItemType::NpcCorpse,
+            24 => ItemType::PcCorpse,
+            25 => ItemType::Fountain,
+            26 => ItemType::Pill,
+            27 => ItemType::Protect,
+            28 => ItemType::Map,
+            29 => ItemType::Portal,
+            30 => ItemType::WarpStone,
+            31 => ItemType::RoomKey,
+            32 => ItemType::Gem,
+            33 => ItemType::Jewelry,
+            34 => ItemType::Jukebox,
+            _ => return None,
+        };
+        Some(item_type)
+    }
+}
+
+// Object properties, using the bit positions of the ITEM_* extra flags in ROM
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum ExtraFlag {
+    Glow = 0,
+    Hum = 1,
+    Dark = 2,
+    Lock = 3,
+    Evil = 4,
+    Invisible = 5,
+    Magic = 6,
+    NoDrop = 7,
+    Bless = 8,
+    AntiGood = 9,
+    AntiEvil = 10,
+    AntiNeutral = 11,
+    NoRemove = 12,
+    // Shop stock that never runs out
+    Inventory = 13,
+    NoPurge = 14,
+    RotDeath = 15,
+    VisDeath = 16,
+    NonMetal = 18,
+    NoLocate = 19,
+    MeltDrop = 20,
+    HadTimer = 21,
+    SellExtract = 22,
+    BurnProof = 24,
+    NoUncurse = 25,
+}
+
+impl ExtraFlag {
+    pub fn bit(self) -> u64 {
+        1 << (self as u64)
+    }
+}
+
+// Where an object is allowed to be worn, in the same bit order as the ITEM_WEAR_* flags in ROM
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum WearFlag {
+    Take,
+    Finger,
+    Neck,
+    Body,
+    Head,
+    Legs,
+    Feet,
+    Hands,
+    Arms,
+    Shield,
+    About,
+    Waist,
+    Wrist,
+    Wield,
+    Hold,
+    NoSacrifice,
+    Float,
+}
+
+impl WearFlag {
+    pub fn bit(self) -> u64 {
+        1 << (self as u64)
+    }
+}
@@ -0,0 +1,185 @@
+// Online creation: builders editing the world from inside the game, in the style of ROM's OLC.
+// While a player has an editor open, what they type goes to it before the usual commands, until
+// they type `done`.
+use crate::{player::Players, world::World};
+use std::fmt;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+mod areload;
+mod asave;
+mod graph;
+mod medit;
+mod oedit;
+mod redit;
+mod resets;
+mod text;
+
+pub use areload::{areload, reload_area};
+pub use asave::asave;
+pub use graph::graph;
+pub use medit::start_medit;
+pub use oedit::start_oedit;
+pub use redit::start_redit;
+pub use resets::edit_resets;
+
+// What a builder is editing
+#[derive(Clone, Debug)]
+pub enum Editor {
+    Room(u32),
+    Mobile(u32),
+    Object(u32),
+}
+
+// A field that's edited a line at a time rather than with a single command
+#[derive(Clone, Copy, Debug)]
+pub enum TextField {
+    RoomDescription(u32),
+    MobileDescription(u32),
+}
+
+// Text being written with the line editor
+#[derive(Clone, Debug)]
+pub struct TextEdit {
+    pub field: TextField,
+    pub lines: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct OlcState {
+    pub editor: Editor,
+    // Set while the line editor is open on top of the editor
+    pub text: Option<TextEdit>,
+}
+
+impl OlcState {
+    pub fn prompt(&self) -> String {
+        if self.text.is_some() {
+            return String::from("> ");
+        }
+        match self.editor {
+            Editor::Room(vnum) => format!("<redit {}> ", vnum),
+            Editor::Mobile(vnum) => format!("<medit {}> ", vnum),
+            Editor::Object(vnum) => format!("<oedit {}> ", vnum),
+        }
+    }
+}
+
+// Handle a line of input from a player with an editor open. Returns false if it isn't an editor
+// command, in which case it should go through the normal command interpreter, so builders can
+// still look around and talk.
+pub fn interpret(sender: u32, input: &str, players: &Players, world: &mut World) -> bool {
+    let Some(state) = players.read().get(&sender).and_then(|p| p.olc.clone()) else {
+        return false;
+    };
+
+    if let Some(text) = state.text {
+        text::interpret(sender, text, input, players, world);
+        return true;
+    }
+
+    match state.editor {
+        Editor::Room(vnum) => redit::interpret(sender, vnum, input, players, world),
+        Editor::Mobile(vnum) => medit::interpret(sender, vnum, input, players, world),
+        Editor::Object(vnum) => oedit::interpret(sender, vnum, input, players, world),
+    }
+}
+
+fn send(players: &Players, sender: u32, message: String) {
+    if let Some(player) = players.read().get(&sender) {
+        player.send_message(message);
+    }
+}
+
+fn set_state(players: &Players, sender: u32, state: Option<OlcState>) {
+    if let Some(player) = players.write().get_mut(&sender) {
+        player.olc = state;
+    }
+}
+
+// Open the line editor on some text
+fn start_text(players: &Players, sender: u32, field: TextField, current: &str) {
+    let lines: Vec<String> = current.lines().map(String::from).collect();
+    if let Some(player) = players.write().get_mut(&sender) {
+        if let Some(state) = &mut player.olc {
+            state.text = Some(TextEdit { field, lines });
+        }
+        player.send_message(String::from(
+            "Entering the line editor. Type .h on a new line for help, and @ to finish.",
+        ));
+    }
+}
+
+// Check that a player may edit something with a vnum, telling them if not
+fn check_builder(players: &Players, sender: u32, world: &World, vnum: u32) -> bool {
+    let Some(username) = players.read().get(&sender).map(|p| p.username.clone()) else {
+        return false;
+    };
+    if world.can_build(&username, vnum) {
+        true
+    } else {
+        send(
+            players,
+            sender,
+            format!("You aren't a builder for vnum {}.", vnum),
+        );
+        false
+    }
+}
+
+// Split an editor command from its argument
+fn split_command(input: &str) -> (&str, &str) {
+    match input.trim().split_once(char::is_whitespace) {
+        Some((command, argument)) => (command, argument.trim()),
+        None => (input.trim(), ""),
+    }
+}
+
+// All the names of a flag or type, for when a builder gets one wrong
+fn names<T: IntoEnumIterator + fmt::Display>() -> String {
+    T::iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Toggle the flag named by the argument
+fn toggle_flag<F>(
+    flags: &mut u64,
+    argument: &str,
+    bit: fn(F) -> u64,
+    kind: &str,
+) -> Result<String, String>
+where
+    F: FromStr + IntoEnumIterator + fmt::Display + Copy,
+{
+    let Ok(flag) = F::from_str(argument) else {
+        return Err(format!("{} are: {}", kind, names::<F>()));
+    };
+    *flags ^= bit(flag);
+    let now_set = *flags & bit(flag) != 0;
+    Ok(format!(
+        "{} {}.",
+        flag,
+        if now_set { "set" } else { "removed" }
+    ))
+}
+
+fn parse_number<T: FromStr>(argument: &str, what: &str) -> Result<T, String> {
+    argument
+        .parse()
+        .map_err(|_| format!("{} isn't a valid {}.", argument, what))
+}
+
+// Show a set of flags the way the editors do
+fn flag_list<F: IntoEnumIterator + fmt::Display + Copy>(flags: u64, bit: fn(F) -> u64) -> String {
+    let set: Vec<String> = F::iter()
+        .filter(|f| flags & bit(*f) != 0)
+        .map(|f| f.to_string())
+        .collect();
+    if set.is_empty() {
+        String::from("none")
+    } else {
+        set.join(" ")
+    }
+}
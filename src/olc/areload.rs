@@ -0,0 +1,144 @@
+// Reloading an area from its file into the running game, eg after the file was edited by hand or
+// replaced with a new version, without restarting the server
+use super::send;
+use crate::{
+    area_file,
+    player::Players,
+    world::{AreaId, World},
+};
+
+const SYNTAX: &str = "Syntax: areload [area] [force]\n\
+    Reloads the area you're standing in, or the one named, from its file. Unsaved changes to the\n\
+    area are lost, so it has to be forced if there are any.";
+
+pub fn areload(sender: u32, argument: Option<&str>, players: &Players, world: &mut World) {
+    let Some((username, room_id)) = players
+        .read()
+        .get(&sender)
+        .map(|p| (p.username.clone(), p.current_room))
+    else {
+        return;
+    };
+
+    let mut words: Vec<&str> = argument.unwrap_or_default().split_whitespace().collect();
+    let force = words.last() == Some(&"force");
+    if force {
+        words.pop();
+    }
+    let area_id = match words.as_slice() {
+        [] => world.room_area(room_id),
+        ["?"] => {
+            send(players, sender, String::from(SYNTAX));
+            return;
+        }
+        name => world.find_area(&name.join(" ")),
+    };
+    let Some(area_id) = area_id else {
+        send(
+            players,
+            sender,
+            String::from("There's no area by that name."),
+        );
+        return;
+    };
+
+    if !world.can_build_area(&username, area_id) {
+        send(
+            players,
+            sender,
+            String::from("You aren't a builder for that area."),
+        );
+        return;
+    }
+    if world.get_area(area_id).is_some_and(|a| a.changed) && !force {
+        send(
+            players,
+            sender,
+            String::from(
+                "That area has changes that haven't been saved. Save them with asave, or use \
+                 'areload <area> force' to throw them away.",
+            ),
+        );
+        return;
+    }
+
+    let response = match reload_area(area_id, players, world) {
+        Ok(response) | Err(response) => response,
+    };
+    send(players, sender, response);
+}
+
+// Load an area again from the file it came from and swap it in. Players standing in rooms that
+// are gone afterwards are sent to the recall room.
+pub fn reload_area(
+    area_id: AreaId,
+    players: &Players,
+    world: &mut World,
+) -> Result<String, String> {
+    let Some(area) = world.get_area(area_id) else {
+        return Err(String::from("There's no such area."));
+    };
+    let name = area.metadata.name.clone();
+    let Some(path) = area.metadata.file.clone() else {
+        return Err(format!(
+            "{} wasn't loaded from a file, so it can't be reloaded.",
+            name
+        ));
+    };
+
+    let (area, warnings) = match area_file::load(&path, area.metadata.format) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            tracing::error!(area = name, "Failed to reload area: {}", error);
+            return Err(format!(
+                "Couldn't reload {}, it's unchanged: {}",
+                name, error
+            ));
+        }
+    };
+    for warning in &warnings {
+        tracing::warn!("{}", warning);
+    }
+    let counts = (area.rooms.len(), area.mobiles.len(), area.objects.len());
+    let vanished = world.reload_area(area_id, area);
+    tracing::info!(area = name, file = %path.display(), "Reloaded area");
+
+    // Anyone left in a room that doesn't exist any more
+    let recall = world.recall_vnum;
+    let mut moved = Vec::new();
+    for player in players.write().values_mut() {
+        if vanished.contains(&player.current_room) {
+            player.move_to_room(recall);
+            moved.push(player.id);
+        }
+    }
+    for id in &moved {
+        send(
+            players,
+            *id,
+            String::from("The world shifts around you, and you find yourself somewhere else."),
+        );
+    }
+
+    let mut response = format!(
+        "Reloaded {} from {}: {} rooms, {} mobiles and {} objects.",
+        name,
+        path.display(),
+        counts.0,
+        counts.1,
+        counts.2
+    );
+    if !moved.is_empty() {
+        response.push_str(&format!(
+            "\n{} players were moved out of rooms that are gone.",
+            moved.len()
+        ));
+    }
+    if !warnings.is_empty() {
+        response.push_str(&format!(
+            "\nThere were {} warnings, see the server log.",
+            warnings.len()
+        ));
+    }
+    Ok(response)
+}
@@ -0,0 +1,86 @@
+// Saving edited areas back to their files
+use super::send;
+use crate::{
+    area_file,
+    player::Players,
+    world::{AreaId, World},
+};
+
+const SYNTAX: &str = "Syntax: asave changed|area|world\n\
+    asave changed   save the areas you can edit that have been changed\n\
+    asave area      save the area you're standing in\n\
+    asave world     save every area you can edit";
+
+pub fn asave(sender: u32, argument: Option<&str>, players: &Players, world: &mut World) {
+    let Some((username, room_id)) = players
+        .read()
+        .get(&sender)
+        .map(|p| (p.username.clone(), p.current_room))
+    else {
+        return;
+    };
+
+    let areas: Vec<AreaId> = match argument.unwrap_or_default().trim() {
+        "changed" => (0..world.areas().len())
+            .filter(|&id| world.areas()[id].changed && world.can_build_area(&username, id))
+            .collect(),
+        "area" => match world.room_area(room_id) {
+            Some(id) if world.can_build_area(&username, id) => vec![id],
+            Some(_) => {
+                send(
+                    players,
+                    sender,
+                    String::from("You aren't a builder for this area."),
+                );
+                return;
+            }
+            None => {
+                send(players, sender, String::from("This room isn't in an area."));
+                return;
+            }
+        },
+        "world" => (0..world.areas().len())
+            .filter(|&id| world.can_build_area(&username, id))
+            .collect(),
+        _ => {
+            send(players, sender, String::from(SYNTAX));
+            return;
+        }
+    };
+
+    if areas.is_empty() {
+        send(players, sender, String::from("Nothing to save."));
+        return;
+    }
+
+    let mut report = Vec::new();
+    for id in areas {
+        let Some(area) = world.get_area(id) else {
+            continue;
+        };
+        let name = area.metadata.name.clone();
+        let Some(path) = area.metadata.file.clone() else {
+            report.push(format!(
+                "{} wasn't loaded from a file, so it can't be saved.",
+                name
+            ));
+            continue;
+        };
+
+        match area_file::save(area, world, &path, area.metadata.format) {
+            Ok(()) => {
+                tracing::info!(area = name, file = %path.display(), "Saved area");
+                if let Some(area) = world.get_area_mut(id) {
+                    area.changed = false;
+                    area.modified = area_file::modified(&path, area.metadata.format);
+                }
+                report.push(format!("Saved {} to {}.", name, path.display()));
+            }
+            Err(error) => {
+                tracing::error!(area = name, file = %path.display(), "Failed to save area: {}", error);
+                report.push(format!("Couldn't save {}: {}", name, error));
+            }
+        }
+    }
+    send(players, sender, report.join("\n"));
+}
@@ -0,0 +1,93 @@
+// Writing the room graph out to a file for drawing maps, from inside the game
+use super::send;
+use crate::{
+    graph::{self, GraphFormat},
+    player::Players,
+    world::World,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Where exported graphs go, relative to where the server was started
+const MAP_DIRECTORY: &str = "maps";
+
+const SYNTAX: &str = "Syntax: graph [area|world] [dot|json]\n\
+    Writes the rooms and exits of the area you're standing in, the one named, or the whole world\n\
+    to a file under maps/, as Graphviz DOT or JSON for the web map.";
+
+pub fn graph(sender: u32, argument: Option<&str>, players: &Players, world: &mut World) {
+    let Some((username, room_id)) = players
+        .read()
+        .get(&sender)
+        .map(|p| (p.username.clone(), p.current_room))
+    else {
+        return;
+    };
+    if !world.is_builder(&username) {
+        send(players, sender, String::from("Only builders can do that."));
+        return;
+    }
+
+    let mut words: Vec<&str> = argument.unwrap_or_default().split_whitespace().collect();
+    let format = match words.last().map(|word| word.parse::<GraphFormat>()) {
+        Some(Ok(format)) => {
+            words.pop();
+            format
+        }
+        _ => GraphFormat::Dot,
+    };
+    let area_id = match words.as_slice() {
+        ["?"] => {
+            send(players, sender, String::from(SYNTAX));
+            return;
+        }
+        ["world"] => None,
+        [] => match world.room_area(room_id) {
+            Some(area_id) => Some(area_id),
+            None => {
+                send(players, sender, String::from("This room isn't in an area."));
+                return;
+            }
+        },
+        name => match world.find_area(&name.join(" ")) {
+            Some(area_id) => Some(area_id),
+            None => {
+                send(
+                    players,
+                    sender,
+                    String::from("There's no area by that name."),
+                );
+                return;
+            }
+        },
+    };
+
+    // Named after the area's file, so exporting it again replaces the old one
+    let name = area_id
+        .and_then(|id| world.get_area(id))
+        .map_or(String::from("world"), |area| {
+            area.metadata
+                .file
+                .as_deref()
+                .and_then(Path::file_stem)
+                .map_or(area.metadata.name.to_lowercase(), |stem| {
+                    stem.to_string_lossy().to_string()
+                })
+                .replace(|c: char| !c.is_alphanumeric() && c != '-', "_")
+        });
+    let path = PathBuf::from(MAP_DIRECTORY).join(format!("{}.{}", name, format.extension()));
+
+    let output = graph::export(world, area_id, format);
+    let result = fs::create_dir_all(MAP_DIRECTORY).and_then(|()| fs::write(&path, output));
+    let response = match result {
+        Ok(()) => {
+            tracing::info!(file = %path.display(), username, "Exported room graph");
+            format!("Wrote the room graph to {}.", path.display())
+        }
+        Err(error) => {
+            tracing::error!(file = %path.display(), "Failed to export room graph: {}", error);
+            format!("Couldn't write {}: {}", path.display(), error)
+        }
+    };
+    send(players, sender, response);
+}
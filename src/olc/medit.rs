@@ -0,0 +1,251 @@
+// The mobile editor. Changes are made to the template, so they show up on mobiles spawned after
+// the edit, eg by running the area's resets.
+use super::{
+    check_builder, flag_list, parse_number, send, set_state, split_command, start_text,
+    toggle_flag, Editor, OlcState, TextField,
+};
+use crate::{
+    dice::Dice,
+    mobiles::{ActFlag, AffectFlag, Mobile},
+    player::Players,
+    world::World,
+};
+
+const COMMANDS: &str = "Mobile editor commands:\n\
+    show                      show the mobile\n\
+    keywords <words>          set the names it can be referred to by\n\
+    short <text>              set the short description, eg \"the baker\"\n\
+    long <text>               set the line shown when it's in a room\n\
+    desc                      edit what players see when they look at it\n\
+    level <number>            set its level\n\
+    alignment <number>        set its alignment, from -1000 to 1000\n\
+    hitroll <number>          set its bonus to hit\n\
+    hitdice <dice>            set the dice its hit points are rolled with, eg 3d8+10\n\
+    manadice <dice>           set the dice its mana is rolled with\n\
+    damdice <dice>            set the dice its damage is rolled with\n\
+    act <flag>                toggle an act flag\n\
+    affect <flag>             toggle something it's always affected by\n\
+    create <vnum>             create a mobile and start editing it\n\
+    done                      stop editing";
+
+// `medit <vnum>` or `medit create <vnum>` from the normal command interpreter
+pub fn start_medit(sender: u32, argument: Option<&str>, players: &Players, world: &mut World) {
+    let words: Vec<&str> = argument.unwrap_or_default().split_whitespace().collect();
+    let (create, vnum) = match words.as_slice() {
+        ["create", vnum] => (true, vnum.parse().ok()),
+        [vnum] => (false, vnum.parse().ok()),
+        _ => (false, None),
+    };
+    let Some(vnum) = vnum else {
+        send(
+            players,
+            sender,
+            String::from("Syntax: medit [create] <vnum>"),
+        );
+        return;
+    };
+
+    if !check_builder(players, sender, world, vnum) {
+        return;
+    }
+    if create {
+        if let Err(error) = world.create_mobile_template(vnum) {
+            send(players, sender, error);
+            return;
+        }
+    } else if world.get_mobile_template(vnum).is_none() {
+        send(players, sender, format!("There's no mobile {}.", vnum));
+        return;
+    }
+
+    set_state(
+        players,
+        sender,
+        Some(OlcState {
+            editor: Editor::Mobile(vnum),
+            text: None,
+        }),
+    );
+    send(players, sender, show(vnum, world));
+}
+
+pub fn interpret(
+    sender: u32,
+    vnum: u32,
+    input: &str,
+    players: &Players,
+    world: &mut World,
+) -> bool {
+    let (command, argument) = split_command(input);
+    let command = command.to_lowercase();
+
+    if world.get_mobile_template(vnum).is_none() && command != "done" {
+        send(
+            players,
+            sender,
+            String::from("The mobile you were editing is gone."),
+        );
+        set_state(players, sender, None);
+        return true;
+    }
+
+    let response = match command.as_str() {
+        "" | "show" => show(vnum, world),
+        "?" | "commands" => String::from(COMMANDS),
+        "done" => {
+            set_state(players, sender, None);
+            String::from("You stop editing.")
+        }
+        "desc" => {
+            let current = world
+                .get_mobile_template(vnum)
+                .map(|m| m.description.clone())
+                .unwrap_or_default();
+            start_text(
+                players,
+                sender,
+                TextField::MobileDescription(vnum),
+                &current,
+            );
+            return true;
+        }
+        "create" => {
+            start_medit(
+                sender,
+                Some(&format!("create {}", argument)),
+                players,
+                world,
+            );
+            return true;
+        }
+        _ => {
+            let Some(mobile) = world.get_mobile_template_mut(vnum) else {
+                return true;
+            };
+            match edit(mobile, &command, argument) {
+                Some(Ok(response)) => {
+                    world.mark_changed(vnum);
+                    response
+                }
+                Some(Err(response)) => response,
+                None => return false,
+            }
+        }
+    };
+    send(players, sender, response);
+    true
+}
+
+// Change a field of the template. Returns None if the command isn't one of ours.
+fn edit(mobile: &mut Mobile, command: &str, argument: &str) -> Option<Result<String, String>> {
+    if argument.is_empty() {
+        return match command {
+            "keywords" | "short" | "long" | "level" | "alignment" | "hitroll" | "hitdice"
+            | "manadice" | "damdice" | "act" | "affect" => {
+                Some(Err(format!("Syntax: {} <value>, or ? for help", command)))
+            }
+            _ => None,
+        };
+    }
+
+    let result = match command {
+        "keywords" => {
+            mobile.keywords = argument.split_whitespace().map(String::from).collect();
+            Ok(String::from("Keywords set."))
+        }
+        "short" => {
+            mobile.room_description = argument.to_string();
+            Ok(String::from("Short description set."))
+        }
+        "long" => {
+            mobile.long_description = argument.to_string();
+            Ok(String::from("Long description set."))
+        }
+        "level" => parse_number(argument, "level").map(|level| {
+            mobile.level = level;
+            format!("Level set to {}.", level)
+        }),
+        "alignment" => match parse_number(argument, "alignment") {
+            Ok(alignment) if (-1000..=1000).contains(&alignment) => {
+                mobile.alignment = alignment;
+                Ok(format!("Alignment set to {}.", alignment))
+            }
+            Ok(_) => Err(String::from("Alignment runs from -1000 to 1000.")),
+            Err(error) => Err(error),
+        },
+        "hitroll" => parse_number(argument, "hitroll").map(|hitroll| {
+            mobile.hitroll = hitroll;
+            format!("Hitroll set to {}.", hitroll)
+        }),
+        "hitdice" | "manadice" | "damdice" => argument.parse::<Dice>().map(|dice| {
+            let (field, name) = match command {
+                "hitdice" => (&mut mobile.hit_dice, "Hit"),
+                "manadice" => (&mut mobile.mana_dice, "Mana"),
+                _ => (&mut mobile.damage_dice, "Damage"),
+            };
+            *field = dice;
+            format!("{} dice set to {}.", name, dice)
+        }),
+        "act" => toggle_flag(&mut mobile.act_flags, argument, ActFlag::bit, "Act flags"),
+        "affect" => toggle_flag(
+            &mut mobile.affected_by,
+            argument,
+            AffectFlag::bit,
+            "Affects",
+        ),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn show(vnum: u32, world: &World) -> String {
+    let Some(mobile) = world.get_mobile_template(vnum) else {
+        return format!("There's no mobile {}.", vnum);
+    };
+    let area = world
+        .areas()
+        .iter()
+        .find(|a| a.mobiles.contains_key(&vnum))
+        .map_or("none", |a| a.metadata.name.as_str());
+
+    let mut programs: Vec<String> = mobile
+        .triggers
+        .iter()
+        .map(|t| format!(" {:<8} program {:<6} {}", t.trigger, t.program, t.phrase))
+        .collect();
+    if programs.is_empty() {
+        programs.push(String::from(" none"));
+    }
+
+    format!(
+        "Keywords:    [{}]\n\
+         Area:        [{}]\n\
+         Vnum:        [{}]\n\
+         Level:       [{}]  Alignment: [{}]  Hitroll: [{}]\n\
+         Hit dice:    [{}]  Mana dice: [{}]  Damage dice: [{}]\n\
+         Act:         [{}]\n\
+         Affected by: [{}]\n\
+         Special:     [{}]  Shop: [{}]\n\
+         Short:       [{}]\n\
+         Long:        [{}]\n\
+         Description:\n{}\n\
+         MOBprograms:\n{}",
+        mobile.keywords.join(" "),
+        area,
+        mobile.id,
+        mobile.level,
+        mobile.alignment,
+        mobile.hitroll,
+        mobile.hit_dice,
+        mobile.mana_dice,
+        mobile.damage_dice,
+        flag_list(mobile.act_flags, ActFlag::bit),
+        flag_list(mobile.affected_by, AffectFlag::bit),
+        mobile.special.as_ref().map_or("none", |s| s.name),
+        if mobile.shop.is_some() { "yes" } else { "no" },
+        mobile.room_description,
+        mobile.long_description,
+        mobile.description.trim_end(),
+        programs.join("\n")
+    )
+}
@@ -0,0 +1,317 @@
+// The object editor. Like the mobile editor it changes the template, so objects already in the
+// world keep the properties they were created with.
+use super::{
+    check_builder, flag_list, names, parse_number, send, set_state, split_command, toggle_flag,
+    Editor, OlcState,
+};
+use crate::{
+    objects::{ExtraFlag, ItemType, Object, ObjectAffect, WearFlag},
+    player::Players,
+    stats::Apply,
+    world::World,
+};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+// ROM objects have five values, whose meaning depends on the item type
+const VALUES: usize = 5;
+
+const COMMANDS: &str = "Object editor commands:\n\
+    show                         show the object\n\
+    keywords <words>             set the names it can be referred to by\n\
+    short <text>                 set the short description, eg \"a long sword\"\n\
+    long <text>                  set the line shown when it's lying in a room\n\
+    material <word>              set what it's made of\n\
+    type <item type>             set the item type\n\
+    level <number>               set its level\n\
+    weight <number>              set its weight\n\
+    cost <number>                set its value in silver\n\
+    extra <flag>                 toggle an extra flag\n\
+    wear <flag>                  toggle where it can be worn\n\
+    v0 .. v4 <value>             set one of the values, eg the damage dice of a weapon\n\
+    addaffect <location> <mod>   add a modifier to a stat of whoever uses it\n\
+    delaffect <number>           remove a modifier\n\
+    create <vnum>                create an object and start editing it\n\
+    done                         stop editing";
+
+// `oedit <vnum>` or `oedit create <vnum>` from the normal command interpreter
+pub fn start_oedit(sender: u32, argument: Option<&str>, players: &Players, world: &mut World) {
+    let words: Vec<&str> = argument.unwrap_or_default().split_whitespace().collect();
+    let (create, vnum) = match words.as_slice() {
+        ["create", vnum] => (true, vnum.parse().ok()),
+        [vnum] => (false, vnum.parse().ok()),
+        _ => (false, None),
+    };
+    let Some(vnum) = vnum else {
+        send(
+            players,
+            sender,
+            String::from("Syntax: oedit [create] <vnum>"),
+        );
+        return;
+    };
+
+    if !check_builder(players, sender, world, vnum) {
+        return;
+    }
+    if create {
+        if let Err(error) = world.create_object_template(vnum) {
+            send(players, sender, error);
+            return;
+        }
+    } else if world.get_object_template(vnum).is_none() {
+        send(players, sender, format!("There's no object {}.", vnum));
+        return;
+    }
+
+    set_state(
+        players,
+        sender,
+        Some(OlcState {
+            editor: Editor::Object(vnum),
+            text: None,
+        }),
+    );
+    send(players, sender, show(vnum, world));
+}
+
+pub fn interpret(
+    sender: u32,
+    vnum: u32,
+    input: &str,
+    players: &Players,
+    world: &mut World,
+) -> bool {
+    let (command, argument) = split_command(input);
+    let command = command.to_lowercase();
+
+    if world.get_object_template(vnum).is_none() && command != "done" {
+        send(
+            players,
+            sender,
+            String::from("The object you were editing is gone."),
+        );
+        set_state(players, sender, None);
+        return true;
+    }
+
+    let response = match command.as_str() {
+        "" | "show" => show(vnum, world),
+        "?" | "commands" => String::from(COMMANDS),
+        "done" => {
+            set_state(players, sender, None);
+            String::from("You stop editing.")
+        }
+        "create" => {
+            start_oedit(
+                sender,
+                Some(&format!("create {}", argument)),
+                players,
+                world,
+            );
+            return true;
+        }
+        _ => {
+            let Some(object) = world.get_object_template_mut(vnum) else {
+                return true;
+            };
+            match edit(object, &command, argument) {
+                Some(Ok(response)) => {
+                    world.mark_changed(vnum);
+                    response
+                }
+                Some(Err(response)) => response,
+                None => return false,
+            }
+        }
+    };
+    send(players, sender, response);
+    true
+}
+
+// Change a field of the template. Returns None if the command isn't one of ours.
+fn edit(object: &mut Object, command: &str, argument: &str) -> Option<Result<String, String>> {
+    // v0 to v4
+    let value = command
+        .strip_prefix('v')
+        .and_then(|n| n.parse::<usize>().ok())
+        .filter(|&n| n < VALUES);
+
+    if argument.is_empty() {
+        return match command {
+            "keywords" | "short" | "long" | "material" | "type" | "level" | "weight" | "cost"
+            | "extra" | "wear" | "addaffect" | "delaffect" => {
+                Some(Err(format!("Syntax: {} <value>, or ? for help", command)))
+            }
+            _ if value.is_some() => Some(Err(format!("Syntax: {} <value>", command))),
+            _ => None,
+        };
+    }
+
+    if let Some(n) = value {
+        if object.values.len() < VALUES {
+            object.values.resize(VALUES, String::from("0"));
+        }
+        object.values[n] = argument.to_string();
+        return Some(Ok(format!("Value {} set to {}.", n, argument)));
+    }
+
+    let result = match command {
+        "keywords" => {
+            object.keywords = argument.split_whitespace().map(String::from).collect();
+            Ok(String::from("Keywords set."))
+        }
+        "short" => {
+            object.short_description = argument.to_string();
+            Ok(String::from("Short description set."))
+        }
+        "long" => {
+            object.long_description = argument.to_string();
+            Ok(String::from("Long description set."))
+        }
+        "material" => {
+            object.material = argument.to_string();
+            Ok(String::from("Material set."))
+        }
+        "type" => match ItemType::from_str(argument) {
+            Ok(item_type) => {
+                object.item_type = item_type;
+                Ok(format!(
+                    "Item type set to {}. Check its values still make sense.",
+                    item_type
+                ))
+            }
+            Err(_) => Err(format!("Item types are: {}", names::<ItemType>())),
+        },
+        "level" => parse_number(argument, "level").map(|level| {
+            object.level = level;
+            format!("Level set to {}.", level)
+        }),
+        "weight" => parse_number(argument, "weight").map(|weight| {
+            object.weight = weight;
+            format!("Weight set to {}.", weight)
+        }),
+        "cost" => parse_number(argument, "cost").map(|cost| {
+            object.cost = cost;
+            format!("Cost set to {}.", cost)
+        }),
+        "extra" => toggle_flag(
+            &mut object.extra_flags,
+            argument,
+            ExtraFlag::bit,
+            "Extra flags",
+        ),
+        "wear" => toggle_flag(
+            &mut object.wear_flags,
+            argument,
+            WearFlag::bit,
+            "Wear flags",
+        ),
+        "addaffect" => add_affect(object, argument),
+        "delaffect" => match parse_number::<usize>(argument, "affect number") {
+            Ok(n) if n >= 1 && n <= object.affects.len() => {
+                let affect = object.affects.remove(n - 1);
+                Ok(format!(
+                    "Removed {:+} {}.",
+                    affect.modifier, affect.location
+                ))
+            }
+            Ok(_) => Err(String::from("There's no affect with that number.")),
+            Err(error) => Err(error),
+        },
+        _ => return None,
+    };
+    Some(result)
+}
+
+// `addaffect <location> <modifier>`. Locations with spaces in them can be typed with
+// underscores, eg armor_class.
+fn add_affect(object: &mut Object, argument: &str) -> Result<String, String> {
+    let syntax = || {
+        format!(
+            "Syntax: addaffect <location> <modifier>\nLocations are: {}",
+            Apply::iter()
+                .filter(|a| *a != Apply::None)
+                .map(|a| a.to_string().replace(' ', "_"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    };
+    let (location, modifier) = argument
+        .rsplit_once(char::is_whitespace)
+        .ok_or_else(syntax)?;
+    let location = location.trim().replace('_', " ").to_lowercase();
+    let location = Apply::iter()
+        .filter(|a| *a != Apply::None)
+        .find(|a| a.to_string() == location)
+        .ok_or_else(syntax)?;
+    let modifier = parse_number(modifier, "modifier")?;
+
+    object.affects.push(ObjectAffect { location, modifier });
+    Ok(format!("Added {:+} {}.", modifier, location))
+}
+
+fn show(vnum: u32, world: &World) -> String {
+    let Some(object) = world.get_object_template(vnum) else {
+        return format!("There's no object {}.", vnum);
+    };
+    let area = world
+        .areas()
+        .iter()
+        .find(|a| a.objects.contains_key(&vnum))
+        .map_or("none", |a| a.metadata.name.as_str());
+
+    let mut values: Vec<String> = (0..VALUES)
+        .map(|n| {
+            format!(
+                "[v{}] {}",
+                n,
+                object.values.get(n).map_or("0", |v| v.as_str())
+            )
+        })
+        .collect();
+    // The values most often wanted are a weapon's damage dice
+    if object.item_type == ItemType::Weapon {
+        if let (Some(number), Some(size)) = (object.values.get(1), object.values.get(2)) {
+            values.push(format!("Damage dice {}d{}", number, size));
+        }
+    }
+
+    let mut affects: Vec<String> = object
+        .affects
+        .iter()
+        .enumerate()
+        .map(|(i, a)| format!(" {:2}] {:+} {}", i + 1, a.modifier, a.location))
+        .collect();
+    if affects.is_empty() {
+        affects.push(String::from(" none"));
+    }
+
+    format!(
+        "Keywords:    [{}]\n\
+         Area:        [{}]\n\
+         Vnum:        [{}]\n\
+         Type:        [{}]  Material: [{}]\n\
+         Level:       [{}]  Weight: [{}]  Cost: [{}]\n\
+         Extra flags: [{}]\n\
+         Wear flags:  [{}]\n\
+         Short:       [{}]\n\
+         Long:        [{}]\n\
+         Values:      {}\n\
+         Affects:\n{}",
+        object.keywords.join(" "),
+        area,
+        object.id,
+        object.item_type,
+        object.material,
+        object.level,
+        object.weight,
+        object.cost,
+        flag_list(object.extra_flags, ExtraFlag::bit),
+        flag_list(object.wear_flags, WearFlag::bit),
+        object.short_description,
+        object.long_description,
+        values.join(" "),
+        affects.join("\n")
+    )
+}
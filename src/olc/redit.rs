@@ -0,0 +1,343 @@
+// The room editor
+use super::{check_builder, send, set_state, start_text, Editor, OlcState, TextField};
+use crate::{
+    actions::{LookAction, PlayerAction},
+    message::Direction,
+    player::Players,
+    room::{RoomFlag, Sector},
+    world::World,
+};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+const COMMANDS: &str = "Room editor commands:\n\
+    show                      show the room\n\
+    name <text>               set the room's name\n\
+    desc                      edit the description with the line editor\n\
+    sector <sector>           set the terrain\n\
+    room <flag>               toggle a room flag\n\
+    <direction> link <vnum>   make a two-way exit\n\
+    <direction> room <vnum>   make a one-way exit\n\
+    <direction> dig <vnum>    create a room and link to it\n\
+    <direction> delete        remove an exit, and the one coming back\n\
+    create <vnum>             create a room and start editing it\n\
+    done                      stop editing";
+
+// `redit` from the normal command interpreter. With no argument it edits the room the builder is
+// standing in, otherwise it takes them to the given room, creating it first for `redit create`.
+pub fn start_redit(sender: u32, argument: Option<&str>, players: &Players, world: &mut World) {
+    let Some(current_room) = players.read().get(&sender).map(|p| p.current_room) else {
+        return;
+    };
+
+    let words: Vec<&str> = argument.unwrap_or_default().split_whitespace().collect();
+    let (create, vnum) = match words.as_slice() {
+        [] => (false, Some(current_room)),
+        ["create", vnum] => (true, vnum.parse().ok()),
+        [vnum] => (false, vnum.parse().ok()),
+        _ => (false, None),
+    };
+    let Some(vnum) = vnum else {
+        send(
+            players,
+            sender,
+            String::from("Syntax: redit [create] [vnum]"),
+        );
+        return;
+    };
+
+    if !check_builder(players, sender, world, vnum) {
+        return;
+    }
+    if create {
+        if let Err(error) = world.create_room(vnum) {
+            send(players, sender, error);
+            return;
+        }
+    } else if world.get_room(vnum).is_none() {
+        send(players, sender, format!("There's no room {}.", vnum));
+        return;
+    }
+
+    edit_room(sender, vnum, players, world);
+}
+
+// Start editing a room, taking the builder there
+fn edit_room(sender: u32, vnum: u32, players: &Players, world: &mut World) {
+    if let Some(player) = players.write().get_mut(&sender) {
+        player.olc = Some(OlcState {
+            editor: Editor::Room(vnum),
+            text: None,
+        });
+        if player.current_room != vnum {
+            player.move_to_room(vnum);
+        }
+    }
+    LookAction { sender }.perform(players, world);
+    send(players, sender, show(vnum, world));
+}
+
+pub fn interpret(
+    sender: u32,
+    vnum: u32,
+    input: &str,
+    players: &Players,
+    world: &mut World,
+) -> bool {
+    let (command, argument) = match input.trim().split_once(char::is_whitespace) {
+        Some((command, argument)) => (command, argument.trim()),
+        None => (input.trim(), ""),
+    };
+
+    // Someone may have removed the room from under us, eg by reloading the area
+    if world.get_room(vnum).is_none() && command != "done" {
+        send(
+            players,
+            sender,
+            String::from("The room you were editing is gone."),
+        );
+        set_state(players, sender, None);
+        return true;
+    }
+
+    if let Some(direction) = Direction::from_word(command) {
+        // A direction on its own is still just movement
+        if argument.is_empty() {
+            return false;
+        }
+        edit_exit(sender, vnum, direction, argument, players, world);
+        return true;
+    }
+
+    let response = match command.to_lowercase().as_str() {
+        "" | "show" => show(vnum, world),
+        "?" | "commands" => String::from(COMMANDS),
+        "done" => {
+            set_state(players, sender, None);
+            String::from("You stop editing.")
+        }
+        "name" if !argument.is_empty() => {
+            if let Some(room) = world.get_room_mut(vnum) {
+                room.name = argument.to_string();
+            }
+            world.mark_changed(vnum);
+            String::from("Name set.")
+        }
+        "desc" => {
+            let current = world
+                .get_room(vnum)
+                .map(|r| r.description.clone())
+                .unwrap_or_default();
+            start_text(players, sender, TextField::RoomDescription(vnum), &current);
+            return true;
+        }
+        "sector" => match Sector::from_str(argument) {
+            Ok(sector) => {
+                if let Some(room) = world.get_room_mut(vnum) {
+                    room.sector = sector;
+                }
+                world.mark_changed(vnum);
+                format!("Sector set to {}.", sector)
+            }
+            Err(_) => format!(
+                "Sectors are: {}",
+                Sector::iter()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+        },
+        "room" | "flags" => match RoomFlag::from_str(argument) {
+            Ok(flag) => {
+                let mut now_set = false;
+                if let Some(room) = world.get_room_mut(vnum) {
+                    room.flags ^= flag.bit();
+                    now_set = room.has_flag(flag);
+                }
+                world.mark_changed(vnum);
+                format!("{} {}.", flag, if now_set { "set" } else { "removed" })
+            }
+            Err(_) => format!(
+                "Room flags are: {}",
+                RoomFlag::iter()
+                    .map(|f| f.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+        },
+        "create" => {
+            let Ok(new_vnum) = argument.parse::<u32>() else {
+                send(players, sender, String::from("Syntax: create <vnum>"));
+                return true;
+            };
+            if !check_builder(players, sender, world, new_vnum) {
+                return true;
+            }
+            if let Err(error) = world.create_room(new_vnum) {
+                send(players, sender, error);
+                return true;
+            }
+            edit_room(sender, new_vnum, players, world);
+            return true;
+        }
+        _ => return false,
+    };
+    send(players, sender, response);
+    true
+}
+
+fn edit_exit(
+    sender: u32,
+    vnum: u32,
+    direction: Direction,
+    argument: &str,
+    players: &Players,
+    world: &mut World,
+) {
+    let (action, target) = match argument.split_once(char::is_whitespace) {
+        Some((action, target)) => (action, target.trim().parse::<u32>().ok()),
+        None => (argument, None),
+    };
+    let direction_name = direction.to_string();
+    let reverse_name = direction.reverse().to_string();
+
+    let response = match (action.to_lowercase().as_str(), target) {
+        ("delete", _) => {
+            let Some(destination) = world
+                .get_room(vnum)
+                .and_then(|r| r.get_exit(&direction_name))
+                .copied()
+            else {
+                send(players, sender, String::from("There's no exit that way."));
+                return;
+            };
+            if let Some(room) = world.get_room_mut(vnum) {
+                room.remove_exit(&direction_name);
+            }
+            world.mark_changed(vnum);
+
+            // Only take away the way back if it actually comes back here, and the builder is
+            // allowed to edit that side
+            let comes_back = world
+                .get_room(destination)
+                .and_then(|r| r.get_exit(&reverse_name))
+                == Some(&vnum);
+            let username = players
+                .read()
+                .get(&sender)
+                .map(|p| p.username.clone())
+                .unwrap_or_default();
+            if comes_back && world.can_build(&username, destination) {
+                if let Some(room) = world.get_room_mut(destination) {
+                    room.remove_exit(&reverse_name);
+                }
+                world.mark_changed(destination);
+                String::from("Two-way exit removed.")
+            } else {
+                String::from("Exit removed.")
+            }
+        }
+        ("room", Some(destination)) => {
+            if world.get_room(destination).is_none() {
+                format!("There's no room {}.", destination)
+            } else {
+                if let Some(room) = world.get_room_mut(vnum) {
+                    room.add_exit(&direction_name, destination);
+                }
+                world.mark_changed(vnum);
+                format!(
+                    "One-way exit {} to room {} made.",
+                    direction_name, destination
+                )
+            }
+        }
+        ("link" | "dig", Some(destination)) => {
+            if !check_builder(players, sender, world, destination) {
+                return;
+            }
+            if action.eq_ignore_ascii_case("dig") {
+                if let Err(error) = world.create_room(destination) {
+                    send(players, sender, error);
+                    return;
+                }
+            } else if world.get_room(destination).is_none() {
+                send(players, sender, format!("There's no room {}.", destination));
+                return;
+            }
+            if world
+                .get_room(destination)
+                .is_some_and(|r| r.get_exit(&reverse_name).is_some())
+            {
+                send(
+                    players,
+                    sender,
+                    format!("Room {} already has an exit {}.", destination, reverse_name),
+                );
+                return;
+            }
+
+            if let Some(room) = world.get_room_mut(vnum) {
+                room.add_exit(&direction_name, destination);
+            }
+            if let Some(room) = world.get_room_mut(destination) {
+                room.add_exit(&reverse_name, vnum);
+            }
+            world.mark_changed(vnum);
+            world.mark_changed(destination);
+            format!(
+                "Two-way exit {} to room {} made.",
+                direction_name, destination
+            )
+        }
+        _ => format!(
+            "Syntax: {} link|room|dig <vnum>, or {} delete",
+            direction_name, direction_name
+        ),
+    };
+    send(players, sender, response);
+}
+
+fn show(vnum: u32, world: &World) -> String {
+    let Some(room) = world.get_room(vnum) else {
+        return format!("There's no room {}.", vnum);
+    };
+    let area = world
+        .room_area(vnum)
+        .and_then(|id| world.get_area(id))
+        .map_or("none", |a| a.metadata.name.as_str());
+    let flags: Vec<String> = RoomFlag::iter()
+        .filter(|f| room.has_flag(*f))
+        .map(|f| f.to_string())
+        .collect();
+
+    let mut exits: Vec<String> = Direction::iter()
+        .filter_map(|d| {
+            room.get_exit(&d.to_string())
+                .map(|to| format!(" -{:<6} to [{}]", d.to_string(), to))
+        })
+        .collect();
+    if exits.is_empty() {
+        exits.push(String::from(" none"));
+    }
+
+    format!(
+        "Name:        [{}]\n\
+         Area:        [{}]\n\
+         Vnum:        [{}]\n\
+         Sector:      [{}]\n\
+         Flags:       [{}]\n\
+         Description:\n{}\n\
+         Exits:\n{}",
+        room.name,
+        area,
+        room.id,
+        room.sector,
+        if flags.is_empty() {
+            String::from("none")
+        } else {
+            flags.join(" ")
+        },
+        room.description.trim_end(),
+        exits.join("\n")
+    )
+}
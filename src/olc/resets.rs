@@ -0,0 +1,324 @@
+// Editing the reset list of the area a builder is standing in. Resets are numbered by their
+// position in the area's list, which is also the order they run in.
+use super::{check_builder, names, parse_number, send};
+use crate::{
+    equipment::WearLocation,
+    message::Direction,
+    player::Players,
+    reset::ResetCommand,
+    world::{AreaId, World},
+};
+use std::str::FromStr;
+
+const COMMANDS: &str = "Reset commands:\n\
+    resets                                 list the resets for this room\n\
+    resets all                             list every reset in the area\n\
+    resets mob <vnum> [world max] [room max]  load a mobile here\n\
+    resets obj <vnum>                      load an object on the floor here\n\
+    resets give <number> <vnum>            give an object to the mobile from reset <number>\n\
+    resets equip <number> <vnum> <slot>    equip the mobile from reset <number> with an object\n\
+    resets delete <number>                 remove a reset, along with what a mobile is given\n\
+    resets run                             reset the area now to see the result";
+
+pub fn edit_resets(sender: u32, argument: Option<&str>, players: &Players, world: &mut World) {
+    let Some(room_id) = players.read().get(&sender).map(|p| p.current_room) else {
+        return;
+    };
+    let Some(area_id) = world.room_area(room_id) else {
+        send(players, sender, String::from("This room isn't in an area."));
+        return;
+    };
+    if !check_builder(players, sender, world, room_id) {
+        return;
+    }
+
+    let words: Vec<&str> = argument.unwrap_or_default().split_whitespace().collect();
+    let response = match words.as_slice() {
+        [] => list(world, area_id, Some(room_id)),
+        ["all"] => list(world, area_id, None),
+        ["run"] => {
+            world.reset_area(area_id);
+            Ok(String::from("Area reset."))
+        }
+        ["mob", vnum, limits @ ..] if limits.len() <= 2 => {
+            add_mobile(world, area_id, room_id, vnum, limits)
+        }
+        ["obj", vnum] => parse_number(vnum, "vnum").and_then(|object_id| {
+            check_object(world, object_id)?;
+            push(
+                world,
+                area_id,
+                None,
+                ResetCommand::LoadObject { object_id, room_id },
+            );
+            Ok(format!(
+                "Object {} will be loaded in room {}.",
+                object_id, room_id
+            ))
+        }),
+        ["give", number, vnum] => parse_number(vnum, "vnum").and_then(|object_id| {
+            let after = mobile_reset(world, area_id, number)?;
+            check_object(world, object_id)?;
+            push(
+                world,
+                area_id,
+                Some(after),
+                ResetCommand::GiveObject { object_id },
+            );
+            Ok(format!("Object {} will be given to the mobile.", object_id))
+        }),
+        ["equip", number, vnum, slot] => parse_number(vnum, "vnum").and_then(|object_id| {
+            let after = mobile_reset(world, area_id, number)?;
+            check_object(world, object_id)?;
+            let wear_location = WearLocation::from_str(slot)
+                .map_err(|_| format!("Wear slots are: {}", names::<WearLocation>()))?;
+            push(
+                world,
+                area_id,
+                Some(after),
+                ResetCommand::EquipObject {
+                    object_id,
+                    wear_location,
+                },
+            );
+            Ok(format!(
+                "Object {} will be equipped on the mobile at {}.",
+                object_id, wear_location
+            ))
+        }),
+        ["delete", number] => delete(world, area_id, number),
+        _ => Err(String::from(COMMANDS)),
+    };
+
+    match response {
+        Ok(response) | Err(response) => send(players, sender, response),
+    }
+}
+
+// The room each reset puts something in. Give and equip resets go with the mobile before them.
+fn reset_rooms(resets: &[ResetCommand]) -> Vec<Option<u32>> {
+    let mut mobile_room = None;
+    resets
+        .iter()
+        .map(|reset| match *reset {
+            ResetCommand::LoadMobile { room_id, .. } => {
+                mobile_room = Some(room_id);
+                mobile_room
+            }
+            ResetCommand::LoadObject { room_id, .. }
+            | ResetCommand::SetDoor { room_id, .. }
+            | ResetCommand::RandomizeExits { room_id, .. } => Some(room_id),
+            ResetCommand::GiveObject { .. } | ResetCommand::EquipObject { .. } => mobile_room,
+            // Wherever the container is
+            ResetCommand::PutObject { .. } => None,
+        })
+        .collect()
+}
+
+fn list(world: &World, area_id: AreaId, room: Option<u32>) -> Result<String, String> {
+    let Some(area) = world.get_area(area_id) else {
+        return Err(String::from("The area is gone."));
+    };
+    let mobile_name = |vnum| {
+        world
+            .get_mobile_template(vnum)
+            .map_or("unknown", |m| m.room_description.as_str())
+    };
+    let object_name = |vnum| {
+        world
+            .get_object_template(vnum)
+            .map_or("unknown", |o| o.short_description.as_str())
+    };
+    let limit = |limit: Option<u32>| limit.map_or(String::from("-"), |l| l.to_string());
+
+    let lines: Vec<String> = area
+        .resets
+        .iter()
+        .zip(reset_rooms(&area.resets))
+        .enumerate()
+        .filter(|(_, (_, reset_room))| room.is_none() || *reset_room == room)
+        .map(|(i, (reset, _))| {
+            let description = match *reset {
+                ResetCommand::LoadMobile {
+                    mobile_id,
+                    room_id,
+                    world_limit,
+                    room_limit,
+                } => format!(
+                    "M mobile {} ({}) in room {}, max {} in world, {} in room",
+                    mobile_id,
+                    mobile_name(mobile_id),
+                    room_id,
+                    limit(world_limit),
+                    limit(room_limit)
+                ),
+                ResetCommand::LoadObject { object_id, room_id } => format!(
+                    "O object {} ({}) in room {}",
+                    object_id,
+                    object_name(object_id),
+                    room_id
+                ),
+                ResetCommand::GiveObject { object_id } => format!(
+                    "  G object {} ({}) in its inventory",
+                    object_id,
+                    object_name(object_id)
+                ),
+                ResetCommand::EquipObject {
+                    object_id,
+                    wear_location,
+                } => format!(
+                    "  E object {} ({}) at {}",
+                    object_id,
+                    object_name(object_id),
+                    wear_location
+                ),
+                ResetCommand::PutObject {
+                    object_id,
+                    container_id,
+                    ..
+                } => format!(
+                    "P object {} ({}) in object {} ({})",
+                    object_id,
+                    object_name(object_id),
+                    container_id,
+                    object_name(container_id)
+                ),
+                ResetCommand::SetDoor {
+                    room_id,
+                    direction,
+                    state,
+                } => format!(
+                    "D door {} of room {} set to {}",
+                    Direction::from_number(direction).map_or(String::from("?"), |d| d.to_string()),
+                    room_id,
+                    match state {
+                        0 => "open",
+                        1 => "closed",
+                        _ => "locked",
+                    }
+                ),
+                ResetCommand::RandomizeExits { room_id, exits } => {
+                    format!("R the first {} exits of room {} shuffled", exits, room_id)
+                }
+            };
+            format!("[{:3}] {}", i + 1, description)
+        })
+        .collect();
+
+    if lines.is_empty() {
+        Ok(String::from("No resets."))
+    } else {
+        Ok(lines.join("\n"))
+    }
+}
+
+fn check_object(world: &World, object_id: u32) -> Result<(), String> {
+    match world.get_object_template(object_id) {
+        Some(_) => Ok(()),
+        None => Err(format!("There's no object {}.", object_id)),
+    }
+}
+
+fn add_mobile(
+    world: &mut World,
+    area_id: AreaId,
+    room_id: u32,
+    vnum: &str,
+    limits: &[&str],
+) -> Result<String, String> {
+    let mobile_id = parse_number(vnum, "vnum")?;
+    if world.get_mobile_template(mobile_id).is_none() {
+        return Err(format!("There's no mobile {}.", mobile_id));
+    }
+    // Like ROM, a mobile reset with no limits only loads one into the world
+    let world_limit = match limits.first() {
+        Some(limit) => Some(parse_number(limit, "limit")?),
+        None => Some(1),
+    };
+    let room_limit = match limits.get(1) {
+        Some(limit) => Some(parse_number(limit, "limit")?),
+        None => None,
+    };
+    push(
+        world,
+        area_id,
+        None,
+        ResetCommand::LoadMobile {
+            mobile_id,
+            room_id,
+            world_limit,
+            room_limit,
+        },
+    );
+    Ok(format!(
+        "Mobile {} will be loaded in room {}.",
+        mobile_id, room_id
+    ))
+}
+
+// The index of a reset that objects can be given to a mobile after: the mobile's own reset, or
+// one of the objects it's already given
+fn mobile_reset(world: &World, area_id: AreaId, number: &str) -> Result<usize, String> {
+    let resets = world
+        .get_area(area_id)
+        .map(|a| a.resets.as_slice())
+        .unwrap_or_default();
+    let index = parse_number::<usize>(number, "reset number")?
+        .checked_sub(1)
+        .filter(|&i| i < resets.len())
+        .ok_or_else(|| String::from("There's no reset with that number."))?;
+
+    let follows_mobile = match resets[index] {
+        ResetCommand::LoadMobile { .. } => true,
+        ResetCommand::GiveObject { .. } | ResetCommand::EquipObject { .. } => {
+            reset_rooms(resets)[index].is_some()
+        }
+        _ => false,
+    };
+    if follows_mobile {
+        Ok(index)
+    } else {
+        Err(format!("Reset {} doesn't load a mobile.", index + 1))
+    }
+}
+
+// Add a reset after the one at `after`, or at the end of the list
+fn push(world: &mut World, area_id: AreaId, after: Option<usize>, reset: ResetCommand) {
+    if let Some(area) = world.get_area_mut(area_id) {
+        match after {
+            Some(index) => area.resets.insert(index + 1, reset),
+            None => area.resets.push(reset),
+        }
+        area.changed = true;
+    }
+}
+
+fn delete(world: &mut World, area_id: AreaId, number: &str) -> Result<String, String> {
+    let Some(area) = world.get_area_mut(area_id) else {
+        return Err(String::from("The area is gone."));
+    };
+    let index = parse_number::<usize>(number, "reset number")?
+        .checked_sub(1)
+        .filter(|&i| i < area.resets.len())
+        .ok_or_else(|| String::from("There's no reset with that number."))?;
+
+    // The give and equip resets after a mobile would end up going to some other mobile, so they
+    // go with it
+    let mut end = index + 1;
+    if let ResetCommand::LoadMobile { .. } = area.resets[index] {
+        while matches!(
+            area.resets.get(end),
+            Some(ResetCommand::GiveObject { .. } | ResetCommand::EquipObject { .. })
+        ) {
+            end += 1;
+        }
+    }
+    area.resets.drain(index..end);
+    area.changed = true;
+
+    Ok(if end - index == 1 {
+        String::from("Reset deleted.")
+    } else {
+        format!("Deleted {} resets.", end - index)
+    })
+}
@@ -0,0 +1,102 @@
+// The line editor, for descriptions and anything else that runs over several lines. Lines are
+// added as they're typed, and dot commands edit what's there so far, like ROM's string editor.
+use super::{send, TextEdit, TextField};
+use crate::{player::Players, world::World};
+
+const HELP: &str = "Line editor commands:\n\
+    .s   show the text so far\n\
+    .c   clear the text\n\
+    .d   delete the last line\n\
+    .h   this help\n\
+    @    finish editing";
+
+pub fn interpret(
+    sender: u32,
+    mut text: TextEdit,
+    input: &str,
+    players: &Players,
+    world: &mut World,
+) {
+    let input = input.trim_end();
+    match input.trim() {
+        "@" => {
+            finish(&text, world);
+            if let Some(player) = players.write().get_mut(&sender) {
+                if let Some(state) = &mut player.olc {
+                    state.text = None;
+                }
+                player.send_message(String::from("Done editing."));
+            }
+            return;
+        }
+        ".s" => {
+            let shown = if text.lines.is_empty() {
+                String::from("(empty)")
+            } else {
+                text.lines
+                    .iter()
+                    .enumerate()
+                    .map(|(i, line)| format!("{:2}] {}", i + 1, line))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            send(players, sender, shown);
+            return;
+        }
+        ".c" => {
+            text.lines.clear();
+            send(players, sender, String::from("Text cleared."));
+        }
+        ".d" => {
+            if text.lines.pop().is_some() {
+                send(players, sender, String::from("Deleted the last line."));
+            } else {
+                send(players, sender, String::from("There's nothing to delete."));
+            }
+        }
+        ".h" => {
+            send(players, sender, String::from(HELP));
+            return;
+        }
+        command if command.starts_with('.') => {
+            send(
+                players,
+                sender,
+                format!("Unknown command {}, try .h", command),
+            );
+            return;
+        }
+        _ => text.lines.push(input.to_string()),
+    }
+
+    if let Some(state) = players
+        .write()
+        .get_mut(&sender)
+        .and_then(|p| p.olc.as_mut())
+    {
+        state.text = Some(text);
+    }
+}
+
+// Put the finished text where it belongs
+fn finish(text: &TextEdit, world: &mut World) {
+    let mut value = text.lines.join("\n");
+    if !value.is_empty() {
+        value.push('\n');
+    }
+
+    match text.field {
+        TextField::RoomDescription(vnum) => {
+            if let Some(room) = world.get_room_mut(vnum) {
+                room.description = value;
+            }
+            world.mark_changed(vnum);
+        }
+        TextField::MobileDescription(vnum) => {
+            if let Some(mobile) = world.get_mobile_template_mut(vnum) {
+                mobile.description = value;
+            }
+            world.mark_changed(vnum);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+// Finding the shortest way between rooms by following exits, for tracking, hunting mobiles and
+// speedwalks. Every exit costs the same and rooms have no coordinates to aim for, so a breadth
+// first search finds the shortest path without needing A*.
+use crate::{
+    message::Direction,
+    mobiles::ActFlag,
+    room::{Room, RoomFlag},
+    world::{AreaId, World},
+};
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+// Which exits a path is allowed to use
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PathOptions {
+    // Whether it can go through doors
+    // TODO: Check whether doors are closed or locked once they can be
+    pub through_doors: bool,
+    // Keep to this area
+    pub area: Option<AreaId>,
+    // Keep out of rooms mobiles can't go in
+    pub avoid_no_mob: bool,
+    // Give up after searching this many rooms
+    pub max_rooms: Option<usize>,
+}
+
+impl PathOptions {
+    // How a mobile can move, going by its flags
+    pub fn mobile(world: &World, mobile_id: u32) -> PathOptions {
+        let Some(mobile) = world.mobiles.get(&mobile_id) else {
+            return PathOptions::default();
+        };
+        let stay_area = mobile.template.act_flags & ActFlag::StayArea.bit() != 0;
+        PathOptions {
+            through_doors: false,
+            area: stay_area
+                .then(|| world.room_area(mobile.current_room))
+                .flatten(),
+            avoid_no_mob: true,
+            max_rooms: Some(MAX_HUNT_ROOMS),
+        }
+    }
+}
+
+// How far mobiles will look for their prey, so a hunt across the whole world doesn't search it
+const MAX_HUNT_ROOMS: usize = 1000;
+
+fn can_enter(world: &World, room: &Room, options: &PathOptions) -> bool {
+    if options.avoid_no_mob && room.has_flag(RoomFlag::NoMob) {
+        return false;
+    }
+    match options.area {
+        Some(area_id) => world.room_area(room.id) == Some(area_id),
+        None => true,
+    }
+}
+
+// The exits that can be taken from a room, in a fixed order so paths come out the same each time
+fn exits<'a>(
+    world: &'a World,
+    room: &'a Room,
+    options: &'a PathOptions,
+) -> impl Iterator<Item = (Direction, u32)> + 'a {
+    let mut exits: Vec<(Direction, u32)> = room
+        .exits
+        .iter()
+        .filter(|(word, _)| {
+            options.through_doors || room.doors.get(*word).is_none_or(|door| door.locks == 0)
+        })
+        .filter_map(|(word, &to)| Some((Direction::from_word(word)?, to)))
+        .collect();
+    exits.sort_by_key(|(direction, _)| direction.number());
+    exits.into_iter().filter(move |&(_, to)| {
+        world
+            .get_room(to)
+            .is_some_and(|r| can_enter(world, r, options))
+    })
+}
+
+// The nearest room that's a goal, and the directions to get there. An empty path means the start
+// room is a goal itself.
+pub fn find_path_to<F>(
+    world: &World,
+    from: u32,
+    options: &PathOptions,
+    is_goal: F,
+) -> Option<(u32, Vec<Direction>)>
+where
+    F: Fn(u32) -> bool,
+{
+    world.get_room(from)?;
+    // How each room was first reached
+    let mut came_from: HashMap<u32, Option<(u32, Direction)>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    came_from.insert(from, None);
+    queue.push_back(from);
+
+    while let Some(room_id) = queue.pop_front() {
+        if is_goal(room_id) {
+            let mut path = Vec::new();
+            let mut at = room_id;
+            while let Some(&Some((previous, direction))) = came_from.get(&at) {
+                path.push(direction);
+                at = previous;
+            }
+            path.reverse();
+            return Some((room_id, path));
+        }
+        if options.max_rooms.is_some_and(|max| came_from.len() >= max) {
+            continue;
+        }
+
+        let Some(room) = world.get_room(room_id) else {
+            continue;
+        };
+        for (direction, to) in exits(world, room, options) {
+            if let Entry::Vacant(entry) = came_from.entry(to) {
+                entry.insert(Some((room_id, direction)));
+                queue.push_back(to);
+            }
+        }
+    }
+    None
+}
+
+pub fn find_path(
+    world: &World,
+    from: u32,
+    to: u32,
+    options: &PathOptions,
+) -> Option<Vec<Direction>> {
+    find_path_to(world, from, options, |room_id| room_id == to).map(|(_, path)| path)
+}
+
+// Write a path the way players type speedwalks, eg "3n2ew"
+pub fn speedwalk(path: &[Direction]) -> String {
+    let mut out = String::new();
+    let mut steps = path.iter().peekable();
+    while let Some(&direction) = steps.next() {
+        let mut count = 1;
+        while steps
+            .next_if(|&&next| next.number() == direction.number())
+            .is_some()
+        {
+            count += 1;
+        }
+        if count > 1 {
+            out.push_str(&count.to_string());
+        }
+        out.extend(direction.to_string().chars().next());
+    }
+    out
+}
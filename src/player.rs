@@ -1,8 +1,25 @@
-use crate::{connection::Prompt, message::GameMessage, world::World};
-use std::collections::HashMap;
+use crate::{
+    affects::{self, Affect},
+    connection::Prompt,
+    creation::{Class, NewCharacter, Race, Sex},
+    equipment::Equipment,
+    message::GameMessage,
+    mobiles::AffectFlag,
+    objects::ObjectInstance,
+    olc::OlcState,
+    position::Position,
+    stats::Stats,
+    world::World,
+};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tokio::sync::mpsc;
 
+mod save;
+mod skills;
+
+pub use save::{is_saved, is_valid_username};
+
 #[derive(Clone, Debug)]
 pub struct Player {
     // TODO: Is this really how we want to handle player IDs?
@@ -16,6 +33,44 @@ pub struct Player {
     // our predicate channel creation, we'd need to know all the other players in the area. Not
     // that we have areas yet...
     pub current_room: u32,
+    // Stats before anything (eg equipment) modifies them
+    pub base_stats: Stats,
+    pub sex: Sex,
+    pub race: Arc<Race>,
+    pub class: Arc<Class>,
+    // From -1000 (evil) to 1000 (good), like ROM
+    pub alignment: i32,
+    pub level: u32,
+    pub exp: u32,
+    pub hit: i32,
+    pub mana: i32,
+    pub movement: i32,
+    pub inventory: Vec<ObjectInstance>,
+    pub equipment: Equipment,
+    pub gold: u32,
+    pub silver: u32,
+    pub flags: u64,
+    pub position: Position,
+    // Set while the player has one of the online building editors open
+    pub olc: Option<OlcState>,
+    // Whether to show a small map beside room descriptions
+    pub automap: bool,
+    // How well the player knows each skill they've learnt, as a percentage
+    pub skills: BTreeMap<String, u32>,
+    // Sessions left for practicing skills at a guild master
+    pub practices: u32,
+    pub affects: Vec<Affect>,
+    // Words that stand for other commands, by the word
+    pub aliases: BTreeMap<String, String>,
+    // Commands typed but not run yet. The game loop runs one a pulse.
+    pub queue: VecDeque<String>,
+    // Pulses before the next queued command runs, like ROM's wait state
+    pub wait: u32,
+    // Who the player is following around, like ROM's master
+    pub master: Option<u32>,
+    // Who leads the group the player is in, unless it's their own (or they aren't in one), like
+    // ROM's leader
+    pub leader: Option<u32>,
 }
 
 impl Player {
@@ -24,16 +79,140 @@ impl Player {
         players: &Players,
         sender: mpsc::UnboundedSender<GameMessage>,
         starting_room: u32,
+        character: NewCharacter,
     ) -> Player {
         let player_id = generate_player_id(players);
-        Player {
+        let base_stats = character.stats;
+        let mut player = Player {
             id: player_id,
             username: username,
             sender,
             current_room: starting_room,
+            base_stats,
+            sex: character.sex,
+            race: character.race,
+            class: character.class,
+            alignment: character.alignment,
+            level: 1,
+            exp: 0,
+            hit: base_stats.max_hit,
+            mana: base_stats.max_mana,
+            movement: base_stats.max_move,
+            inventory: Vec::new(),
+            equipment: Equipment::new(),
+            gold: STARTING_GOLD,
+            silver: 0,
+            flags: 0,
+            position: Position::Standing,
+            olc: None,
+            automap: false,
+            skills: BTreeMap::new(),
+            practices: STARTING_PRACTICES,
+            affects: Vec::new(),
+            aliases: BTreeMap::new(),
+            queue: VecDeque::new(),
+            wait: 0,
+            master: None,
+            leader: None,
+        };
+        player.learn_skills();
+        player
+    }
+
+    // The player's stats with everything they're wearing and affected by taken into account
+    pub fn stats(&self) -> Stats {
+        let mut stats = self.base_stats;
+        for (_, object) in self.equipment.iter() {
+            for affect in &object.template.affects {
+                stats.apply(affect.location, affect.modifier);
+            }
+        }
+        affects::apply(&mut stats, &self.affects);
+        stats
+    }
+
+    // The AffectFlag bits the player has from their affects and what they're wearing
+    pub fn affected_by(&self) -> u64 {
+        let worn = self
+            .equipment
+            .iter()
+            .flat_map(|(_, object)| &object.template.flag_affects)
+            .filter(|affect| affect.target == "A")
+            .fold(0, |bits, affect| bits | affect.bitvector);
+        affects::bits(&self.affects) | worn
+    }
+
+    pub fn is_affected(&self, flag: AffectFlag) -> bool {
+        self.affected_by() & flag.bit() != 0
+    }
+
+    // Make sure current hit points, mana and movement don't exceed their maximums, eg after
+    // removing something that was boosting them
+    pub fn clamp_points(&mut self) {
+        let stats = self.stats();
+        self.hit = self.hit.min(stats.max_hit);
+        self.mana = self.mana.min(stats.max_mana);
+        self.movement = self.movement.min(stats.max_move);
+    }
+
+    // Hold the player up after doing something slow, eg using a skill. Like ROM's WAIT_STATE, this
+    // never shortens a wait that's already longer.
+    pub fn wait_state(&mut self, pulses: u32) {
+        self.wait = self.wait.max(pulses);
+    }
+
+    // Add commands to the end of the queue. If they don't all fit none of them are added, so a
+    // speedwalk doesn't stop halfway for no reason.
+    pub fn queue_commands(&mut self, commands: Vec<String>) -> bool {
+        if self.queue.len() + commands.len() > MAX_QUEUE {
+            return false;
+        }
+        self.queue.extend(commands);
+        true
+    }
+
+    // Players who aren't in anyone else's group lead their own, even if it's just them
+    pub fn group_leader(&self) -> u32 {
+        self.leader.unwrap_or(self.id)
+    }
+
+    pub fn is_same_group(&self, other: &Player) -> bool {
+        self.group_leader() == other.group_leader()
+    }
+
+    pub fn has_flag(&self, flag: PlayerFlag) -> bool {
+        self.flags & flag.bit() != 0
+    }
+
+    // Everything the player has, counted in silver
+    pub fn wealth(&self) -> u32 {
+        self.silver + self.gold * 100
+    }
+
+    // Pay for something in silver, breaking gold into silver if we need to. Callers should check
+    // that the player can afford it first.
+    pub fn deduct_cost(&mut self, cost: u32) {
+        if self.silver >= cost {
+            self.silver -= cost;
+        } else {
+            let gold_needed = (cost - self.silver).div_ceil(100).min(self.gold);
+            self.gold -= gold_needed;
+            self.silver = (self.silver + gold_needed * 100).saturating_sub(cost);
         }
     }
 
+    // Receive an amount in silver, as whole gold coins where possible
+    pub fn add_money(&mut self, amount: u32) {
+        self.gold += amount / 100;
+        self.silver += amount % 100;
+    }
+
+    // Take an object out of the player's inventory by name
+    pub fn take_from_inventory(&mut self, name: &str) -> Option<ObjectInstance> {
+        let index = self.inventory.iter().position(|o| o.matches(name))?;
+        Some(self.inventory.remove(index))
+    }
+
     pub fn send_message(&self, message: String) {
         let _ = self.sender.send(GameMessage::Plain(message));
     }
@@ -53,6 +232,10 @@ impl Player {
     }
 
     pub fn prompt_str(&self, world: &World) -> String {
+        if let Some(olc) = &self.olc {
+            return olc.prompt();
+        }
+
         let exit_str = {
             if let Some(exits) = world.get_player_exits(&self) {
                 // TODO: Global?
@@ -70,10 +253,36 @@ impl Player {
             }
         };
 
-        format!("{} > ", exit_str)
+        format!(
+            "<{}hp {}m {}mv> {} > ",
+            self.hit, self.mana, self.movement, exit_str
+        )
+    }
+}
+
+// Marks on a player that the rest of the world reacts to, using the bit positions of the PLR_*
+// flags in ROM
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayerFlag {
+    Killer = 26,
+    Thief = 27,
+}
+
+impl PlayerFlag {
+    pub fn bit(self) -> u64 {
+        1 << (self as u64)
     }
 }
 
+// How many commands a player can have waiting to run
+const MAX_QUEUE: usize = 50;
+
+// Same as ROM gives new characters
+const STARTING_PRACTICES: u32 = 5;
+
+// A little money so new players can afford some basic equipment
+const STARTING_GOLD: u32 = 20;
+
 // newtype for ease of use
 // We can derive Clone for free because it's a wrapper around Arc
 #[derive(Clone)]
@@ -0,0 +1,136 @@
+// What's kept of a player between sessions, in a TOML file per player in the player directory
+use super::Player;
+use crate::{
+    affects::Affect,
+    creation::{CreationData, Sex},
+    stats::Stats,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
+struct PlayerFile {
+    sex: Option<Sex>,
+    race: Option<String>,
+    class: Option<String>,
+    alignment: Option<i32>,
+    stats: Option<Stats>,
+    level: Option<u32>,
+    exp: Option<u32>,
+    practices: Option<u32>,
+    skills: BTreeMap<String, u32>,
+    affects: Vec<Affect>,
+    aliases: BTreeMap<String, String>,
+}
+
+// Usernames name the player's file, so they can only have the characters that are safe in a file
+// name. Names that only differ in case share a file, and so are the same player.
+pub fn is_valid_username(username: &str) -> bool {
+    !username.is_empty()
+        && username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn file_path(directory: &Path, username: &str) -> io::Result<PathBuf> {
+    if !is_valid_username(username) {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "the username can't be used as a file name",
+        ));
+    }
+    Ok(directory
+        .join(username.to_lowercase())
+        .with_extension("toml"))
+}
+
+// Whether the player has been here before, so doesn't need to create a character
+pub fn is_saved(directory: &Path, username: &str) -> bool {
+    file_path(directory, username).is_ok_and(|path| path.exists())
+}
+
+fn invalid(error: impl ToString) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, error.to_string())
+}
+
+impl Player {
+    // Pick up what was saved last time, if the player has been here before. Races and classes are
+    // saved by name and looked up in the data files.
+    pub fn load(&mut self, directory: &Path, data: &CreationData) -> io::Result<()> {
+        let path = file_path(directory, &self.username)?;
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error),
+        };
+        let file: PlayerFile = toml::from_str(&text).map_err(invalid)?;
+        // Files from before something was saved keep what a new player gets
+        if let Some(sex) = file.sex {
+            self.sex = sex;
+        }
+        if let Some(name) = file.race {
+            match data.race(&name) {
+                Some(race) => self.race = race,
+                None => tracing::warn!(race = name, "Saved player has an unknown race"),
+            }
+        }
+        if let Some(name) = file.class {
+            match data.class(&name) {
+                Some(class) => self.class = class,
+                None => tracing::warn!(class = name, "Saved player has an unknown class"),
+            }
+        }
+        if let Some(alignment) = file.alignment {
+            self.alignment = alignment;
+        }
+        if let Some(stats) = file.stats {
+            // Current points aren't saved, so they start full
+            self.base_stats = stats;
+            self.hit = stats.max_hit;
+            self.mana = stats.max_mana;
+            self.movement = stats.max_move;
+        }
+        if let Some(level) = file.level {
+            self.level = level;
+        }
+        if let Some(exp) = file.exp {
+            self.exp = exp;
+        }
+        if let Some(practices) = file.practices {
+            self.practices = practices;
+        }
+        self.skills = file.skills;
+        self.affects = file.affects;
+        self.aliases = file.aliases;
+        self.learn_skills();
+        Ok(())
+    }
+
+    // Written to a temporary file and moved into place, like areas are
+    pub fn save(&self, directory: &Path) -> io::Result<()> {
+        let path = file_path(directory, &self.username)?;
+        let file = PlayerFile {
+            sex: Some(self.sex),
+            race: Some(self.race.name.clone()),
+            class: Some(self.class.name.clone()),
+            alignment: Some(self.alignment),
+            stats: Some(self.base_stats),
+            level: Some(self.level),
+            exp: Some(self.exp),
+            practices: Some(self.practices),
+            skills: self.skills.clone(),
+            affects: self.affects.clone(),
+            aliases: self.aliases.clone(),
+        };
+        let text = toml::to_string_pretty(&file).map_err(invalid)?;
+
+        fs::create_dir_all(directory)?;
+        let temp_path = path.with_extension("toml.tmp");
+        fs::write(&temp_path, text)?;
+        fs::rename(&temp_path, &path)
+    }
+}
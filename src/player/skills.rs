@@ -0,0 +1,51 @@
+// How well a player knows their skills and spells
+use super::Player;
+use crate::skills::{self, Skill, SKILLS};
+
+impl Player {
+    // The level the player can first use a skill at, if they can at all. Their race's skills can be
+    // used from the start, whatever their class.
+    pub fn skill_level(&self, skill: &Skill) -> Option<u32> {
+        if self.race.skills.iter().any(|name| name == skill.name) {
+            return Some(1);
+        }
+        self.class.skills.get(skill.name).copied()
+    }
+
+    // How well the player knows a skill, as a percentage. Skills they can't use yet count as not
+    // known at all.
+    pub fn proficiency(&self, skill: &Skill) -> u32 {
+        match self.skill_level(skill) {
+            Some(level) if level <= self.level => self.skills.get(skill.name).copied().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    // Start the player off in every skill they can use by their level, like ROM does for new
+    // characters. They'll need to practice before they're any good.
+    pub fn learn_skills(&mut self) {
+        for skill in SKILLS {
+            if self
+                .skill_level(skill)
+                .is_some_and(|level| level <= self.level)
+            {
+                self.skills.entry(String::from(skill.name)).or_insert(1);
+            }
+        }
+    }
+
+    // Using a skill might make the player better at it. Returns what to tell them if it did.
+    pub fn improve_skill(&mut self, skill: &Skill, success: bool) -> Option<String> {
+        let learned = self.proficiency(skill);
+        let improved = skills::improve(learned, success, self.stats().intelligence, self.level)?;
+        self.skills.insert(String::from(skill.name), improved);
+        Some(if success {
+            format!("You have become better at {}!", skill.name)
+        } else {
+            format!(
+                "You learn from your mistakes, and your {} skill improves.",
+                skill.name
+            )
+        })
+    }
+}
@@ -0,0 +1,71 @@
+// How a character is placed: standing, sitting, asleep and so on. They're in the same order as
+// ROM's POS_* constants, so a command's minimum position can be checked with a comparison.
+// TODO: Nothing can be hurt or fight yet, so nothing ends up dead, stunned or fighting. Mobiles
+// can still start that way if their area file says so.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Position {
+    Dead,
+    #[strum(serialize = "mortally wounded")]
+    Mortal,
+    Incapacitated,
+    Stunned,
+    Sleeping,
+    Resting,
+    Sitting,
+    Fighting,
+    #[default]
+    Standing,
+}
+
+impl Position {
+    // Parse a position the way area files write them, eg "stand" or "sleep"
+    pub fn from_word(word: &str) -> Option<Position> {
+        match word.to_lowercase().as_str() {
+            "dead" => Some(Position::Dead),
+            "mort" | "mortal" => Some(Position::Mortal),
+            "incap" | "incapacitated" => Some(Position::Incapacitated),
+            "stun" | "stunned" => Some(Position::Stunned),
+            "sleep" | "sleeping" => Some(Position::Sleeping),
+            "rest" | "resting" => Some(Position::Resting),
+            "sit" | "sitting" => Some(Position::Sitting),
+            "fight" | "fighting" => Some(Position::Fighting),
+            "stand" | "standing" => Some(Position::Standing),
+            _ => None,
+        }
+    }
+
+    pub fn is_awake(self) -> bool {
+        self > Position::Sleeping
+    }
+
+    // How a character in this position looks to others in the room, eg "is resting here."
+    pub fn room_text(self) -> &'static str {
+        match self {
+            Position::Dead => "is DEAD!!",
+            Position::Mortal => "is mortally wounded.",
+            Position::Incapacitated => "is incapacitated.",
+            Position::Stunned => "is lying here stunned.",
+            Position::Sleeping => "is sleeping here.",
+            Position::Resting => "is resting here.",
+            Position::Sitting => "is sitting here.",
+            Position::Fighting => "is here, fighting.",
+            Position::Standing => "is here.",
+        }
+    }
+
+    // What a player in this position is told when they try something they can't do in it. These
+    // are ROM's.
+    pub fn refusal(self) -> &'static str {
+        match self {
+            Position::Dead => "Lie still; you are DEAD.",
+            Position::Mortal | Position::Incapacitated => "You are hurt far too bad for that.",
+            Position::Stunned => "You are too stunned to do that.",
+            Position::Sleeping => "In your dreams, or what?",
+            Position::Resting => "Nah... You feel too relaxed...",
+            Position::Sitting => "Better stand up first.",
+            Position::Fighting => "No way!  You are still fighting!",
+            Position::Standing => "You can't do that right now.",
+        }
+    }
+}
@@ -0,0 +1,493 @@
+// MOBprograms let builders script how mobiles react to what goes on around them, without needing
+// a special procedure compiled into the server. They're compatible with the ones from ROM's OLC:
+// programs are loaded from #MOBPROGS, and mobiles list the triggers that run them. Objects and
+// rooms have programs of their own in #OBJPROGS and #ROOMPROGS, as in the OLC releases that added
+// them to ROM.
+use crate::{
+    character::{can_see, CharacterId},
+    objects::ObjectInstance,
+    player::Players,
+    random::number_percent,
+    world::World,
+};
+use serde::{Deserialize, Serialize};
+
+mod interpreter;
+
+use interpreter::{Context, Execution, Owner};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MobProgram {
+    pub vnum: u32,
+    pub code: String,
+}
+
+// What a program is for. Each kind has its own vnums, so mobile program 3001 and room program 3001
+// are different programs.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, strum_macros::Display, strum_macros::EnumIter,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum ProgramKind {
+    Mobile,
+    Object,
+    Room,
+}
+
+impl ProgramKind {
+    // The area file section the programs are in
+    pub fn section(self) -> &'static str {
+        match self {
+            ProgramKind::Mobile => "MOBPROGS",
+            ProgramKind::Object => "OBJPROGS",
+            ProgramKind::Room => "ROOMPROGS",
+        }
+    }
+}
+
+// The names here are what area files use, see trigger_table in ROM's tables.c
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    Deserialize,
+    Serialize,
+)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerType {
+    Act,
+    Speech,
+    Random,
+    Fight,
+    Hpcnt,
+    Death,
+    Entry,
+    Greet,
+    Grall,
+    Give,
+    Bribe,
+    Kill,
+    Delay,
+    Surrender,
+    Exit,
+    Exall,
+    // Only objects have these
+    Get,
+    Drop,
+    Sac,
+    Wear,
+    Remove,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProgramTrigger {
+    pub trigger: TriggerType,
+    pub program: u32,
+    // What the trigger fires on. Depending on the type this is some text to listen for, a percent
+    // chance, an amount of money or an object.
+    pub phrase: String,
+}
+
+impl ProgramTrigger {
+    fn chance(&self) -> bool {
+        number_percent() <= self.phrase.trim().parse::<i32>().unwrap_or(0)
+    }
+}
+
+// The triggers of each mobile in a room that match some predicate
+fn mobile_triggers<F>(world: &World, room_id: u32, predicate: F) -> Vec<(u32, ProgramTrigger)>
+where
+    F: Fn(&ProgramTrigger) -> bool,
+{
+    world
+        .mobiles
+        .values()
+        .filter(|m| m.current_room == room_id)
+        .flat_map(|m| {
+            m.template
+                .triggers
+                .iter()
+                .filter(|t| predicate(t))
+                .map(|t| (m.id, t.clone()))
+        })
+        .collect()
+}
+
+// For a room, the objects lying in it, and the objects carried by some of the players in it, the
+// first trigger that matches some predicate. The room goes first, like in ROM.
+fn place_triggers<F>(
+    players: &Players,
+    world: &World,
+    room_id: u32,
+    carriers: &[u32],
+    predicate: F,
+) -> Vec<(Owner, ProgramTrigger)>
+where
+    F: Fn(&ProgramTrigger) -> bool,
+{
+    let Some(room) = world.get_room(room_id) else {
+        return Vec::new();
+    };
+    let first = |triggers: &[ProgramTrigger]| triggers.iter().find(|t| predicate(t)).cloned();
+
+    let mut result = Vec::new();
+    if let Some(trigger) = first(&room.triggers) {
+        result.push((Owner::Room(room_id), trigger));
+    }
+    let guard = players.read();
+    let carried = carriers
+        .iter()
+        .filter_map(|id| guard.get(id))
+        .flat_map(|p| p.inventory.iter().chain(p.equipment.iter().map(|(_, o)| o)));
+    for object in room.objects.iter().chain(carried) {
+        if let Some(trigger) = first(&object.template.triggers) {
+            result.push((Owner::Object(Box::new(object.clone()), room_id), trigger));
+        }
+    }
+    result
+}
+
+fn run(trigger: &ProgramTrigger, context: Context, players: &Players, world: &mut World) {
+    tracing::debug!(
+        owner = %context.owner,
+        program = trigger.program,
+        "Running {} trigger",
+        trigger.trigger
+    );
+    Execution::new(context, players, world).start(trigger.program, world);
+}
+
+fn player_room(players: &Players, player_id: u32) -> Option<u32> {
+    players.read().get(&player_id).map(|p| p.current_room)
+}
+
+// A player said something in a room. Speech triggers fire if the phrase appears anywhere in what
+// they said.
+pub fn speech_trigger(players: &Players, world: &mut World, speaker: u32, speech: &str) {
+    let Some(room_id) = player_room(players, speaker) else {
+        return;
+    };
+    let speech = speech.to_lowercase();
+    let heard = |t: &ProgramTrigger| {
+        t.trigger == TriggerType::Speech && speech.contains(&t.phrase.to_lowercase())
+    };
+
+    for (mobile_id, trigger) in mobile_triggers(world, room_id, heard) {
+        run(
+            &trigger,
+            Context::new(Owner::Mobile(mobile_id), Some(speaker)),
+            players,
+            world,
+        );
+    }
+    // The room, what's lying around and what the speaker is carrying
+    for (owner, trigger) in place_triggers(players, world, room_id, &[speaker], heard) {
+        run(&trigger, Context::new(owner, Some(speaker)), players, world);
+    }
+}
+
+// A player walked into a room. Each mobile, the room and each object lying in it run at most one
+// greet program.
+pub fn greet_trigger(players: &Players, world: &mut World, player_id: u32) {
+    let Some(room_id) = player_room(players, player_id) else {
+        return;
+    };
+    let triggers = mobile_triggers(world, room_id, |t| {
+        matches!(t.trigger, TriggerType::Greet | TriggerType::Grall)
+    });
+    // Like ROM, greet (rather than grall) programs are only run by mobiles that can see the player
+    // and aren't busy, ie are where the area put them
+    let triggers: Vec<_> = triggers
+        .into_iter()
+        .filter(|(mobile_id, trigger)| {
+            trigger.trigger == TriggerType::Grall
+                || (world
+                    .mobiles
+                    .get(mobile_id)
+                    .is_some_and(|m| m.position == m.default_position())
+                    && can_see(
+                        players,
+                        world,
+                        CharacterId::Mobile(*mobile_id),
+                        CharacterId::Player(player_id),
+                    ))
+        })
+        .collect();
+
+    let mut greeted = Vec::new();
+    for (mobile_id, trigger) in triggers {
+        if greeted.contains(&mobile_id) || !trigger.chance() {
+            continue;
+        }
+        greeted.push(mobile_id);
+        run(
+            &trigger,
+            Context::new(Owner::Mobile(mobile_id), Some(player_id)),
+            players,
+            world,
+        );
+    }
+
+    // Rooms and objects can't see, so for them greet and grall are the same
+    let triggers = place_triggers(players, world, room_id, &[], |t| {
+        matches!(t.trigger, TriggerType::Greet | TriggerType::Grall) && t.chance()
+    });
+    for (owner, trigger) in triggers {
+        run(
+            &trigger,
+            Context::new(owner, Some(player_id)),
+            players,
+            world,
+        );
+    }
+}
+
+// The first of a mobile's triggers of some type whose percent chance comes up, like the trigger
+// picking part of ROM's mp_percent_trigger
+fn percent_trigger(world: &World, mobile_id: u32, trigger: TriggerType) -> Option<ProgramTrigger> {
+    world.mobiles.get(&mobile_id).and_then(|mobile| {
+        mobile
+            .template
+            .triggers
+            .iter()
+            .find(|t| t.trigger == trigger && t.chance())
+            .cloned()
+    })
+}
+
+// Run a mobile's first trigger of some type that comes up, returning true if one did
+fn run_percent_trigger(
+    players: &Players,
+    world: &mut World,
+    mobile_id: u32,
+    actor: Option<u32>,
+    trigger: TriggerType,
+) -> bool {
+    let Some(trigger) = percent_trigger(world, mobile_id, trigger) else {
+        return false;
+    };
+    run(
+        &trigger,
+        Context::new(Owner::Mobile(mobile_id), actor),
+        players,
+        world,
+    );
+    true
+}
+
+// Run on the mobile pulse. Only bothers if there's a player around to see it, and returns true if
+// the mobile did something.
+pub fn random_trigger(players: &Players, world: &mut World, mobile_id: u32) -> bool {
+    let Some(mobile) = world.mobiles.get(&mobile_id) else {
+        return false;
+    };
+    let room_id = mobile.current_room;
+    if !players.read().values().any(|p| p.current_room == room_id) {
+        return false;
+    }
+
+    run_percent_trigger(players, world, mobile_id, None, TriggerType::Random)
+}
+
+// A mobile moved into a room by itself, eg while hunting someone
+pub fn entry_trigger(players: &Players, world: &mut World, mobile_id: u32) {
+    run_percent_trigger(players, world, mobile_id, None, TriggerType::Entry);
+}
+
+// A player hurt a mobile without killing it. ROM runs these every round of combat, and until we
+// have rounds every hit will have to do.
+pub fn fight_trigger(players: &Players, world: &mut World, mobile_id: u32, attacker: u32) {
+    run_percent_trigger(
+        players,
+        world,
+        mobile_id,
+        Some(attacker),
+        TriggerType::Fight,
+    );
+}
+
+// A player killed a mobile, which gets to do one last thing before it's gone
+pub fn death_trigger(players: &Players, world: &mut World, mobile_id: u32, killer: u32) {
+    run_percent_trigger(players, world, mobile_id, Some(killer), TriggerType::Death);
+}
+
+// A player handed a mobile an object. The phrase is either the object's vnum, a keyword or "all".
+pub fn give_trigger(
+    players: &Players,
+    world: &mut World,
+    player_id: u32,
+    mobile_id: u32,
+    object: &ObjectInstance,
+) {
+    let Some(mobile) = world.mobiles.get(&mobile_id) else {
+        return;
+    };
+    let trigger = mobile
+        .template
+        .triggers
+        .iter()
+        .find(|t| {
+            let phrase = t.phrase.trim();
+            t.trigger == TriggerType::Give
+                && match phrase.parse::<u32>() {
+                    Ok(vnum) => vnum == object.template.id,
+                    Err(_) => phrase.eq_ignore_ascii_case("all") || object.matches(phrase),
+                }
+        })
+        .cloned();
+
+    if let Some(trigger) = trigger {
+        let mut context = Context::new(Owner::Mobile(mobile_id), Some(player_id));
+        context.object = Some(object.clone());
+        run(&trigger, context, players, world);
+    }
+}
+
+// A player handed a mobile some money, counted in silver. Fires if it's at least the amount in the
+// phrase.
+pub fn bribe_trigger(
+    players: &Players,
+    world: &mut World,
+    player_id: u32,
+    mobile_id: u32,
+    amount: u32,
+) {
+    let Some(mobile) = world.mobiles.get(&mobile_id) else {
+        return;
+    };
+    let trigger = mobile
+        .template
+        .triggers
+        .iter()
+        .find(|t| {
+            t.trigger == TriggerType::Bribe
+                && t.phrase.trim().parse::<u32>().is_ok_and(|a| amount >= a)
+        })
+        .cloned();
+
+    if let Some(trigger) = trigger {
+        run(
+            &trigger,
+            Context::new(Owner::Mobile(mobile_id), Some(player_id)),
+            players,
+            world,
+        );
+    }
+}
+
+// A player is about to leave a room. The phrase is the number of the direction (0 for north
+// through 5 for down, as in the area files). Returns true if a program ran, in which case the
+// player stays where they are.
+pub fn exit_trigger(players: &Players, world: &mut World, player_id: u32, direction: u8) -> bool {
+    let Some(room_id) = player_room(players, player_id) else {
+        return false;
+    };
+    let leaving = |t: &ProgramTrigger| {
+        matches!(t.trigger, TriggerType::Exit | TriggerType::Exall)
+            && t.phrase.trim().parse::<u8>() == Ok(direction)
+    };
+    let triggers = mobile_triggers(world, room_id, leaving);
+    // As with greet, plain exit programs need the mobile to see the player
+    let seen = |mobile_id: u32| {
+        can_see(
+            players,
+            world,
+            CharacterId::Mobile(mobile_id),
+            CharacterId::Player(player_id),
+        )
+    };
+    let found = triggers
+        .into_iter()
+        .find(|(mobile_id, t)| t.trigger == TriggerType::Exall || seen(*mobile_id))
+        .map(|(mobile_id, t)| (Owner::Mobile(mobile_id), t));
+    // Then the room and the objects in it
+    let found = found.or_else(|| {
+        place_triggers(players, world, room_id, &[], leaving)
+            .into_iter()
+            .next()
+    });
+    let Some((owner, trigger)) = found else {
+        return false;
+    };
+
+    run(
+        &trigger,
+        Context::new(owner, Some(player_id)),
+        players,
+        world,
+    );
+    true
+}
+
+// Run on the mobile pulse for rooms with players in them, for the room's random program and those
+// of the objects in it and carried by the players there
+pub fn room_random_trigger(players: &Players, world: &mut World, room_id: u32) {
+    let carriers: Vec<u32> = players
+        .read()
+        .values()
+        .filter(|p| p.current_room == room_id)
+        .map(|p| p.id)
+        .collect();
+    let triggers = place_triggers(players, world, room_id, &carriers, |t| {
+        t.trigger == TriggerType::Random && t.chance()
+    });
+    for (owner, trigger) in triggers {
+        run(&trigger, Context::new(owner, None), players, world);
+    }
+}
+
+// Something a player did to an object, like picking it up or wearing it
+pub fn object_trigger(
+    players: &Players,
+    world: &mut World,
+    player_id: u32,
+    object: &ObjectInstance,
+    trigger: TriggerType,
+) {
+    let Some(room_id) = player_room(players, player_id) else {
+        return;
+    };
+    let found = object
+        .template
+        .triggers
+        .iter()
+        .find(|t| t.trigger == trigger && t.chance())
+        .cloned();
+    if let Some(found) = found {
+        let mut context = Context::new(
+            Owner::Object(Box::new(object.clone()), room_id),
+            Some(player_id),
+        );
+        context.object = Some(object.clone());
+        run(&found, context, players, world);
+    }
+}
+
+// A player dropped an object, which both it and the room it landed in can react to
+pub fn drop_trigger(players: &Players, world: &mut World, player_id: u32, object: &ObjectInstance) {
+    object_trigger(players, world, player_id, object, TriggerType::Drop);
+
+    let Some(room_id) = player_room(players, player_id) else {
+        return;
+    };
+    let found = world.get_room(room_id).and_then(|room| {
+        room.triggers
+            .iter()
+            .find(|t| t.trigger == TriggerType::Drop && t.chance())
+            .cloned()
+    });
+    if let Some(found) = found {
+        let mut context = Context::new(Owner::Room(room_id), Some(player_id));
+        context.object = Some(object.clone());
+        run(&found, context, players, world);
+    }
+}
+
+// TODO: Hpcnt, kill and surrender triggers need mobiles that fight back, sac triggers need a way to
+// sacrifice objects, and act and delay triggers aren't hooked up yet either. They're loaded so
+// areas that use them still work.
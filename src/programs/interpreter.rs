@@ -0,0 +1,746 @@
+// Running MOBprograms, following program_flow and friends in ROM's mob_prog.c. A program is a list
+// of lines, each of which is control flow (if/or/and/else/endif/break), a `mob` command (or `obj`
+// or `room` for the programs of objects and rooms) or something the mobile does like `say`.
+use crate::{
+    actions::{capitalize, send_targeted_message, LookAction, PlayerAction},
+    equipment::Equipment,
+    objects::ObjectInstance,
+    player::Players,
+    programs::{ProgramKind, TriggerType},
+    random::{number_percent, number_range},
+    world::World,
+};
+use std::fmt;
+
+// Everything a single trigger runs, including any programs it calls, shares this budget so a bad
+// program can't hold up the game loop
+const MAX_INSTRUCTIONS: u32 = 1000;
+// How deep `mob call` can go
+const MAX_CALL_LEVEL: u32 = 5;
+// How deep ifs can be nested in a single program
+const MAX_NESTED_LEVEL: usize = 12;
+
+// What's running a program
+#[derive(Clone)]
+pub enum Owner {
+    // A MobileInstance
+    Mobile(u32),
+    // Objects don't keep an identity of their own as they're passed around, so their programs get
+    // a copy of the object and the room it was in when the trigger fired
+    Object(Box<ObjectInstance>, u32),
+    Room(u32),
+}
+
+impl Owner {
+    pub fn kind(&self) -> ProgramKind {
+        match self {
+            Owner::Mobile(_) => ProgramKind::Mobile,
+            Owner::Object(..) => ProgramKind::Object,
+            Owner::Room(_) => ProgramKind::Room,
+        }
+    }
+
+    fn mobile(&self) -> Option<u32> {
+        match *self {
+            Owner::Mobile(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Owner::Mobile(id) => write!(f, "mobile {}", id),
+            Owner::Object(object, _) => write!(f, "object {}", object.template.id),
+            Owner::Room(id) => write!(f, "room {}", id),
+        }
+    }
+}
+
+// Who and what a program is running for
+pub struct Context {
+    pub owner: Owner,
+    // The player who set off the trigger ($n)
+    pub actor: Option<u32>,
+    // An object involved in the trigger, eg one that was given to the mobile ($o)
+    pub object: Option<ObjectInstance>,
+}
+
+impl Context {
+    pub fn new(owner: Owner, actor: Option<u32>) -> Self {
+        Context {
+            owner,
+            actor,
+            object: None,
+        }
+    }
+}
+
+pub struct Execution<'a> {
+    context: Context,
+    players: &'a Players,
+    // A random player in the room ($r), picked when the trigger fires
+    random_player: Option<u32>,
+    instructions: u32,
+}
+
+// Something a program can refer to, eg with $n
+#[derive(Clone, Copy, PartialEq)]
+enum Target {
+    Player(u32),
+    Mobile(u32),
+}
+
+// One level of if
+struct Block {
+    // Whether the code around the if is running
+    parent_active: bool,
+    condition: bool,
+    in_else: bool,
+}
+
+impl Block {
+    fn active(&self) -> bool {
+        self.parent_active && self.condition != self.in_else
+    }
+}
+
+type ProgramResult<T> = Result<T, String>;
+
+fn split_word(line: &str) -> (&str, &str) {
+    let line = line.trim();
+    match line.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (line, ""),
+    }
+}
+
+fn parse_vnum(word: &str) -> ProgramResult<u32> {
+    word.parse()
+        .map_err(|_| format!("'{}' isn't a valid vnum", word))
+}
+
+// Checks like `hour > 12` end with a comparison
+fn compare(value: i64, operator: &str, operand: &str) -> ProgramResult<bool> {
+    let operand: i64 = operand
+        .parse()
+        .map_err(|_| format!("'{}' isn't a number", operand))?;
+    Ok(match operator {
+        "==" => value == operand,
+        "!=" => value != operand,
+        ">" => value > operand,
+        "<" => value < operand,
+        ">=" => value >= operand,
+        "<=" => value <= operand,
+        _ => return Err(format!("Unknown operator '{}'", operator)),
+    })
+}
+
+impl<'a> Execution<'a> {
+    pub fn new(context: Context, players: &'a Players, world: &World) -> Self {
+        let mut execution = Execution {
+            context,
+            players,
+            random_player: None,
+            instructions: 0,
+        };
+        execution.random_player = execution.room(world).and_then(|room_id| {
+            let in_room: Vec<u32> = players
+                .read()
+                .values()
+                .filter(|p| p.current_room == room_id)
+                .map(|p| p.id)
+                .collect();
+            if in_room.is_empty() {
+                None
+            } else {
+                Some(in_room[number_range(0, in_room.len() as i32 - 1) as usize])
+            }
+        });
+        execution
+    }
+
+    pub fn start(&mut self, program: u32, world: &mut World) {
+        if let Err(error) = self.run(program, world, 0) {
+            tracing::warn!(
+                owner = %self.context.owner,
+                program,
+                "Program failed: {}",
+                error
+            );
+        }
+    }
+
+    fn run(&mut self, program: u32, world: &mut World, call_level: u32) -> ProgramResult<()> {
+        if call_level > MAX_CALL_LEVEL {
+            return Err(String::from("Too many nested calls"));
+        }
+        let kind = self.context.owner.kind();
+        let Some(code) = world.get_program(kind, program).map(|p| p.code.clone()) else {
+            return Err(format!("Unknown program {}", program));
+        };
+
+        let mut blocks: Vec<Block> = Vec::new();
+        for (number, line) in code.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('*') {
+                continue;
+            }
+            self.instructions += 1;
+            if self.instructions > MAX_INSTRUCTIONS {
+                return Err(format!("Ran out of instructions in program {}", program));
+            }
+
+            let result = self.line(line, &mut blocks, world, call_level);
+            match result {
+                Ok(true) => {}
+                // A break
+                Ok(false) => return Ok(()),
+                Err(error) => {
+                    return Err(format!(
+                        "Program {} line {}: {}",
+                        program,
+                        number + 1,
+                        error
+                    ))
+                }
+            }
+        }
+
+        if blocks.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Program {} is missing an endif", program))
+        }
+    }
+
+    // Run a single line, returning false if the program should stop here
+    fn line(
+        &mut self,
+        line: &str,
+        blocks: &mut Vec<Block>,
+        world: &mut World,
+        call_level: u32,
+    ) -> ProgramResult<bool> {
+        let active = blocks.last().is_none_or(Block::active);
+        let (keyword, rest) = split_word(line);
+
+        match keyword.to_lowercase().as_str() {
+            "if" => {
+                if blocks.len() >= MAX_NESTED_LEVEL {
+                    return Err(String::from("Ifs are nested too deeply"));
+                }
+                let condition = active && self.check(rest, world)?;
+                blocks.push(Block {
+                    parent_active: active,
+                    condition,
+                    in_else: false,
+                });
+            }
+            "or" => {
+                let block = blocks.last_mut().ok_or("or without an if")?;
+                if block.parent_active && !block.in_else && !block.condition {
+                    block.condition = self.check(rest, world)?;
+                }
+            }
+            "and" => {
+                let block = blocks.last_mut().ok_or("and without an if")?;
+                if block.parent_active && !block.in_else && block.condition {
+                    block.condition = self.check(rest, world)?;
+                }
+            }
+            "else" => {
+                let block = blocks.last_mut().ok_or("else without an if")?;
+                block.in_else = true;
+            }
+            "endif" => {
+                blocks.pop().ok_or("endif without an if")?;
+            }
+            "break" => return Ok(!active),
+            _ if active => self.command(line, world, call_level)?,
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn resolve(&self, word: &str, world: &World) -> Option<Target> {
+        match word {
+            "$n" => self.context.actor.map(Target::Player),
+            "$i" => self.context.owner.mobile().map(Target::Mobile),
+            "$r" => self.random_player.map(Target::Player),
+            name => {
+                // Anything else is looked up by name in the mobile's room
+                let room_id = self.room(world)?;
+                let player = self.players.read().values().find_map(|p| {
+                    (p.current_room == room_id && p.username.eq_ignore_ascii_case(name))
+                        .then_some(p.id)
+                });
+                player.map(Target::Player).or_else(|| {
+                    world
+                        .mobiles
+                        .values()
+                        .find(|m| m.current_room == room_id && m.matches(name))
+                        .map(|m| Target::Mobile(m.id))
+                })
+            }
+        }
+    }
+
+    fn room(&self, world: &World) -> Option<u32> {
+        match self.context.owner {
+            Owner::Mobile(id) => world.mobiles.get(&id).map(|m| m.current_room),
+            Owner::Object(_, room_id) | Owner::Room(room_id) => Some(room_id),
+        }
+    }
+
+    // The keyword and the short description of whatever is running the program, for $i and $I
+    fn owner_names(&self, world: &World) -> (String, String) {
+        match &self.context.owner {
+            Owner::Mobile(id) => world.mobiles.get(id).map_or_else(Default::default, |m| {
+                let keyword = m.template.keywords.first().cloned().unwrap_or_default();
+                (keyword, m.template.room_description.clone())
+            }),
+            Owner::Object(object, _) => (
+                object
+                    .template
+                    .keywords
+                    .first()
+                    .cloned()
+                    .unwrap_or_default(),
+                object.template.short_description.clone(),
+            ),
+            Owner::Room(id) => {
+                let name = world
+                    .get_room(*id)
+                    .map(|r| r.name.clone())
+                    .unwrap_or_default();
+                (name.clone(), name)
+            }
+        }
+    }
+
+    fn name(&self, target: Target, world: &World) -> String {
+        match target {
+            Target::Player(id) => self
+                .players
+                .read()
+                .get(&id)
+                .map(|p| p.username.clone())
+                .unwrap_or_default(),
+            Target::Mobile(id) => world
+                .mobiles
+                .get(&id)
+                .map(|m| m.template.room_description.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    // Evaluate the condition of an if, or or and. See cmd_eval in ROM's mob_prog.c for the full
+    // list, of which this is the part that makes sense for us so far.
+    fn check(&self, expression: &str, world: &World) -> ProgramResult<bool> {
+        let words: Vec<&str> = expression.split_whitespace().collect();
+        let Some((check, args)) = words.split_first() else {
+            return Err(String::from("Empty check"));
+        };
+        let check = check.to_lowercase();
+        let check = check.as_str();
+        let room_id = self.room(world).ok_or("Not in the world")?;
+        let arg = |index: usize| -> ProgramResult<&str> {
+            args.get(index)
+                .copied()
+                .ok_or_else(|| format!("Missing argument for {}", check))
+        };
+
+        let result = match check {
+            "rand" => {
+                let percent: i32 = arg(0)?.parse().map_err(|_| "rand needs a number")?;
+                number_percent() <= percent
+            }
+            "hour" => compare(i64::from(world.time.hour), arg(0)?, arg(1)?)?,
+            "people" | "players" | "mobs" => {
+                let players = self
+                    .players
+                    .read()
+                    .values()
+                    .filter(|p| p.current_room == room_id)
+                    .count();
+                // Not counting the mobile itself
+                let mobiles = world
+                    .mobiles
+                    .values()
+                    .filter(|m| {
+                        m.current_room == room_id && Some(m.id) != self.context.owner.mobile()
+                    })
+                    .count();
+                let count = match check {
+                    "people" => players + mobiles,
+                    "players" => players,
+                    _ => mobiles,
+                };
+                compare(count as i64, arg(0)?, arg(1)?)?
+            }
+            "objhere" => {
+                let name = arg(0)?;
+                world.get_room(room_id).is_some_and(|room| {
+                    room.objects.iter().any(|o| match name.parse::<u32>() {
+                        Ok(vnum) => o.template.id == vnum,
+                        Err(_) => o.matches(name),
+                    })
+                })
+            }
+            "mobhere" => {
+                let name = arg(0)?;
+                world.mobiles.values().any(|m| {
+                    m.current_room == room_id
+                        && Some(m.id) != self.context.owner.mobile()
+                        && match name.parse::<u32>() {
+                            Ok(vnum) => m.template.id == vnum,
+                            Err(_) => m.matches(name),
+                        }
+                })
+            }
+            // The rest are about a particular character
+            _ => {
+                let target = self.resolve(arg(0)?, world);
+                self.check_target(check, target, &args[1..], world)?
+            }
+        };
+        Ok(result)
+    }
+
+    fn check_target(
+        &self,
+        check: &str,
+        target: Option<Target>,
+        args: &[&str],
+        world: &World,
+    ) -> ProgramResult<bool> {
+        let arg = |index: usize| -> ProgramResult<&str> {
+            args.get(index)
+                .copied()
+                .ok_or_else(|| format!("Missing argument for {}", check))
+        };
+        let matches_object = |o: &ObjectInstance, name: &str| match name.parse::<u32>() {
+            Ok(vnum) => o.template.id == vnum,
+            Err(_) => o.matches(name),
+        };
+
+        let result = match (check, target) {
+            ("exists", target) => target.is_some(),
+            ("ispc", target) => matches!(target, Some(Target::Player(_))),
+            ("isnpc", target) => matches!(target, Some(Target::Mobile(_))),
+            ("name", Some(target)) => {
+                let name = arg(0)?;
+                match target {
+                    Target::Player(_) => self.name(target, world).eq_ignore_ascii_case(name),
+                    Target::Mobile(id) => world.mobiles.get(&id).is_some_and(|m| m.matches(name)),
+                }
+            }
+            ("carries" | "wears", Some(Target::Player(id))) => {
+                let name = arg(0)?;
+                self.players.read().get(&id).is_some_and(|p| {
+                    p.equipment.iter().any(|(_, o)| matches_object(o, name))
+                        || (check == "carries"
+                            && p.inventory.iter().any(|o| matches_object(o, name)))
+                })
+            }
+            ("carries" | "wears", Some(Target::Mobile(id))) => {
+                let name = arg(0)?;
+                world.mobiles.get(&id).is_some_and(|m| {
+                    m.equipment.iter().any(|(_, o)| matches_object(o, name))
+                        || (check == "carries"
+                            && m.inventory.iter().any(|o| matches_object(o, name)))
+                })
+            }
+            ("money", Some(target)) => {
+                let money = match target {
+                    Target::Player(id) => self.players.read().get(&id).map_or(0, |p| p.wealth()),
+                    Target::Mobile(id) => world
+                        .mobiles
+                        .get(&id)
+                        .map_or(0, |m| m.silver + m.gold * 100),
+                };
+                compare(i64::from(money), arg(0)?, arg(1)?)?
+            }
+            ("name" | "carries" | "wears" | "money", None) => false,
+            (check, _) => return Err(format!("Unknown check '{}'", check)),
+        };
+        Ok(result)
+    }
+
+    // Fill in the $ codes in some text, see expand_arg in ROM's mob_prog.c
+    fn expand(&self, text: &str, world: &World) -> String {
+        let (keyword, short_description) = self.owner_names(world);
+        let mut expanded = String::with_capacity(text.len());
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c != '$' {
+                expanded.push(c);
+                continue;
+            }
+            let Some(code) = chars.next() else {
+                expanded.push(c);
+                break;
+            };
+            let replacement = match code {
+                'i' => keyword.clone(),
+                'I' => short_description.clone(),
+                'n' | 'N' => self
+                    .context
+                    .actor
+                    .map(|id| self.name(Target::Player(id), world))
+                    .unwrap_or_else(|| String::from("someone")),
+                'r' | 'R' => self
+                    .random_player
+                    .map(|id| self.name(Target::Player(id), world))
+                    .unwrap_or_else(|| String::from("someone")),
+                'o' => self
+                    .context
+                    .object
+                    .as_ref()
+                    .and_then(|o| o.template.keywords.first().cloned())
+                    .unwrap_or_else(|| String::from("something")),
+                'O' => self
+                    .context
+                    .object
+                    .as_ref()
+                    .map(|o| o.template.short_description.clone())
+                    .unwrap_or_else(|| String::from("something")),
+                // TODO: Pronouns for mobiles, whose sex is still just the word from the area file
+                'e' | 'm' | 's' => {
+                    let pronouns = self
+                        .context
+                        .actor
+                        .and_then(|id| self.players.read().get(&id).map(|p| p.sex.pronouns()));
+                    let (subject, object, possessive) =
+                        pronouns.unwrap_or(("they", "them", "their"));
+                    String::from(match code {
+                        'e' => subject,
+                        'm' => object,
+                        _ => possessive,
+                    })
+                }
+                'j' => String::from("it"),
+                'k' => String::from("it"),
+                'l' => String::from("its"),
+                '$' => String::from("$"),
+                other => {
+                    tracing::warn!(owner = %self.context.owner, "Unknown code ${}", other);
+                    String::new()
+                }
+            };
+            expanded.push_str(&replacement);
+        }
+        expanded
+    }
+
+    fn send_room_message(&self, room_id: u32, except: Option<u32>, message: String) {
+        send_targeted_message(self.players, message, |&(id, player)| {
+            player.current_room == room_id && Some(*id) != except
+        });
+    }
+
+    // A mobile the program moved runs its entry program, which counts towards this program's
+    // budget so that mobiles sending each other back and forth can't go on forever
+    fn entry(&mut self, mobile_id: u32, world: &mut World, call_level: u32) -> ProgramResult<()> {
+        let Some(trigger) = super::percent_trigger(world, mobile_id, TriggerType::Entry) else {
+            return Ok(());
+        };
+        let context = Context::new(Owner::Mobile(mobile_id), None);
+        let mut nested = Execution::new(context, self.players, world);
+        nested.instructions = self.instructions;
+        let result = nested.run(trigger.program, world, call_level + 1);
+        self.instructions = nested.instructions;
+        result
+    }
+
+    // A program command, or for mobiles something they do as if they were a player typing a command
+    fn command(&mut self, line: &str, world: &mut World, call_level: u32) -> ProgramResult<()> {
+        let (keyword, rest) = split_word(line);
+        let keyword = keyword.to_lowercase();
+        let prefix = match self.context.owner.kind() {
+            ProgramKind::Mobile => "mob",
+            ProgramKind::Object => "obj",
+            ProgramKind::Room => "room",
+        };
+        if keyword == prefix {
+            return self.mob_command(rest, world, call_level);
+        }
+        let Some(mobile_id) = self.context.owner.mobile() else {
+            return Err(format!("Unknown command '{}'", keyword));
+        };
+        let room_id = self.room(world).ok_or("Not in the world")?;
+        let name = capitalize(&self.name(Target::Mobile(mobile_id), world));
+
+        match keyword.as_str() {
+            "say" => {
+                let message = format!("{} says '{}'", name, self.expand(rest, world));
+                self.send_room_message(room_id, None, message);
+            }
+            "emote" => {
+                let message = format!("{} {}", name, self.expand(rest, world));
+                self.send_room_message(room_id, None, message);
+            }
+            // TODO: ROM lets programs use any command the mobile could type, which needs the
+            // command interpreter to work for mobiles as well
+            other => return Err(format!("Unknown command '{}'", other)),
+        }
+        Ok(())
+    }
+
+    // The `mob` commands that only programs can use, see mob_cmd.c in ROM. Objects and rooms get
+    // the ones that don't need a body.
+    fn mob_command(&mut self, line: &str, world: &mut World, call_level: u32) -> ProgramResult<()> {
+        let (command, rest) = split_word(line);
+        let room_id = self.room(world).ok_or("Not in the world")?;
+        let mobile_id = self.context.owner.mobile();
+        let bodily = matches!(command.to_lowercase().as_str(), "hunt" | "goto" | "junk");
+        if bodily && mobile_id.is_none() {
+            return Err(format!("Only mobiles can {}", command));
+        }
+
+        match command.to_lowercase().as_str() {
+            "echo" => {
+                let message = self.expand(rest, world);
+                self.send_room_message(room_id, None, message);
+            }
+            "echoat" | "echoaround" => {
+                let (target, text) = split_word(rest);
+                let Some(Target::Player(target_id)) = self.resolve(target, world) else {
+                    return Ok(());
+                };
+                let message = self.expand(text, world);
+                if command.eq_ignore_ascii_case("echoat") {
+                    if let Some(player) = self.players.read().get(&target_id) {
+                        player.send_message(message);
+                    }
+                } else {
+                    self.send_room_message(room_id, Some(target_id), message);
+                }
+            }
+            "transfer" => {
+                let (target, destination) = split_word(rest);
+                let destination = match destination {
+                    "" => room_id,
+                    vnum => parse_vnum(vnum)?,
+                };
+                if world.get_room(destination).is_none() {
+                    return Err(format!("No room {} to transfer to", destination));
+                }
+
+                let moving: Vec<u32> = if target.eq_ignore_ascii_case("all") {
+                    self.players
+                        .read()
+                        .values()
+                        .filter(|p| p.current_room == room_id)
+                        .map(|p| p.id)
+                        .collect()
+                } else {
+                    match self.resolve(target, world) {
+                        Some(Target::Player(id)) => vec![id],
+                        Some(Target::Mobile(id)) => {
+                            if let Some(mobile) = world.mobiles.get_mut(&id) {
+                                mobile.current_room = destination;
+                            }
+                            self.entry(id, world, call_level)?;
+                            Vec::new()
+                        }
+                        None => Vec::new(),
+                    }
+                };
+                for player_id in moving {
+                    if let Some(player) = self.players.write().get_mut(&player_id) {
+                        player.move_to_room(destination);
+                    }
+                    LookAction { sender: player_id }.perform(self.players, world);
+                }
+            }
+            // Follow a player's trail, or stop if there's nobody to hunt
+            "hunt" => {
+                let hunting = match self.resolve(rest, world) {
+                    Some(Target::Player(id)) => Some(id),
+                    _ => None,
+                };
+                if let Some(mobile) = mobile_id.and_then(|id| world.mobiles.get_mut(&id)) {
+                    mobile.hunting = hunting;
+                }
+            }
+            "goto" => {
+                let destination = parse_vnum(rest)?;
+                if world.get_room(destination).is_none() {
+                    return Err(format!("No room {} to go to", destination));
+                }
+                let Some(mobile_id) = mobile_id else {
+                    return Ok(());
+                };
+                if let Some(mobile) = world.mobiles.get_mut(&mobile_id) {
+                    mobile.current_room = destination;
+                }
+                self.entry(mobile_id, world, call_level)?;
+            }
+            "oload" => {
+                let (vnum, location) = split_word(rest);
+                let Some(object) = world.create_object(parse_vnum(vnum)?) else {
+                    return Err(format!("No object {} to load", vnum));
+                };
+                // Objects and rooms have nowhere else to put it
+                let mobile = mobile_id.and_then(|id| world.mobiles.get_mut(&id));
+                match mobile {
+                    Some(mobile) if !location.eq_ignore_ascii_case("room") => {
+                        mobile.inventory.push(object)
+                    }
+                    _ => {
+                        if let Some(room) = world.get_room_mut(room_id) {
+                            room.objects.push(object);
+                        }
+                    }
+                }
+            }
+            "mload" => {
+                let vnum = parse_vnum(rest)?;
+                if world.spawn_mobile(vnum, room_id).is_none() {
+                    return Err(format!("No mobile {} to load", vnum));
+                }
+            }
+            "junk" => {
+                let Some(mobile) = mobile_id.and_then(|id| world.mobiles.get_mut(&id)) else {
+                    return Ok(());
+                };
+                if rest.eq_ignore_ascii_case("all") {
+                    mobile.inventory.clear();
+                    mobile.equipment = Equipment::new();
+                } else if let Some(index) = mobile.inventory.iter().position(|o| o.matches(rest)) {
+                    mobile.inventory.remove(index);
+                } else if let Some(location) = mobile.equipment.find(rest) {
+                    mobile.equipment.unequip(location);
+                }
+            }
+            "purge" => {
+                if rest.is_empty() {
+                    // Everything in the room except the mobile and any players
+                    world
+                        .mobiles
+                        .retain(|&id, m| Some(id) == mobile_id || m.current_room != room_id);
+                    if let Some(room) = world.get_room_mut(room_id) {
+                        room.objects.clear();
+                    }
+                } else if let Some(Target::Mobile(id)) = self.resolve(rest, world) {
+                    if Some(id) != mobile_id {
+                        world.mobiles.remove(&id);
+                    }
+                } else if let Some(room) = world.get_room_mut(room_id) {
+                    if let Some(index) = room.objects.iter().position(|o| o.matches(rest)) {
+                        room.objects.remove(index);
+                    }
+                }
+            }
+            "call" => {
+                let (vnum, _) = split_word(rest);
+                self.run(parse_vnum(vnum)?, world, call_level + 1)?;
+            }
+            other => return Err(format!("Unknown mob command '{}'", other)),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+// Random numbers for game mechanics, along the lines of the number_* helpers in ROM. This doesn't
+// need to be anything fancy, so a xorshift generator seeded from the clock will do.
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+thread_local! {
+    static STATE: Cell<u64> = Cell::new(seed());
+}
+
+fn seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    // The state must never be zero
+    nanos | 1
+}
+
+fn next() -> u64 {
+    STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+        x
+    })
+}
+
+// A number between from and to, inclusive
+pub fn number_range(from: i32, to: i32) -> i32 {
+    if to <= from {
+        return from;
+    }
+    let span = (to - from) as u64 + 1;
+    from + (next() % span) as i32
+}
+
+// A number with the given number of random bits
+pub fn number_bits(width: u32) -> u32 {
+    (next() & ((1 << width) - 1)) as u32
+}
+
+// A number between 1 and 100, for percent chances
+pub fn number_percent() -> i32 {
+    number_range(1, 100)
+}
@@ -0,0 +1,87 @@
+// Recovering hit points, mana and movement each tick, along the lines of hit_gain, mana_gain and
+// move_gain in ROM's update.c. How much comes back depends on the character's level and stats,
+// how they're resting, and the room's heal and mana rates.
+use crate::{
+    mobiles::{AffectFlag, MobileInstance},
+    player::Player,
+    position::Position,
+};
+
+// Players recover hit points and mana fully while asleep, and much more slowly on their feet
+fn player_rate(gain: i32, position: Position) -> i32 {
+    match position {
+        Position::Sleeping => gain,
+        Position::Resting => gain / 2,
+        Position::Fighting => gain / 6,
+        _ => gain / 4,
+    }
+}
+
+// Mobiles recover faster than players to begin with, so the position matters less
+fn mobile_rate(gain: i32, position: Position) -> i32 {
+    match position {
+        Position::Sleeping => gain * 3 / 2,
+        Position::Resting => gain,
+        Position::Fighting => gain / 3,
+        _ => gain / 2,
+    }
+}
+
+// Scale a gain by one of the room's rates, which are percentages
+fn room_rate(gain: i32, rate: i32) -> i32 {
+    gain * rate / 100
+}
+
+// Add a gain to some points without going over their maximum, or taking anything away from
+// characters who are over it, eg from something they're wearing
+fn recover(current: &mut i32, gain: i32, max: i32) {
+    if *current < max {
+        *current = (*current + gain.max(0)).min(max);
+    }
+}
+
+impl Player {
+    // Recover for a tick, in a room with the given heal and mana rates
+    pub fn regenerate(&mut self, heal_rate: i32, mana_rate: i32) {
+        let stats = self.stats();
+        let level = self.level as i32;
+
+        // TODO: Hunger, thirst, poison and the fast healing and meditation skills all change these
+        // in ROM, once we have them
+        let hit = (stats.constitution - 3 + level / 2).max(3);
+        let hit = room_rate(player_rate(hit, self.position), heal_rate);
+
+        let mana = (stats.wisdom + stats.intelligence + level) / 2;
+        let mana = room_rate(player_rate(mana, self.position), mana_rate);
+
+        let movement = level.max(15)
+            + match self.position {
+                Position::Sleeping => stats.dexterity,
+                Position::Resting => stats.dexterity / 2,
+                _ => 0,
+            };
+        let movement = room_rate(movement, heal_rate);
+
+        recover(&mut self.hit, hit, stats.max_hit);
+        recover(&mut self.mana, mana, stats.max_mana);
+        recover(&mut self.movement, movement, stats.max_move);
+    }
+}
+
+impl MobileInstance {
+    pub fn regenerate(&mut self, heal_rate: i32, mana_rate: i32) {
+        let level = self.template.level as i32;
+
+        let mut hit = 5 + level;
+        if self.is_affected(AffectFlag::Regeneration) {
+            hit *= 2;
+        }
+        let hit = room_rate(mobile_rate(hit, self.position), heal_rate);
+        let mana = room_rate(mobile_rate(5 + level, self.position), mana_rate);
+        let movement = room_rate(level, heal_rate);
+
+        recover(&mut self.hit, hit, self.max_hit);
+        recover(&mut self.mana, mana, self.max_mana);
+        recover(&mut self.movement, movement, self.max_move);
+    }
+}
@@ -1,5 +1,50 @@
-#[derive(Copy, Clone, Debug)]
-pub struct ResetCommand {
-    pub mobile_id: u32,
-    pub room_id: u32,
+use crate::equipment::WearLocation;
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ResetCommand {
+    // Load a mobile into a room, unless there are already enough of them in the world or that room
+    LoadMobile {
+        mobile_id: u32,
+        room_id: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        world_limit: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        room_limit: Option<u32>,
+    },
+    // Load an object onto the floor of a room, unless there's one there already
+    LoadObject {
+        object_id: u32,
+        room_id: u32,
+    },
+    // Give an object to the most recently loaded mobile
+    GiveObject {
+        object_id: u32,
+    },
+    // Equip an object on the most recently loaded mobile
+    EquipObject {
+        object_id: u32,
+        wear_location: WearLocation,
+    },
+    // Put an object inside the most recently loaded copy of a container
+    PutObject {
+        object_id: u32,
+        container_id: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        world_limit: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        count: Option<u32>,
+    },
+    // Open, close or lock a door
+    SetDoor {
+        room_id: u32,
+        direction: u8,
+        state: u8,
+    },
+    // Shuffle the first few exits of a room, for mazes
+    RandomizeExits {
+        room_id: u32,
+        exits: u8,
+    },
 }
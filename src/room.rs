@@ -1,15 +1,67 @@
 use crate::{
+    area_file::sorted_map,
     mobiles::MobileInstance,
+    objects::ObjectInstance,
     player::{Player, Players},
+    programs::ProgramTrigger,
     world::World,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use strum::IntoEnumIterator;
 
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Room {
     pub id: u32,
     pub name: String,
     pub description: String,
+    #[serde(serialize_with = "sorted_map")]
     pub exits: HashMap<String, u32>,
+    // Objects lying on the floor
+    #[serde(skip)]
+    pub objects: Vec<ObjectInstance>,
+    pub flags: u64,
+    pub sector: Sector,
+    // Details of the exits that have more to them than where they go, by direction
+    #[serde(serialize_with = "sorted_map")]
+    pub doors: HashMap<String, Door>,
+    pub extra_descriptions: Vec<ExtraDescription>,
+    // Percentages of the normal hit point and mana regeneration for anyone in the room
+    pub heal_rate: i32,
+    pub mana_rate: i32,
+    // Who the room is restricted to, if anyone
+    pub clan: String,
+    pub owner: String,
+    // Events that run room programs
+    pub triggers: Vec<ProgramTrigger>,
+}
+
+// The parts of an exit from a ROM area file we don't do anything with yet, kept so that the area can
+// be written back out
+// TODO: Open, close and lock doors
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Door {
+    pub description: String,
+    pub keyword: String,
+    // The ROM lock value: 0 for no door, 1 for a door, 2 for a pickproof door and so on
+    pub locks: i64,
+    pub key: i64,
+}
+
+// Something extra that can be looked at, eg a sign in a room
+// TODO: Let players look at these
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExtraDescription {
+    pub keyword: String,
+    pub description: String,
+}
+
+impl Default for Room {
+    fn default() -> Self {
+        Room::new(0, "", "")
+    }
 }
 
 impl Room {
@@ -19,9 +71,23 @@ impl Room {
             name: name.to_string(),
             description: description.to_string(),
             exits: HashMap::new(),
+            objects: Vec::new(),
+            flags: 0,
+            sector: Sector::Inside,
+            doors: HashMap::new(),
+            extra_descriptions: Vec::new(),
+            heal_rate: 100,
+            mana_rate: 100,
+            clan: String::new(),
+            owner: String::new(),
+            triggers: Vec::new(),
         }
     }
 
+    pub fn has_flag(&self, flag: RoomFlag) -> bool {
+        self.flags & flag.bit() != 0
+    }
+
     pub fn add_exit(&mut self, direction: &str, destination_id: u32) {
         self.exits.insert(direction.to_string(), destination_id);
     }
@@ -29,6 +95,7 @@ impl Room {
     pub fn remove_exit(&mut self, direction: &str) {
         // TODO: Not sure if we really need this
         self.exits.remove(direction);
+        self.doors.remove(direction);
     }
 
     pub fn get_exit(&self, direction: &str) -> Option<&u32> {
@@ -55,6 +122,75 @@ impl Room {
     }
 }
 
+// Room properties, using the bit positions of the ROOM_* flags in ROM
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum RoomFlag {
+    Dark = 0,
+    NoMob = 2,
+    Indoors = 3,
+    Private = 9,
+    Safe = 10,
+    Solitary = 11,
+    PetShop = 12,
+    NoRecall = 13,
+    ImpOnly = 14,
+    GodsOnly = 15,
+    HeroesOnly = 16,
+    NewbiesOnly = 17,
+    Law = 18,
+    Nowhere = 19,
+}
+
+impl RoomFlag {
+    pub fn bit(self) -> u64 {
+        1 << (self as u64)
+    }
+}
+
+// The terrain of a room, numbered as the SECT_* values in ROM
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+    Deserialize,
+    Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Sector {
+    Inside = 0,
+    City = 1,
+    Field = 2,
+    Forest = 3,
+    Hills = 4,
+    Mountain = 5,
+    WaterSwim = 6,
+    WaterNoSwim = 7,
+    Air = 9,
+    Desert = 10,
+}
+
+impl Sector {
+    pub fn from_rom(value: i64) -> Option<Sector> {
+        Sector::iter().find(|s| *s as i64 == value)
+    }
+}
+
 pub fn get_sample_rooms() -> Vec<Room> {
     let mut rooms = Vec::new();
 
@@ -0,0 +1,58 @@
+use crate::objects::{ExtraFlag, ItemType, Object, ObjectInstance};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Shop {
+    // The vnum of the mobile running the shop
+    pub keeper: u32,
+    // The kinds of items the keeper will buy from players
+    pub buy_types: Vec<ItemType>,
+    // Percentages applied to an object's cost when players buy and sell
+    pub profit_buy: i32,
+    pub profit_sell: i32,
+    pub open_hour: u32,
+    pub close_hour: u32,
+}
+
+impl Shop {
+    // Why the shop won't trade at the given hour, if it's closed
+    pub fn closed_message(&self, hour: u32) -> Option<&'static str> {
+        if hour < self.open_hour {
+            Some("Sorry, I am closed. Come back later.")
+        } else if hour > self.close_hour {
+            Some("Sorry, I am closed. Come back tomorrow.")
+        } else {
+            None
+        }
+    }
+
+    // What a player pays for an object, in silver
+    pub fn selling_price(&self, object: &Object) -> u32 {
+        price(object.cost, self.profit_buy)
+    }
+
+    // What the keeper will pay a player for an object in silver, given what they're already
+    // carrying. Zero means they aren't interested.
+    pub fn buying_price(&self, object: &Object, stock: &[ObjectInstance]) -> u32 {
+        if !self.buy_types.contains(&object.item_type) {
+            return 0;
+        }
+
+        // Like ROM, the more the keeper already has of something the less they'll pay for it
+        let mut price = price(object.cost, self.profit_sell);
+        for stocked in stock.iter().filter(|o| o.template.id == object.id) {
+            if stocked.template.has_flag(ExtraFlag::Inventory) {
+                price /= 2;
+            } else {
+                price = price * 3 / 4;
+            }
+        }
+        price
+    }
+}
+
+// A percentage of an object's cost. Builders can set costs big enough to overflow an i32 when
+// they're multiplied, and anything that doesn't fit isn't for sale.
+fn price(cost: i32, percent: i32) -> u32 {
+    u32::try_from(i64::from(cost) * i64::from(percent) / 100).unwrap_or(0)
+}
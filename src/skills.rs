@@ -0,0 +1,197 @@
+// Skills and spells, in a table along the lines of skill_table in ROM's const.c. Each says what it
+// can be used on, what it costs and how long it holds the character up, while the level each class
+// learns it at is in the class data files. Spells also have a function that does what they do,
+// while skills are used by their own commands (eg track).
+use crate::{
+    character::CharacterId,
+    player::Players,
+    position::Position,
+    random::{number_percent, number_range},
+    world::World,
+};
+
+mod spells;
+
+// What a spell or skill can be used on, like ROM's TAR_* constants
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetType {
+    // Nobody in particular
+    Ignore,
+    // Someone to hurt
+    CharOffensive,
+    // Someone to help, or the caster if nobody is named
+    CharDefensive,
+    // Only the caster
+    CharSelf,
+}
+
+// Everything a spell needs to know when it's cast
+pub struct Cast {
+    pub skill: &'static Skill,
+    pub level: u32,
+    pub caster: CharacterId,
+    pub target: Option<CharacterId>,
+}
+
+pub type SpellFunction = fn(&Cast, &Players, &mut World);
+
+pub struct Skill {
+    pub name: &'static str,
+    pub target: TargetType,
+    // How awake the character has to be to use it
+    pub min_position: Position,
+    pub spell: Option<SpellFunction>,
+    // The least mana it can cost. It costs more for characters close to the level they learnt it.
+    pub min_mana: i32,
+    // Pulses using it holds the character up for
+    pub beats: u32,
+    // What its damage is called in messages, eg "Your magic missile hits the hound."
+    pub noun: &'static str,
+    // What characters are told when it wears off
+    pub msg_off: &'static str,
+}
+
+impl Skill {
+    // What it costs a character of the given level to cast, like ROM's do_cast. The skill level is
+    // when the character could first use it.
+    pub fn mana_cost(&self, skill_level: Option<u32>, level: u32) -> i32 {
+        let Some(skill_level) = skill_level else {
+            return self.min_mana;
+        };
+        let over = level.saturating_sub(skill_level) as i32;
+        (100 / (2 + over)).max(self.min_mana)
+    }
+}
+
+pub const SKILLS: &[Skill] = &[
+    Skill {
+        name: "armor",
+        target: TargetType::CharDefensive,
+        min_position: Position::Standing,
+        spell: Some(spells::spell_armor),
+        min_mana: 5,
+        beats: 12,
+        noun: "",
+        msg_off: "You feel less armored.",
+    },
+    Skill {
+        name: "blindness",
+        target: TargetType::CharOffensive,
+        min_position: Position::Fighting,
+        spell: Some(spells::spell_blindness),
+        min_mana: 5,
+        beats: 12,
+        noun: "",
+        msg_off: "You can see again.",
+    },
+    Skill {
+        name: "cure light",
+        target: TargetType::CharDefensive,
+        min_position: Position::Fighting,
+        spell: Some(spells::spell_cure_light),
+        min_mana: 10,
+        beats: 12,
+        noun: "",
+        msg_off: "",
+    },
+    Skill {
+        name: "detect invis",
+        target: TargetType::CharSelf,
+        min_position: Position::Standing,
+        spell: Some(spells::spell_detect_invis),
+        min_mana: 5,
+        beats: 12,
+        noun: "",
+        msg_off: "You no longer see invisible objects.",
+    },
+    Skill {
+        name: "invisibility",
+        target: TargetType::CharDefensive,
+        min_position: Position::Standing,
+        spell: Some(spells::spell_invisibility),
+        min_mana: 5,
+        beats: 12,
+        noun: "",
+        msg_off: "You are no longer invisible.",
+    },
+    Skill {
+        name: "magic missile",
+        target: TargetType::CharOffensive,
+        min_position: Position::Fighting,
+        spell: Some(spells::spell_magic_missile),
+        min_mana: 15,
+        beats: 12,
+        noun: "magic missile",
+        msg_off: "",
+    },
+    Skill {
+        name: "sanctuary",
+        target: TargetType::CharDefensive,
+        min_position: Position::Standing,
+        spell: Some(spells::spell_sanctuary),
+        min_mana: 75,
+        beats: 12,
+        noun: "",
+        msg_off: "The white aura around your body fades.",
+    },
+    Skill {
+        name: "track",
+        target: TargetType::Ignore,
+        min_position: Position::Standing,
+        spell: None,
+        min_mana: 0,
+        beats: 2,
+        noun: "",
+        msg_off: "",
+    },
+];
+
+pub fn lookup(name: &str) -> Option<&'static Skill> {
+    SKILLS.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+// The first skill whose name starts with what was typed that passes a check, eg that the
+// character knows it
+pub fn find(name: &str, usable: impl Fn(&Skill) -> bool) -> Option<&'static Skill> {
+    let name = name.to_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+    SKILLS
+        .iter()
+        .find(|s| s.name.starts_with(&name) && usable(s))
+}
+
+// How well classes can get at a skill by practicing. The rest comes from using it.
+pub const ADEPT: u32 = 75;
+
+// How quickly characters learn, by intelligence, from int_app in ROM's const.c
+const LEARN_RATES: [i32; 26] = [
+    3, 5, 7, 8, 9, 10, 11, 12, 13, 15, 17, 19, 22, 25, 28, 31, 34, 37, 40, 44, 49, 55, 60, 70, 80,
+    85,
+];
+
+pub fn learn_rate(intelligence: i32) -> i32 {
+    LEARN_RATES[intelligence.clamp(0, 25) as usize]
+}
+
+// Whether using a skill made the character better at it, like ROM's check_improve. Successes are
+// more likely to teach something the less the character knows, and failures the more they know.
+// Returns how well they know it now if they improved.
+pub fn improve(learned: u32, success: bool, intelligence: i32, level: u32) -> Option<u32> {
+    if learned == 0 || learned >= 100 {
+        return None;
+    }
+    let chance = 10 * learn_rate(intelligence) / 4 + level as i32;
+    if number_range(1, 1000) > chance {
+        return None;
+    }
+    let learned = learned as i32;
+    if success {
+        let chance = (100 - learned).clamp(5, 95);
+        (number_percent() < chance).then_some(learned as u32 + 1)
+    } else {
+        let chance = (learned / 2).clamp(5, 30);
+        (number_percent() < chance).then(|| (learned + number_range(1, 3)).min(100) as u32)
+    }
+}
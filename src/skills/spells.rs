@@ -0,0 +1,224 @@
+// What each spell does, like the spell_* functions in ROM's magic.c
+use super::Cast;
+use crate::{
+    actions::send_targeted_message,
+    affects::{self, is_affected_by, Affect},
+    character::CharacterId,
+    fight,
+    mobiles::AffectFlag,
+    player::Players,
+    random::number_range,
+    stats::Apply,
+    world::World,
+};
+
+// Tell the caster something happened to someone else. ROM just says "Ok." for most spells.
+fn tell_caster(cast: &Cast, target: CharacterId, players: &Players, message: String) {
+    if cast.caster != target {
+        cast.caster.send_message(players, message);
+    }
+}
+
+// Tell everyone else in the target's room, like act with TO_ROOM
+fn tell_room(target: CharacterId, players: &Players, world: &World, message: String) {
+    let Some(room_id) = target.room(players, world) else {
+        return;
+    };
+    send_targeted_message(players, message, |&(id, p)| {
+        p.current_room == room_id && target != CharacterId::Player(*id)
+    });
+}
+
+// Put an affect on the target unless the spell is already on them. Returns whether it was added,
+// or None if the target has gone.
+fn add_affect(
+    cast: &Cast,
+    target: CharacterId,
+    players: &Players,
+    world: &mut World,
+    affect: Affect,
+) -> Option<bool> {
+    target.with_affects(players, world, |list| {
+        if is_affected_by(list, cast.skill.name) {
+            return false;
+        }
+        affects::add(list, affect);
+        true
+    })
+}
+
+pub fn spell_armor(cast: &Cast, players: &Players, world: &mut World) {
+    let target = cast.target.unwrap_or(cast.caster);
+    let name = target.capitalized_name(players, world);
+    let affect = Affect {
+        skill: String::from(cast.skill.name),
+        duration: 24,
+        location: Apply::ArmorClass,
+        modifier: -20,
+        bits: 0,
+    };
+    let added = add_affect(cast, target, players, world, affect);
+    match added {
+        Some(true) => {
+            target.send_message(players, String::from("You feel someone protecting you."));
+            tell_caster(
+                cast,
+                target,
+                players,
+                format!("{} is protected by your magic.", name),
+            );
+        }
+        Some(false) if target == cast.caster => {
+            target.send_message(players, String::from("You are already armored."));
+        }
+        Some(false) => {
+            cast.caster
+                .send_message(players, format!("{} is already armored.", name));
+        }
+        None => {}
+    }
+}
+
+pub fn spell_cure_light(cast: &Cast, players: &Players, world: &mut World) {
+    let target = cast.target.unwrap_or(cast.caster);
+    let heal = number_range(1, 8) + cast.level as i32 / 3;
+    match target {
+        CharacterId::Player(id) => {
+            if let Some(player) = players.write().get_mut(&id) {
+                player.hit = (player.hit + heal).min(player.stats().max_hit);
+            }
+        }
+        CharacterId::Mobile(id) => {
+            if let Some(mobile) = world.mobiles.get_mut(&id) {
+                mobile.hit = (mobile.hit + heal).min(mobile.max_hit);
+            }
+        }
+    }
+    target.send_message(players, String::from("You feel better!"));
+    tell_caster(cast, target, players, String::from("Ok."));
+}
+
+pub fn spell_detect_invis(cast: &Cast, players: &Players, world: &mut World) {
+    let target = cast.target.unwrap_or(cast.caster);
+    let bit = AffectFlag::DetectInvis.bit();
+    let affect = Affect {
+        skill: String::from(cast.skill.name),
+        duration: cast.level as i32,
+        location: Apply::None,
+        modifier: 0,
+        bits: bit,
+    };
+    let added = target.with_affects(players, world, |list| {
+        if affects::bits(list) & bit != 0 {
+            return false;
+        }
+        affects::add(list, affect);
+        true
+    });
+    match added {
+        Some(true) => target.send_message(players, String::from("Your eyes tingle.")),
+        Some(false) => target.send_message(players, String::from("You can already see invisible.")),
+        None => {}
+    }
+}
+
+pub fn spell_blindness(cast: &Cast, players: &Players, world: &mut World) {
+    let Some(target) = cast.target else {
+        return;
+    };
+    // TODO: Saving throws, once there's something to save with
+    if target.affected_by(players, world) & AffectFlag::Blind.bit() != 0 {
+        cast.caster
+            .send_message(players, String::from("You failed."));
+        return;
+    }
+    let affect = Affect {
+        skill: String::from(cast.skill.name),
+        duration: 1 + cast.level as i32,
+        location: Apply::Hitroll,
+        modifier: -4,
+        bits: AffectFlag::Blind.bit(),
+    };
+    target.with_affects(players, world, |list| affects::add(list, affect));
+    target.send_message(players, String::from("You are blinded!"));
+    let name = target.capitalized_name(players, world);
+    tell_room(
+        target,
+        players,
+        world,
+        format!("{} appears to be blinded.", name),
+    );
+}
+
+pub fn spell_invisibility(cast: &Cast, players: &Players, world: &mut World) {
+    let target = cast.target.unwrap_or(cast.caster);
+    // Like ROM, nothing happens to someone who's already invisible
+    if target.affected_by(players, world) & AffectFlag::Invisible.bit() != 0 {
+        return;
+    }
+    let affect = Affect {
+        skill: String::from(cast.skill.name),
+        duration: cast.level as i32 + 12,
+        location: Apply::None,
+        modifier: 0,
+        bits: AffectFlag::Invisible.bit(),
+    };
+    let name = target.capitalized_name(players, world);
+    tell_room(
+        target,
+        players,
+        world,
+        format!("{} fades out of existence.", name),
+    );
+    target.with_affects(players, world, |list| affects::add(list, affect));
+    target.send_message(players, String::from("You fade out of existence."));
+}
+
+pub fn spell_sanctuary(cast: &Cast, players: &Players, world: &mut World) {
+    let target = cast.target.unwrap_or(cast.caster);
+    let name = target.capitalized_name(players, world);
+    if target.affected_by(players, world) & AffectFlag::Sanctuary.bit() != 0 {
+        let message = if target == cast.caster {
+            String::from("You are already in sanctuary.")
+        } else {
+            format!("{} is already in sanctuary.", name)
+        };
+        cast.caster.send_message(players, message);
+        return;
+    }
+    let affect = Affect {
+        skill: String::from(cast.skill.name),
+        duration: cast.level as i32 / 6,
+        location: Apply::None,
+        modifier: 0,
+        bits: AffectFlag::Sanctuary.bit(),
+    };
+    target.with_affects(players, world, |list| affects::add(list, affect));
+    tell_room(
+        target,
+        players,
+        world,
+        format!("{} is surrounded by a white aura.", name),
+    );
+    target.send_message(players, String::from("You are surrounded by a white aura."));
+}
+
+// How much magic missile does by level, from ROM
+const MAGIC_MISSILE_DAMAGE: [i32; 51] = [
+    0, 3, 3, 4, 4, 5, 6, 6, 6, 6, 6, 7, 7, 7, 7, 7, 8, 8, 8, 8, 8, 9, 9, 9, 9, 9, 10, 10, 10, 10,
+    10, 11, 11, 11, 11, 11, 12, 12, 12, 12, 12, 13, 13, 13, 13, 13, 14, 14, 14, 14, 14,
+];
+
+pub fn spell_magic_missile(cast: &Cast, players: &Players, world: &mut World) {
+    // Casting checks the target is a mobile, since players can't be hurt yet
+    let (CharacterId::Player(caster), Some(CharacterId::Mobile(mobile_id))) =
+        (cast.caster, cast.target)
+    else {
+        return;
+    };
+    let level = (cast.level as usize).min(MAGIC_MISSILE_DAMAGE.len() - 1);
+    let base = MAGIC_MISSILE_DAMAGE[level];
+    // TODO: Saving throws halve this in ROM
+    let damage = number_range(base / 2, base * 2);
+    fight::damage_mobile(players, world, caster, mobile_id, damage, cast.skill.noun);
+}
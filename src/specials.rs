@@ -0,0 +1,349 @@
+// Special procedures give mobiles behaviors beyond what the area files can describe. Areas refer to
+// them by name in #SPECIALS, and they're run for each mobile that has one on the mobile pulse.
+use crate::{
+    actions::{capitalize, send_targeted_message},
+    character::CharacterId,
+    objects::{ItemType, WearFlag},
+    player::{PlayerFlag, Players},
+    random::{number_bits, number_range},
+    skills::{self, Cast},
+    world::World,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+// A special procedure is given the ID of the MobileInstance it's running for, and returns true if
+// the mobile did something this pulse
+pub type SpecialFunction = fn(u32, &Players, &mut World) -> bool;
+
+#[derive(Clone, Copy)]
+pub struct Special {
+    pub name: &'static str,
+    pub function: SpecialFunction,
+}
+
+// Specials are saved by name, and have to be one we know about to be loaded
+impl Serialize for Special {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name)
+    }
+}
+
+impl<'de> Deserialize<'de> for Special {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        lookup(&name)
+            .ok_or_else(|| de::Error::custom(format!("unknown special procedure '{}'", name)))
+    }
+}
+
+const SPECIALS: &[Special] = &[
+    Special {
+        name: "spec_cast_adept",
+        function: spec_cast_adept,
+    },
+    Special {
+        name: "spec_executioner",
+        function: spec_executioner,
+    },
+    Special {
+        name: "spec_fido",
+        function: spec_fido,
+    },
+    Special {
+        name: "spec_guard",
+        function: spec_guard,
+    },
+    Special {
+        name: "spec_janitor",
+        function: spec_janitor,
+    },
+    Special {
+        name: "spec_thief",
+        function: spec_thief,
+    },
+    // These only do anything while the mobile is fighting
+    Special {
+        name: "spec_breath_any",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_breath_acid",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_breath_fire",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_breath_frost",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_breath_gas",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_breath_lightning",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_cast_cleric",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_cast_judge",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_cast_mage",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_cast_undead",
+        function: spec_fighting_only,
+    },
+    Special {
+        name: "spec_poison",
+        function: spec_fighting_only,
+    },
+];
+
+pub fn lookup(name: &str) -> Option<Special> {
+    SPECIALS
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+// The room a mobile is in and the name it's known by
+fn mobile_info(mobile_id: u32, world: &World) -> Option<(u32, String)> {
+    world
+        .mobiles
+        .get(&mobile_id)
+        .map(|m| (m.current_room, capitalize(&m.template.room_description)))
+}
+
+fn mobile_level(mobile_id: u32, world: &World) -> i32 {
+    world
+        .mobiles
+        .get(&mobile_id)
+        .map_or(0, |m| i32::try_from(m.template.level).unwrap_or(i32::MAX))
+}
+
+fn send_room_message(players: &Players, room_id: u32, message: String) {
+    send_targeted_message(players, message, |&(_, player)| {
+        player.current_room == room_id
+    });
+}
+
+// TODO: Combat and spells. ROM's versions of these bail out unless the mobile is fighting, which
+// for now is never.
+fn spec_fighting_only(_mobile_id: u32, _players: &Players, _world: &mut World) -> bool {
+    false
+}
+
+// Eat corpses lying around
+fn spec_fido(mobile_id: u32, players: &Players, world: &mut World) -> bool {
+    let Some((room_id, name)) = mobile_info(mobile_id, world) else {
+        return false;
+    };
+    let Some(room) = world.get_room_mut(room_id) else {
+        return false;
+    };
+    let Some(index) = room
+        .objects
+        .iter()
+        .position(|o| o.template.item_type == ItemType::NpcCorpse)
+    else {
+        return false;
+    };
+
+    // TODO: Spill whatever was in the corpse once objects can hold other objects
+    room.objects.remove(index);
+    send_room_message(
+        players,
+        room_id,
+        format!("{} savagely devours a corpse.", name),
+    );
+    true
+}
+
+// Pick up trash and anything else that looks cheap
+fn spec_janitor(mobile_id: u32, players: &Players, world: &mut World) -> bool {
+    let Some((room_id, name)) = mobile_info(mobile_id, world) else {
+        return false;
+    };
+    let Some(room) = world.get_room_mut(room_id) else {
+        return false;
+    };
+    let Some(index) = room.objects.iter().position(|o| {
+        o.template.can_wear(WearFlag::Take)
+            && (matches!(
+                o.template.item_type,
+                ItemType::DrinkContainer | ItemType::Trash
+            ) || o.template.cost < 10)
+    }) else {
+        return false;
+    };
+
+    let trash = room.objects.remove(index);
+    if let Some(mobile) = world.mobiles.get_mut(&mobile_id) {
+        mobile.inventory.push(trash);
+    }
+    send_room_message(players, room_id, format!("{} picks up some trash.", name));
+    true
+}
+
+// Help out new players by casting on them
+fn spec_cast_adept(mobile_id: u32, players: &Players, world: &mut World) -> bool {
+    let Some((room_id, name)) = mobile_info(mobile_id, world) else {
+        return false;
+    };
+    let level = mobile_level(mobile_id, world);
+    let Some(target_id) = players
+        .read()
+        .values()
+        .find(|p| p.current_room == room_id && number_bits(1) == 0)
+        .map(|p| p.id)
+    else {
+        return false;
+    };
+
+    // TODO: ROM's adepts also cast bless and cure blindness, poison and disease, which aren't
+    // spells here yet, and refresh, which is done by hand below
+    let (spell, words) = match number_bits(2) {
+        0 => (skills::lookup("armor"), "abrazak"),
+        1 | 2 => (skills::lookup("cure light"), "judicandus dies"),
+        _ => (None, "candusima"),
+    };
+    send_room_message(
+        players,
+        room_id,
+        format!("{} utters the word '{}'.", name, words),
+    );
+
+    match spell {
+        Some(skill) => {
+            if let Some(function) = skill.spell {
+                let cast = Cast {
+                    skill,
+                    level: level.unsigned_abs(),
+                    caster: CharacterId::Mobile(mobile_id),
+                    target: Some(CharacterId::Player(target_id)),
+                };
+                function(&cast, players, world);
+            }
+        }
+        None => {
+            if let Some(target) = players.write().get_mut(&target_id) {
+                let max_move = target.stats().max_move;
+                target.movement = target.movement.saturating_add(level).min(max_move);
+                target.send_message(String::from("You feel less tired."));
+            }
+        }
+    }
+    true
+}
+
+// Steal money from players
+fn spec_thief(mobile_id: u32, players: &Players, world: &mut World) -> bool {
+    let Some((room_id, name)) = mobile_info(mobile_id, world) else {
+        return false;
+    };
+    let level = mobile_level(mobile_id, world);
+
+    let (caught_message, stolen) = {
+        let mut guard = players.write();
+        let Some(victim) = guard
+            .values_mut()
+            .find(|p| p.current_room == room_id && number_bits(5) == 0)
+        else {
+            return false;
+        };
+
+        if number_range(0, level) == 0 {
+            victim.send_message(format!("You discover {}'s hands in your wallet!", name));
+            let message = format!(
+                "{} discovers {}'s hands in their wallet!",
+                victim.username, name
+            );
+            (Some((victim.id, message)), (0, 0))
+        } else {
+            // Take a small cut of what they're carrying, without them noticing
+            let percent = number_range(1, 20).min(level / 2).max(0) as u32;
+            let limit = level.unsigned_abs().saturating_mul(level.unsigned_abs());
+            let gold = (victim.gold * percent / 100).min(limit.saturating_mul(10));
+            let silver = (victim.silver * percent / 100).min(limit.saturating_mul(25));
+            victim.gold -= gold;
+            victim.silver -= silver;
+            (None, (gold, silver))
+        }
+    };
+
+    if let Some((victim_id, message)) = caught_message {
+        send_targeted_message(players, message, |&(id, player)| {
+            player.current_room == room_id && *id != victim_id
+        });
+    }
+    if let Some(mobile) = world.mobiles.get_mut(&mobile_id) {
+        mobile.gold += stolen.0;
+        mobile.silver += stolen.1;
+    }
+    true
+}
+
+fn spec_guard(mobile_id: u32, players: &Players, world: &mut World) -> bool {
+    punish_criminals(
+        mobile_id,
+        players,
+        world,
+        "PROTECT THE INNOCENT!!  BANZAI!!",
+    )
+}
+
+fn spec_executioner(mobile_id: u32, players: &Players, world: &mut World) -> bool {
+    punish_criminals(
+        mobile_id,
+        players,
+        world,
+        "PROTECT THE INNOCENT!  MORE BLOOOOD!!!",
+    )
+}
+
+// Call out any killers or thieves in the room
+fn punish_criminals(
+    mobile_id: u32,
+    players: &Players,
+    world: &mut World,
+    battle_cry: &str,
+) -> bool {
+    let Some((room_id, name)) = mobile_info(mobile_id, world) else {
+        return false;
+    };
+
+    let crime = players.read().values().find_map(|p| {
+        if p.current_room != room_id {
+            None
+        } else if p.has_flag(PlayerFlag::Killer) {
+            Some((p.username.clone(), "KILLER"))
+        } else if p.has_flag(PlayerFlag::Thief) {
+            Some((p.username.clone(), "THIEF"))
+        } else {
+            None
+        }
+    });
+    let Some((criminal, crime)) = crime else {
+        return false;
+    };
+
+    // TODO: Attack them once there's combat
+    send_room_message(
+        players,
+        room_id,
+        format!(
+            "{} screams '{} is a {}!  {}'",
+            name, criminal, crime, battle_cry
+        ),
+    );
+    true
+}
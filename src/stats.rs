@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+
+// Character statistics and the things that can modify them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Stats {
+    pub strength: i32,
+    pub intelligence: i32,
+    pub wisdom: i32,
+    pub dexterity: i32,
+    pub constitution: i32,
+    pub max_hit: i32,
+    pub max_mana: i32,
+    pub max_move: i32,
+    pub hitroll: i32,
+    pub damroll: i32,
+    pub armor: i32,
+    pub saves: i32,
+}
+
+impl Stats {
+    // Starting values for a freshly created player, same as stock ROM
+    pub fn new_player() -> Self {
+        Stats {
+            strength: 13,
+            intelligence: 13,
+            wisdom: 13,
+            dexterity: 13,
+            constitution: 13,
+            max_hit: 20,
+            max_mana: 100,
+            max_move: 100,
+            hitroll: 0,
+            damroll: 0,
+            armor: 100,
+            saves: 0,
+        }
+    }
+
+    pub fn apply(&mut self, location: Apply, modifier: i32) {
+        match location {
+            Apply::Strength => self.strength += modifier,
+            Apply::Dexterity => self.dexterity += modifier,
+            Apply::Intelligence => self.intelligence += modifier,
+            Apply::Wisdom => self.wisdom += modifier,
+            Apply::Constitution => self.constitution += modifier,
+            Apply::Mana => self.max_mana += modifier,
+            Apply::Hit => self.max_hit += modifier,
+            Apply::Move => self.max_move += modifier,
+            Apply::ArmorClass => self.armor += modifier,
+            Apply::Hitroll => self.hitroll += modifier,
+            Apply::Damroll => self.damroll += modifier,
+            Apply::Saves
+            | Apply::SavingRod
+            | Apply::SavingPetrification
+            | Apply::SavingBreath
+            | Apply::SavingSpell => self.saves += modifier,
+            // TODO: The rest of these need systems we don't have yet (gold, experience, etc.)
+            _ => {
+                tracing::trace!("Ignoring unsupported apply {}", location);
+            }
+        }
+    }
+}
+
+// Locations an affect can modify, numbered the same as the APPLY_* constants in ROM so that we can
+// read them straight out of area files
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    Deserialize,
+    Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Apply {
+    #[strum(serialize = "none")]
+    None,
+    #[strum(serialize = "strength")]
+    Strength,
+    #[strum(serialize = "dexterity")]
+    Dexterity,
+    #[strum(serialize = "intelligence")]
+    Intelligence,
+    #[strum(serialize = "wisdom")]
+    Wisdom,
+    #[strum(serialize = "constitution")]
+    Constitution,
+    #[strum(serialize = "sex")]
+    Sex,
+    #[strum(serialize = "class")]
+    Class,
+    #[strum(serialize = "level")]
+    Level,
+    #[strum(serialize = "age")]
+    Age,
+    #[strum(serialize = "height")]
+    Height,
+    #[strum(serialize = "weight")]
+    Weight,
+    #[strum(serialize = "mana")]
+    Mana,
+    #[strum(serialize = "hp")]
+    Hit,
+    #[strum(serialize = "moves")]
+    Move,
+    #[strum(serialize = "gold")]
+    Gold,
+    #[strum(serialize = "experience")]
+    Experience,
+    #[strum(serialize = "armor class")]
+    ArmorClass,
+    #[strum(serialize = "hit roll")]
+    Hitroll,
+    #[strum(serialize = "damage roll")]
+    Damroll,
+    #[strum(serialize = "saves")]
+    Saves,
+    #[strum(serialize = "save vs rod")]
+    SavingRod,
+    #[strum(serialize = "save vs petrification")]
+    SavingPetrification,
+    #[strum(serialize = "save vs breath")]
+    SavingBreath,
+    #[strum(serialize = "save vs spell")]
+    SavingSpell,
+    #[strum(serialize = "spell affect")]
+    SpellAffect,
+}
+
+impl Apply {
+    // The variants are in ROM's order
+    pub fn to_rom(self) -> i64 {
+        self as i64
+    }
+
+    pub fn from_rom(location: i64) -> Option<Apply> {
+        let apply = match location {
+            0 => Apply::None,
+            1 => Apply::Strength,
+            2 => Apply::Dexterity,
+            3 => Apply::Intelligence,
+            4 => Apply::Wisdom,
+            5 => Apply::Constitution,
+            6 => Apply::Sex,
+            7 => Apply::Class,
+            8 => Apply::Level,
+            9 => Apply::Age,
+            10 => Apply::Height,
+            11 => Apply::Weight,
+            12 => Apply::Mana,
+            13 => Apply::Hit,
+            14 => Apply::Move,
+            15 => Apply::Gold,
+            16 => Apply::Experience,
+            17 => Apply::ArmorClass,
+            18 => Apply::Hitroll,
+            19 => Apply::Damroll,
+            20 => Apply::Saves,
+            21 => Apply::SavingRod,
+            22 => Apply::SavingPetrification,
+            23 => Apply::SavingBreath,
+            24 => Apply::SavingSpell,
+            25 => Apply::SpellAffect,
+            _ => return None,
+        };
+        Some(apply)
+    }
+}
@@ -0,0 +1,41 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Like ROM, the game loop pulses four times a second, and players get to run one command a pulse
+pub const PULSES_PER_SECOND: u64 = 4;
+// How many pulses of the game loop make up an hour of game time. As in ROM, an hour passes every
+// minute.
+pub const PULSES_PER_HOUR: u64 = 60 * PULSES_PER_SECOND;
+
+pub struct GameTime {
+    pulse: u64,
+    pub hour: u32,
+}
+
+impl GameTime {
+    pub fn new() -> Self {
+        // Like ROM, base the starting hour on the real time so that restarts don't always begin
+        // at the same time of day
+        let minutes = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() / 60);
+        GameTime {
+            pulse: 0,
+            hour: (minutes % 24) as u32,
+        }
+    }
+
+    pub fn pulse(&self) -> u64 {
+        self.pulse
+    }
+
+    // Count a pulse, returning true if it started a new hour
+    pub fn advance(&mut self) -> bool {
+        self.pulse += 1;
+        if self.pulse.is_multiple_of(PULSES_PER_HOUR) {
+            self.hour = (self.hour + 1) % 24;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// Checks run once every area is loaded, for mistakes that only show up when areas are put
+// together: the same vnum used by two areas, vnums outside the range an area claims, and exits or
+// resets pointing at things that were never loaded
+use crate::{area::Area, programs::ProgramKind, reset::ResetCommand, world::World};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+// What to do when the checks find something
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationPolicy {
+    // Refuse to start
+    Error,
+    // Log it and carry on. Lookups use whichever area was loaded first.
+    #[default]
+    Warn,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum VnumKind {
+    Room,
+    Mobile,
+    Object,
+    // Each kind of program has its own vnums
+    #[strum(serialize = "mobile program")]
+    MobileProgram,
+    #[strum(serialize = "object program")]
+    ObjectProgram,
+    #[strum(serialize = "room program")]
+    RoomProgram,
+}
+
+impl VnumKind {
+    fn is_program(self) -> bool {
+        matches!(
+            self,
+            VnumKind::MobileProgram | VnumKind::ObjectProgram | VnumKind::RoomProgram
+        )
+    }
+}
+
+impl From<ProgramKind> for VnumKind {
+    fn from(kind: ProgramKind) -> Self {
+        match kind {
+            ProgramKind::Mobile => VnumKind::MobileProgram,
+            ProgramKind::Object => VnumKind::ObjectProgram,
+            ProgramKind::Room => VnumKind::RoomProgram,
+        }
+    }
+}
+
+pub enum Problem {
+    Duplicate {
+        kind: VnumKind,
+        vnum: u32,
+        first: String,
+        second: String,
+    },
+    OutOfRange {
+        kind: VnumKind,
+        vnum: u32,
+        area: String,
+        range: (u32, u32),
+    },
+    MissingExit {
+        area: String,
+        room: u32,
+        direction: String,
+        destination: u32,
+    },
+    MissingReset {
+        area: String,
+        kind: VnumKind,
+        vnum: u32,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Duplicate {
+                kind,
+                vnum,
+                first,
+                second,
+            } => write!(f, "{} {} is in both {} and {}", kind, vnum, first, second),
+            Problem::OutOfRange {
+                kind,
+                vnum,
+                area,
+                range,
+            } => write!(
+                f,
+                "{} {} in {} is outside its vnum range {}-{}",
+                kind, vnum, area, range.0, range.1
+            ),
+            Problem::MissingExit {
+                area,
+                room,
+                direction,
+                destination,
+            } => write!(
+                f,
+                "Exit {} from room {} in {} leads to room {}, which isn't in any loaded area",
+                direction, room, area, destination
+            ),
+            Problem::MissingReset { area, kind, vnum } => write!(
+                f,
+                "A reset in {} refers to {} {}, which isn't in any loaded area",
+                area, kind, vnum
+            ),
+        }
+    }
+}
+
+// How to refer to an area in a problem, preferring the file it came from
+fn source(area: &Area) -> String {
+    match &area.metadata.file {
+        Some(file) => file.display().to_string(),
+        None => area.metadata.name.clone(),
+    }
+}
+
+fn vnums(area: &Area) -> impl Iterator<Item = (VnumKind, u32)> + '_ {
+    let rooms = area.rooms.keys().map(|&v| (VnumKind::Room, v));
+    let mobiles = area.mobiles.keys().map(|&v| (VnumKind::Mobile, v));
+    let objects = area.objects.keys().map(|&v| (VnumKind::Object, v));
+    let programs = area
+        .programs
+        .keys()
+        .map(|&(kind, v)| (VnumKind::from(kind), v));
+    rooms.chain(mobiles).chain(objects).chain(programs)
+}
+
+// The rooms, mobiles and objects a reset uses
+pub fn references(reset: &ResetCommand) -> Vec<(VnumKind, u32)> {
+    match *reset {
+        ResetCommand::LoadMobile {
+            mobile_id, room_id, ..
+        } => vec![(VnumKind::Mobile, mobile_id), (VnumKind::Room, room_id)],
+        ResetCommand::LoadObject { object_id, room_id } => {
+            vec![(VnumKind::Object, object_id), (VnumKind::Room, room_id)]
+        }
+        ResetCommand::GiveObject { object_id } | ResetCommand::EquipObject { object_id, .. } => {
+            vec![(VnumKind::Object, object_id)]
+        }
+        ResetCommand::PutObject {
+            object_id,
+            container_id,
+            ..
+        } => vec![
+            (VnumKind::Object, object_id),
+            (VnumKind::Object, container_id),
+        ],
+        ResetCommand::SetDoor { room_id, .. } | ResetCommand::RandomizeExits { room_id, .. } => {
+            vec![(VnumKind::Room, room_id)]
+        }
+    }
+}
+
+pub fn check_world(world: &World) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    // Which area each vnum was first seen in
+    let mut owners: HashMap<(VnumKind, u32), &Area> = HashMap::new();
+    for area in world.areas() {
+        let (low, high) = area.metadata.vnums;
+        let mut area_vnums: Vec<_> = vnums(area).collect();
+        area_vnums.sort_by_key(|&(kind, vnum)| (vnum, kind as u8));
+
+        for (kind, vnum) in area_vnums {
+            if let Some(first) = owners.get(&(kind, vnum)) {
+                problems.push(Problem::Duplicate {
+                    kind,
+                    vnum,
+                    first: source(first),
+                    second: source(area),
+                });
+            } else {
+                owners.insert((kind, vnum), area);
+            }
+
+            // Programs don't have to follow the area's range
+            if !kind.is_program() && (vnum < low || vnum > high) {
+                problems.push(Problem::OutOfRange {
+                    kind,
+                    vnum,
+                    area: source(area),
+                    range: (low, high),
+                });
+            }
+        }
+    }
+
+    let loaded = |kind, vnum| owners.contains_key(&(kind, vnum));
+    for area in world.areas() {
+        let mut rooms: Vec<_> = area.rooms.values().collect();
+        rooms.sort_by_key(|r| r.id);
+        for room in rooms {
+            for (direction, &destination) in &room.exits {
+                if !loaded(VnumKind::Room, destination) {
+                    problems.push(Problem::MissingExit {
+                        area: source(area),
+                        room: room.id,
+                        direction: direction.clone(),
+                        destination,
+                    });
+                }
+            }
+        }
+
+        for reset in &area.resets {
+            for (kind, vnum) in references(reset) {
+                if !loaded(kind, vnum) {
+                    problems.push(Problem::MissingReset {
+                        area: source(area),
+                        kind,
+                        vnum,
+                    });
+                }
+            }
+        }
+    }
+
+    problems
+}
@@ -1,87 +1,395 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-use crate::area::Area;
+use crate::area::{Area, AreaMetadata};
+use crate::creation::CreationData;
+use crate::equipment::Equipment;
 use crate::mobiles::{Mobile, MobileInstance};
+use crate::objects::{ExtraFlag, Object, ObjectInstance};
 use crate::player::Player;
+use crate::position::Position;
+use crate::programs::{MobProgram, ProgramKind};
+use crate::random::number_range;
 use crate::reset::ResetCommand;
 use crate::room::{get_sample_rooms, Room};
+use crate::time::GameTime;
+
+// Areas are referred to by their position in the world's list, which doesn't change when one is
+// reloaded
+pub type AreaId = usize;
+
+// Mobiles don't have movement dice, so they all get ROM's default
+const MOBILE_MOVE: i32 = 100;
 
 // TODO: We might want to do something similar to what we did to the Players struct in terms of
 // making it a wrapper around an Arc/RwLock. That is, if we ever need something other than the game
 // loop to update the world. One example could be if we make the `tick` function do stuff to the
 // world.
 pub struct World {
-    rooms: HashMap<u32, Room>,
-    mobile_templates: HashMap<u32, Mobile>,
+    // Each area owns its rooms, templates and resets
+    areas: Vec<Area>,
     // TODO: Better accessing...
     pub mobiles: HashMap<u32, MobileInstance>,
-    resets: Vec<ResetCommand>,
+    pub time: GameTime,
+    // The vnums each builder is allowed to edit, by username
+    pub builders: HashMap<String, (u32, u32)>,
+    // Where players go when the room they're in stops existing
+    pub recall_vnum: u32,
+    // Where players are saved
+    pub player_directory: PathBuf,
+    // The races and classes players are made of
+    pub creation: Arc<CreationData>,
 }
 
 impl World {
     pub fn new() -> Self {
         World {
-            rooms: HashMap::new(),
-            mobile_templates: HashMap::new(),
+            areas: Vec::new(),
             mobiles: HashMap::new(),
-            resets: Vec::new(),
+            time: GameTime::new(),
+            builders: HashMap::new(),
+            recall_vnum: 3001,
+            player_directory: PathBuf::from("players"),
+            creation: Arc::new(CreationData::default()),
         }
     }
 
     pub fn reset(&mut self) {
+        for area_id in 0..self.areas.len() {
+            self.reset_area(area_id);
+        }
+    }
+
+    pub fn reset_area(&mut self, area_id: AreaId) {
+        let Some(area) = self.areas.get_mut(area_id) else {
+            return;
+        };
+        area.age = 0;
+        tracing::info!(area = area.metadata.name, "Resetting area");
+
+        // Give and equip resets apply to whichever mobile was loaded most recently, and are skipped
+        // if it wasn't
+        let mut last_mobile: Option<u32> = None;
+
         // Perform any resets
-        for reset in &self.resets {
-            tracing::info!("Performing reset {:?}", reset);
-            // TODO: Check if we actually need to run the reset
-            let template = self.mobile_templates.get(&reset.mobile_id).unwrap().clone();
-            let target_room = reset.room_id;
-
-            // Generate a unique ID for the MobileInstance
-            let mut id = 1;
-            while self.mobiles.contains_key(&id) {
-                id += 1;
+        for reset in area.resets.clone() {
+            tracing::debug!("Performing reset {:?}", reset);
+            match reset {
+                ResetCommand::LoadMobile {
+                    mobile_id,
+                    room_id,
+                    world_limit,
+                    room_limit,
+                } => {
+                    let in_world = self.mobiles.values().filter(|m| m.template.id == mobile_id);
+                    let in_room = in_world.clone().filter(|m| m.current_room == room_id);
+                    if world_limit.is_some_and(|limit| in_world.count() >= limit as usize)
+                        || room_limit.is_some_and(|limit| in_room.count() >= limit as usize)
+                    {
+                        last_mobile = None;
+                        continue;
+                    }
+
+                    last_mobile = self.spawn_mobile(mobile_id, room_id);
+                    if last_mobile.is_none() {
+                        tracing::warn!("Reset references unknown mobile {}", mobile_id);
+                    }
+                }
+                ResetCommand::LoadObject { object_id, room_id } => {
+                    let Some(room) = self.get_room(room_id) else {
+                        tracing::warn!("Reset references unknown room {}", room_id);
+                        continue;
+                    };
+                    if room.objects.iter().any(|o| o.template.id == object_id) {
+                        continue;
+                    }
+                    if let Some(object) = self.create_object(object_id) {
+                        if let Some(room) = self.get_room_mut(room_id) {
+                            room.objects.push(object);
+                        }
+                    }
+                }
+                ResetCommand::GiveObject { object_id } => {
+                    let Some(mobile_id) = last_mobile else {
+                        continue;
+                    };
+                    if let Some(mut object) = self.create_object(object_id) {
+                        if let Some(mobile) = self.mobiles.get_mut(&mobile_id) {
+                            // Anything a shopkeeper is given is stock they never run out of
+                            if mobile.template.shop.is_some() {
+                                object.template.extra_flags |= ExtraFlag::Inventory.bit();
+                            }
+                            mobile.inventory.push(object);
+                        }
+                    }
+                }
+                ResetCommand::EquipObject {
+                    object_id,
+                    wear_location,
+                } => {
+                    let Some(mobile_id) = last_mobile else {
+                        continue;
+                    };
+                    if let Some(object) = self.create_object(object_id) {
+                        if let Some(mobile) = self.mobiles.get_mut(&mobile_id) {
+                            if let Some(previous) = mobile.equipment.equip(wear_location, object) {
+                                mobile.inventory.push(previous);
+                            }
+                        }
+                    }
+                }
+                // TODO: Containers, doors and mazes. These are only kept so the area can be saved.
+                ResetCommand::PutObject { .. }
+                | ResetCommand::SetDoor { .. }
+                | ResetCommand::RandomizeExits { .. } => {}
             }
+        }
+    }
+
+    // Create a new instance of a mobile from its template, returning the ID of the MobileInstance
+    pub fn spawn_mobile(&mut self, mobile_id: u32, room_id: u32) -> Option<u32> {
+        let template = self.get_mobile_template(mobile_id).cloned()?;
+
+        // Generate a unique ID for the MobileInstance
+        let mut id = 1;
+        while self.mobiles.contains_key(&id) {
+            id += 1;
+        }
+
+        let position = Position::from_word(&template.start_position).unwrap_or_default();
+        // Like ROM, every mobile gets its own roll of the dice
+        let max_hit = template.hit_dice.roll().max(1);
+        let max_mana = template.mana_dice.roll().max(0);
+        let (gold, silver) = starting_money(template.wealth);
+        let mi = MobileInstance {
+            id: id,
+            template: template,
+            current_room: room_id,
+            inventory: Vec::new(),
+            equipment: Equipment::new(),
+            gold,
+            silver,
+            hunting: None,
+            position,
+            hit: max_hit,
+            max_hit,
+            mana: max_mana,
+            max_mana,
+            movement: MOBILE_MOVE,
+            max_move: MOBILE_MOVE,
+            affects: Vec::new(),
+        };
+
+        // TODO: Add check here that we're not inserting into an already used ID
+        self.mobiles.insert(mi.id, mi);
+        Some(id)
+    }
+
+    // Create a new instance of an object from its template
+    pub fn create_object(&mut self, object_id: u32) -> Option<ObjectInstance> {
+        let Some(template) = self.get_object_template(object_id).cloned() else {
+            tracing::warn!("Tried to create unknown object {}", object_id);
+            return None;
+        };
+
+        Some(ObjectInstance { template })
+    }
+
+    pub fn add_area(&mut self, area: Area) -> AreaId {
+        tracing::info!(
+            area = area.metadata.name,
+            rooms = area.rooms.len(),
+            mobiles = area.mobiles.len(),
+            objects = area.objects.len(),
+            "Adding area to world"
+        );
+        self.areas.push(area);
+        self.areas.len() - 1
+    }
+
+    // Swap in a freshly loaded copy of an area, then reset it. Mobiles and objects already in the
+    // world are left alone, except for mobiles in rooms the new copy doesn't have, which are
+    // removed. Returns those rooms, so the caller can move any players out of them.
+    pub fn reload_area(&mut self, area_id: AreaId, mut area: Area) -> Vec<u32> {
+        let Some(old) = self.areas.get_mut(area_id) else {
+            return Vec::new();
+        };
+        for (room_id, room) in area.rooms.iter_mut() {
+            if let Some(old_room) = old.rooms.get_mut(room_id) {
+                room.objects = std::mem::take(&mut old_room.objects);
+            }
+        }
+        let vanished: Vec<u32> = old
+            .rooms
+            .keys()
+            .filter(|id| !area.rooms.contains_key(id))
+            .copied()
+            .collect();
+        area.metadata.file = area.metadata.file.or(old.metadata.file.take());
+        area.metadata.format = area.metadata.format.or(old.metadata.format);
+        *old = area;
+
+        self.mobiles
+            .retain(|_, mobile| !vanished.contains(&mobile.current_room));
+        self.reset_area(area_id);
+        vanished
+    }
+
+    pub fn areas(&self) -> &[Area] {
+        &self.areas
+    }
+
+    pub fn get_area(&self, area_id: AreaId) -> Option<&Area> {
+        self.areas.get(area_id)
+    }
+
+    pub fn get_area_mut(&mut self, area_id: AreaId) -> Option<&mut Area> {
+        self.areas.get_mut(area_id)
+    }
+
+    // Look up an area by (the start of) its name
+    pub fn find_area(&self, name: &str) -> Option<AreaId> {
+        let name = name.to_lowercase();
+        self.areas
+            .iter()
+            .position(|a| a.metadata.name.to_lowercase().starts_with(&name))
+    }
+
+    // The area a room belongs to
+    pub fn room_area(&self, room_id: u32) -> Option<AreaId> {
+        self.areas
+            .iter()
+            .position(|a| a.rooms.contains_key(&room_id))
+    }
 
-            let mi = MobileInstance {
-                id: id,
-                template: template,
-                current_room: target_room,
-            };
+    // The area whose range a vnum falls in, which is where anything new with that vnum belongs
+    pub fn vnum_area(&self, vnum: u32) -> Option<AreaId> {
+        self.areas.iter().position(|a| {
+            let (low, high) = a.metadata.vnums;
+            (low..=high).contains(&vnum)
+        })
+    }
 
-            // TODO: Add check here that we're not inserting into an already used ID
-            self.mobiles.insert(mi.id, mi);
+    // The vnums a player can edit, if they're a builder. Usernames are matched ignoring case.
+    fn builder_vnums(&self, username: &str) -> Option<(u32, u32)> {
+        self.builders
+            .iter()
+            .find(|(builder, _)| builder.eq_ignore_ascii_case(username))
+            .map(|(_, &vnums)| vnums)
+    }
+
+    pub fn is_builder(&self, username: &str) -> bool {
+        self.builder_vnums(username).is_some()
+    }
+
+    // Whether a player can edit things with this vnum
+    pub fn can_build(&self, username: &str, vnum: u32) -> bool {
+        self.builder_vnums(username)
+            .is_some_and(|(low, high)| (low..=high).contains(&vnum))
+    }
+
+    // Whether a player can edit anything in an area
+    pub fn can_build_area(&self, username: &str, area_id: AreaId) -> bool {
+        let Some(area) = self.areas.get(area_id) else {
+            return false;
+        };
+        let (low, high) = area.metadata.vnums;
+        self.builder_vnums(username)
+            .is_some_and(|(from, to)| from <= high && low <= to)
+    }
+
+    // Note that an area needs saving because something with this vnum was edited
+    pub fn mark_changed(&mut self, vnum: u32) {
+        let owner = self
+            .areas
+            .iter()
+            .position(|a| {
+                a.rooms.contains_key(&vnum)
+                    || a.mobiles.contains_key(&vnum)
+                    || a.objects.contains_key(&vnum)
+            })
+            .or_else(|| self.vnum_area(vnum));
+        if let Some(area) = owner.and_then(|id| self.areas.get_mut(id)) {
+            area.changed = true;
         }
     }
 
-    pub fn add_area(&mut self, area: Area) {
-        // TODO: Right now we just put all the areas into one "scope"
-        for r in area.rooms {
-            self.rooms.insert(r.id, r);
+    // Make a new, empty room in whichever area covers the vnum
+    pub fn create_room(&mut self, vnum: u32) -> Result<(), String> {
+        if self.get_room(vnum).is_some() {
+            return Err(format!("Room {} already exists.", vnum));
         }
+        let Some(area) = self.vnum_area(vnum).and_then(|id| self.areas.get_mut(id)) else {
+            return Err(format!("Vnum {} isn't in any area.", vnum));
+        };
+        area.add_room(Room::new(vnum, "A new room", ""));
+        area.changed = true;
+        Ok(())
+    }
 
-        for m in area.mobiles {
-            self.mobile_templates.insert(m.id, m);
+    // Make a new mobile template in whichever area covers the vnum
+    pub fn create_mobile_template(&mut self, vnum: u32) -> Result<(), String> {
+        if self.get_mobile_template(vnum).is_some() {
+            return Err(format!("Mobile {} already exists.", vnum));
         }
+        let Some(area) = self.vnum_area(vnum).and_then(|id| self.areas.get_mut(id)) else {
+            return Err(format!("Vnum {} isn't in any area.", vnum));
+        };
+        area.add_mobile(Mobile::new(vnum));
+        area.changed = true;
+        Ok(())
+    }
 
-        for rc in area.resets {
-            self.resets.push(rc);
+    // Make a new object template in whichever area covers the vnum
+    pub fn create_object_template(&mut self, vnum: u32) -> Result<(), String> {
+        if self.get_object_template(vnum).is_some() {
+            return Err(format!("Object {} already exists.", vnum));
         }
+        let Some(area) = self.vnum_area(vnum).and_then(|id| self.areas.get_mut(id)) else {
+            return Err(format!("Vnum {} isn't in any area.", vnum));
+        };
+        area.add_object(Object::new(vnum));
+        area.changed = true;
+        Ok(())
+    }
+
+    pub fn get_mobile_template(&self, mobile_id: u32) -> Option<&Mobile> {
+        self.areas.iter().find_map(|a| a.mobiles.get(&mobile_id))
+    }
+
+    // Editing a template only changes mobiles spawned from it afterwards
+    pub fn get_mobile_template_mut(&mut self, mobile_id: u32) -> Option<&mut Mobile> {
+        self.areas
+            .iter_mut()
+            .find_map(|a| a.mobiles.get_mut(&mobile_id))
     }
 
-    pub fn add_room(&mut self, room: Room) {
-        self.rooms.insert(room.id, room);
+    pub fn get_object_template(&self, object_id: u32) -> Option<&Object> {
+        self.areas.iter().find_map(|a| a.objects.get(&object_id))
     }
 
-    pub fn add_mobile_template(&mut self, mobile: Mobile) {
-        self.mobile_templates.insert(mobile.id, mobile);
+    // Editing a template only changes objects created from it afterwards
+    pub fn get_object_template_mut(&mut self, object_id: u32) -> Option<&mut Object> {
+        self.areas
+            .iter_mut()
+            .find_map(|a| a.objects.get_mut(&object_id))
     }
 
-    pub fn add_reset(&mut self, reset: ResetCommand) {
-        self.resets.push(reset);
+    pub fn get_program(&self, kind: ProgramKind, vnum: u32) -> Option<&MobProgram> {
+        self.areas
+            .iter()
+            .find_map(|a| a.programs.get(&(kind, vnum)))
     }
 
     pub fn get_room(&self, room_id: u32) -> Option<&Room> {
-        self.rooms.get(&room_id)
+        self.areas.iter().find_map(|a| a.rooms.get(&room_id))
+    }
+
+    pub fn get_room_mut(&mut self, room_id: u32) -> Option<&mut Room> {
+        self.areas
+            .iter_mut()
+            .find_map(|a| a.rooms.get_mut(&room_id))
     }
 
     pub fn get_player_room(&self, player: &Player) -> Option<&Room> {
@@ -95,13 +403,35 @@ impl World {
     }
 }
 
-pub fn get_sample_world() -> World {
-    let rooms = get_sample_rooms();
+// Like ROM, mobiles carry their template's wealth give or take half, some of it in gold. Returns
+// the gold and silver.
+fn starting_money(wealth: i64) -> (u32, u32) {
+    let wealth = i32::try_from(wealth.clamp(0, i64::from(i32::MAX / 3))).unwrap_or(0);
+    let wealth = number_range(wealth / 2, wealth * 3 / 2);
+    let gold = number_range(wealth / 200, wealth / 100);
+    let silver = wealth - gold * 100;
+    (
+        u32::try_from(gold).unwrap_or(0),
+        u32::try_from(silver).unwrap_or(0),
+    )
+}
 
-    let mut world = World::new();
-    for room in rooms {
-        world.add_room(room);
+pub fn get_sample_world() -> World {
+    let mut area = Area::new(AreaMetadata {
+        name: String::from("Sample"),
+        author: String::from("nostalgia_mud"),
+        vnums: (1, 10),
+        levels: None,
+        file: None,
+        format: None,
+        credits: None,
+        areadata: None,
+    });
+    for room in get_sample_rooms() {
+        area.add_room(room);
     }
 
+    let mut world = World::new();
+    world.add_area(area);
     world
 }